@@ -0,0 +1,17 @@
+use fast_image_resize::pixels::I32;
+use utils::testing::PixelTestingExt;
+
+mod utils;
+
+pub fn bench_downscale_l32(bench_group: &mut utils::BenchGroup) {
+    type P = I32;
+    let src_image = P::load_big_image();
+    utils::image_resize(bench_group, &src_image);
+    utils::libvips_resize::<P>(bench_group, false);
+    utils::fir_resize::<P>(bench_group, false);
+}
+
+fn main() {
+    let res = utils::run_bench(bench_downscale_l32, "Compare resize of L32 image");
+    utils::print_and_write_compare_result(&res);
+}
@@ -54,7 +54,7 @@ fn downscale_bench(
     let mut res_image = Image::new(dst_width, dst_height, image.pixel_type());
     let mut resizer = Resizer::new();
     let options = ResizeOptions::new()
-        .resize_alg(ResizeAlg::Convolution(filter_type))
+        .resize_alg(ResizeAlg::Convolution(filter_type.clone()))
         .use_alpha(false);
     unsafe {
         resizer.set_cpu_extensions(cpu_extensions);
@@ -73,6 +73,33 @@ fn downscale_bench(
     );
 }
 
+fn integer_box_u8x4_bench(bench_group: &mut utils::BenchGroup, cpu_extensions: CpuExtensions) {
+    let src_image = U8x4::load_big_square_src_image();
+    let (dst_width, dst_height) = (src_image.width() / 2, src_image.height() / 2);
+    let mut dst_image = Image::new(dst_width, dst_height, PixelType::U8x4);
+    let mut resizer = Resizer::new();
+    let options = ResizeOptions::new()
+        .resize_alg(ResizeAlg::IntegerBox)
+        .crop(0., 0., (dst_width * 2) as _, (dst_height * 2) as _)
+        .use_alpha(false);
+    unsafe {
+        resizer.set_cpu_extensions(cpu_extensions);
+    }
+    utils::bench(
+        bench_group,
+        100,
+        "U8x4 IntegerBox 2x",
+        cpu_ext_into_str(cpu_extensions),
+        |bencher| {
+            bencher.iter(|| {
+                resizer
+                    .resize(&src_image, &mut dst_image, &options)
+                    .unwrap()
+            })
+        },
+    );
+}
+
 pub fn resize_in_one_dimension_bench(bench_group: &mut utils::BenchGroup) {
     let pixel_types = [
         PixelType::U8,
@@ -222,11 +249,16 @@ pub fn resize_bench(bench_group: &mut utils::BenchGroup) {
         }
     }
 
+    for &cpu_extension in cpu_extensions.iter() {
+        integer_box_u8x4_bench(bench_group, cpu_extension);
+    }
     native_nearest_u8x4_bench(bench_group);
     #[cfg(not(feature = "only_u8x4"))]
     native_nearest_u8_bench(bench_group);
 }
 
+// Alternative entry point to run the full set of benchmarks.
+#[allow(dead_code)]
 fn main1() {
     let results = utils::run_bench(resize_bench, "Resize");
     println!("{}", utils::build_md_table(&results));
@@ -0,0 +1,66 @@
+use fast_image_resize::images::Image;
+use fast_image_resize::pixels::U8x4;
+use fast_image_resize::{AffineMatrix, FilterType, ResizeAlg, ResizeOptions, Resizer};
+use utils::testing::PixelTestingExt;
+
+mod utils;
+
+const DST_WIDTH: u32 = 852;
+const DST_HEIGHT: u32 = 567;
+
+/// Returns the matrix that maps the destination image into the source image
+/// rotated by `angle` degrees around its center and downscaled to the size
+/// of the destination image.
+fn rotation_matrix(src_image: &Image, angle: f64) -> AffineMatrix {
+    let scale_x = src_image.width() as f64 / DST_WIDTH as f64;
+    let scale_y = src_image.height() as f64 / DST_HEIGHT as f64;
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let (dst_cx, dst_cy) = (DST_WIDTH as f64 / 2., DST_HEIGHT as f64 / 2.);
+    let (src_cx, src_cy) = (
+        src_image.width() as f64 / 2.,
+        src_image.height() as f64 / 2.,
+    );
+    let (a, b) = (cos * scale_x, -sin * scale_x);
+    let (d, e) = (sin * scale_y, cos * scale_y);
+    [
+        [a, b, src_cx - a * dst_cx - b * dst_cy],
+        [d, e, src_cy - d * dst_cx - e * dst_cy],
+    ]
+}
+
+pub fn bench_warp(bench_group: &mut utils::BenchGroup) {
+    let src_image = U8x4::load_big_src_image();
+    let mut dst_image = Image::new(DST_WIDTH, DST_HEIGHT, src_image.pixel_type());
+    let mut resizer = Resizer::new();
+    let sample_size = 20;
+
+    for (alg_name, resize_alg) in [
+        ("Nearest", ResizeAlg::Nearest),
+        ("Bilinear", ResizeAlg::Interpolation(FilterType::Bilinear)),
+        ("Lanczos3", ResizeAlg::Convolution(FilterType::Lanczos3)),
+    ] {
+        let options = ResizeOptions::new().resize_alg(resize_alg);
+        // The transformation without rotation is done by passes of convolution.
+        for angle in [0., 30.] {
+            let matrix = rotation_matrix(&src_image, angle);
+            utils::bench(
+                bench_group,
+                sample_size,
+                format!("Warp U8x4, rotation by {angle} degrees"),
+                alg_name,
+                |bencher| {
+                    bencher.iter(|| {
+                        resizer
+                            .warp_affine(&src_image, &mut dst_image, &matrix, &options)
+                            .unwrap();
+                    })
+                },
+            );
+        }
+    }
+}
+
+fn main() {
+    let res = utils::run_bench(bench_warp, "Bench Warp");
+    println!("{}", utils::build_md_table(&res));
+}
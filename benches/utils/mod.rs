@@ -3,12 +3,18 @@ use std::path::PathBuf;
 use std::process::Command;
 
 pub use bencher::*;
+// Every benchmark is a separate crate that uses only a part of
+// the helpers from these modules.
+#[allow(unused_imports)]
 pub use resize_functions::*;
+#[allow(unused_imports)]
 pub use results::*;
 use serde::Deserialize;
 
 mod bencher;
+#[allow(dead_code)]
 mod resize_functions;
+#[allow(dead_code)]
 mod results;
 pub mod testing;
 
@@ -1,6 +1,6 @@
+use std::hint::black_box;
 use std::ops::Deref;
 
-use criterion::black_box;
 use fast_image_resize::images::Image;
 use fast_image_resize::{CpuExtensions, FilterType, ResizeAlg, ResizeOptions, Resizer};
 use image::{imageops, ImageBuffer};
@@ -76,7 +76,6 @@ pub fn resize_resize<Format, Out>(
 }
 
 /// Resize image with help of "libvips" crate (https://crates.io/crates/libvips)
-
 pub fn libvips_resize<P: PixelTestingExt>(bench_group: &mut BenchGroup, has_alpha: bool) {
     #[cfg(all(not(target_arch = "wasm32"), not(target_os = "windows")))]
     vips::libvips_resize_inner::<P>(bench_group, has_alpha);
@@ -84,6 +83,7 @@ pub fn libvips_resize<P: PixelTestingExt>(bench_group: &mut BenchGroup, has_alph
 
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "windows")))]
 mod vips {
+    use fast_image_resize::PixelType;
     use libvips::ops::{self, BandFormat, Kernel, ReduceOptions};
     use libvips::{VipsApp, VipsImage};
 
@@ -111,6 +111,7 @@ mod vips {
         let band_format = match P::count_of_component_values() {
             0x100 => BandFormat::Uchar,
             0x10000 => BandFormat::Ushort,
+            0 if P::pixel_type() == PixelType::I32 => BandFormat::Int,
             0 => BandFormat::Float,
             _ => panic!("Unknown type of pixel"),
         };
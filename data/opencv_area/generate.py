@@ -0,0 +1,61 @@
+#!/usr/bin/env python3
+"""Generates images used to compare the `Area` resize algorithm
+with `cv2.resize()` called with `interpolation=cv2.INTER_AREA`.
+
+Run it from the root of the repository:
+
+    python3 data/opencv_area/generate.py
+
+It requires `opencv-python` and `numpy`. Source images are filled with
+random noise, so every source pixel affects the result. Results are saved
+as `<pixel type>-<left>-<top>-<width>x<height>-<dst width>x<dst height>.png`,
+where the first four numbers are the crop box of the source image.
+"""
+import os
+
+import cv2
+import numpy as np
+
+DIR = os.path.dirname(os.path.abspath(__file__))
+SRC_WIDTH, SRC_HEIGHT = 24, 18
+FULL = (0, 0, SRC_WIDTH, SRC_HEIGHT)
+
+# The same cases are listed in `tests/resize_tests.rs`.
+CASES = [
+    # Integer downscale
+    (FULL, (12, 9)),
+    (FULL, (8, 6)),
+    # Fractional downscale
+    (FULL, (10, 7)),
+    (FULL, (17, 13)),
+    # Integer upscale
+    (FULL, (48, 36)),
+    # Fractional upscale
+    (FULL, (31, 25)),
+    # Upscale by one axis and downscale by another
+    (FULL, (31, 9)),
+    # Cropped source image
+    ((2, 3, 20, 15), (9, 7)),
+    ((5, 1, 11, 16), (25, 21)),
+]
+
+
+def main():
+    rng = np.random.default_rng(2024)
+    sources = {
+        "u8x3": rng.integers(0, 256, (SRC_HEIGHT, SRC_WIDTH, 3), dtype=np.uint8),
+        "u16": rng.integers(0, 65536, (SRC_HEIGHT, SRC_WIDTH), dtype=np.uint16),
+    }
+    for name, src in sources.items():
+        cv2.imwrite(os.path.join(DIR, f"{name}-src.png"), src)
+        for (left, top, width, height), (dst_width, dst_height) in CASES:
+            cropped = src[top : top + height, left : left + width]
+            dst = cv2.resize(
+                cropped, (dst_width, dst_height), interpolation=cv2.INTER_AREA
+            )
+            file_name = f"{name}-{left}-{top}-{width}x{height}-{dst_width}x{dst_height}.png"
+            cv2.imwrite(os.path.join(DIR, file_name), dst)
+
+
+if __name__ == "__main__":
+    main()
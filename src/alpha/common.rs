@@ -10,6 +10,17 @@ pub(crate) fn mul_div_65535(a: u16, b: u16) -> u16 {
     (((tmp >> 16) + tmp) >> 16) as u16
 }
 
+/// Returns the index of the alpha channel and indexes of color channels
+/// of a pixel with four components.
+#[inline(always)]
+pub(crate) const fn component_indexes(alpha_first: bool) -> (usize, [usize; 3]) {
+    if alpha_first {
+        (0, [1, 2, 3])
+    } else {
+        (3, [0, 1, 2])
+    }
+}
+
 const fn recip_alpha_array(precision: u32) -> [u32; 256] {
     let mut res = [0; 256];
     let scale = 1 << (precision + 1);
@@ -53,7 +64,7 @@ pub(crate) const RECIP_ALPHA: [u32; 256] = recip_alpha_array(PRECISION);
 pub(crate) static RECIP_ALPHA16: [u64; 65536] = recip_alpha16_array(PRECISION16);
 
 macro_rules! process_two_images {
-    {$op: ident($src_view: ident, $dst_view: ident, $($arg: ident),+);}  => {
+    {$op: ident $(::<$generic: tt>)? ($src_view: ident, $dst_view: ident, $($arg: ident),+);}  => {
         #[allow(unused_labels)]
         'block: {
             #[cfg(feature = "rayon")]
@@ -63,18 +74,18 @@ macro_rules! process_two_images {
 
                 if let Some(iter) = split_h_two_images_for_threading($src_view, $dst_view, 0) {
                     iter.for_each(|(src, mut dst)| {
-                        $op(&src, &mut dst, $($arg),+);
+                        $op$(::<$generic>)?(&src, &mut dst, $($arg),+);
                     });
                     break 'block;
                 }
             }
-            $op($src_view, $dst_view, $($arg),+);
+            $op$(::<$generic>)?($src_view, $dst_view, $($arg),+);
         }
     };
 }
 
 macro_rules! process_one_images {
-    {$op: ident($image_view: ident, $($arg: ident),+);} => {
+    {$op: ident $(::<$generic: tt>)? ($image_view: ident, $($arg: ident),+);} => {
         #[allow(unused_labels)]
         'block: {
             #[cfg(feature = "rayon")]
@@ -84,12 +95,12 @@ macro_rules! process_one_images {
 
                 if let Some(iter) = split_h_one_image_for_threading($image_view) {
                     iter.for_each(|mut img| {
-                        $op(&mut img, $($arg),+);
+                        $op$(::<$generic>)?(&mut img, $($arg),+);
                     });
                     break 'block;
                 }
             }
-            $op($image_view, $($arg),+);
+            $op$(::<$generic>)?($image_view, $($arg),+);
         }
     };
 }
@@ -0,0 +1,94 @@
+use super::AlphaMulDiv;
+use crate::cpu_extensions::CpuExtensions;
+use crate::pixels::{f16, F16x2, F16x4, InnerPixel};
+use crate::{AlphaPosition, ImageError, ImageView, ImageViewMut};
+
+mod native;
+
+macro_rules! impl_alpha_mul_div {
+    ($pixel: ty) => {
+        impl AlphaMulDiv for $pixel {
+            fn multiply_alpha(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                cpu_extensions: CpuExtensions,
+                _alpha_position: AlphaPosition,
+            ) -> Result<(), ImageError> {
+                process_two_images! {
+                    multiply(src_view, dst_view, cpu_extensions);
+                }
+                Ok(())
+            }
+
+            fn multiply_alpha_inplace(
+                image_view: &mut impl ImageViewMut<Pixel = Self>,
+                cpu_extensions: CpuExtensions,
+                _alpha_position: AlphaPosition,
+            ) -> Result<(), ImageError> {
+                process_one_images! {
+                    multiply_inplace(image_view, cpu_extensions);
+                }
+                Ok(())
+            }
+
+            fn divide_alpha(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                cpu_extensions: CpuExtensions,
+                _alpha_position: AlphaPosition,
+            ) -> Result<(), ImageError> {
+                process_two_images! {
+                    divide(src_view, dst_view, cpu_extensions);
+                }
+                Ok(())
+            }
+
+            fn divide_alpha_inplace(
+                image_view: &mut impl ImageViewMut<Pixel = Self>,
+                cpu_extensions: CpuExtensions,
+                _alpha_position: AlphaPosition,
+            ) -> Result<(), ImageError> {
+                process_one_images! {
+                    divide_inplace(image_view, cpu_extensions);
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_alpha_mul_div!(F16x2);
+impl_alpha_mul_div!(F16x4);
+
+// Components are converted into `f32` to multiply or divide them,
+// so the native implementation is used with any CPU extensions.
+
+fn multiply<T: InnerPixel<Component = f16>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::multiply_alpha(src_view, dst_view);
+}
+
+fn multiply_inplace<T: InnerPixel<Component = f16>>(
+    image_view: &mut impl ImageViewMut<Pixel = T>,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::multiply_alpha_inplace(image_view);
+}
+
+fn divide<T: InnerPixel<Component = f16>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::divide_alpha(src_view, dst_view);
+}
+
+fn divide_inplace<T: InnerPixel<Component = f16>>(
+    image_view: &mut impl ImageViewMut<Pixel = T>,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::divide_alpha_inplace(image_view);
+}
@@ -0,0 +1,74 @@
+use crate::pixels::{f16, InnerPixel};
+use crate::{ImageView, ImageViewMut};
+
+pub(crate) fn multiply_alpha<T: InnerPixel<Component = f16>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+) {
+    let src_rows = src_view.iter_rows(0);
+    let dst_rows = dst_view.iter_rows_mut(0);
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        dst_row.copy_from_slice(src_row);
+        multiply_alpha_row_inplace(dst_row);
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace<T: InnerPixel<Component = f16>>(
+    image_view: &mut impl ImageViewMut<Pixel = T>,
+) {
+    for row in image_view.iter_rows_mut(0) {
+        multiply_alpha_row_inplace(row);
+    }
+}
+
+#[inline(always)]
+fn multiply_alpha_row_inplace<T: InnerPixel<Component = f16>>(row: &mut [T]) {
+    let count = T::count_of_components();
+    for pixel in T::components_mut(row).chunks_exact_mut(count) {
+        let (colors, alpha) = pixel.split_at_mut(count - 1);
+        let alpha = alpha[0].to_f32();
+        for c in colors {
+            *c = f16::from_f32(c.to_f32() * alpha);
+        }
+    }
+}
+
+// Divide
+
+pub(crate) fn divide_alpha<T: InnerPixel<Component = f16>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+) {
+    let src_rows = src_view.iter_rows(0);
+    let dst_rows = dst_view.iter_rows_mut(0);
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        dst_row.copy_from_slice(src_row);
+        divide_alpha_row_inplace(dst_row);
+    }
+}
+
+pub(crate) fn divide_alpha_inplace<T: InnerPixel<Component = f16>>(
+    image_view: &mut impl ImageViewMut<Pixel = T>,
+) {
+    for row in image_view.iter_rows_mut(0) {
+        divide_alpha_row_inplace(row);
+    }
+}
+
+#[inline(always)]
+fn divide_alpha_row_inplace<T: InnerPixel<Component = f16>>(row: &mut [T]) {
+    let count = T::count_of_components();
+    for pixel in T::components_mut(row).chunks_exact_mut(count) {
+        let alpha = pixel[count - 1].to_f32();
+        if alpha == 0. {
+            pixel.fill(f16::ZERO);
+        } else {
+            let recip_alpha = 1. / alpha;
+            for c in &mut pixel[..count - 1] {
+                *c = f16::from_f32(c.to_f32() * recip_alpha);
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use super::AlphaMulDiv;
+use crate::cpu_extensions::CpuExtensions;
+use crate::pixels::{F64x2, F64x4, InnerPixel};
+use crate::{AlphaPosition, ImageError, ImageView, ImageViewMut};
+
+mod native;
+
+macro_rules! impl_alpha_mul_div {
+    ($pixel: ty) => {
+        impl AlphaMulDiv for $pixel {
+            fn multiply_alpha(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                cpu_extensions: CpuExtensions,
+                _alpha_position: AlphaPosition,
+            ) -> Result<(), ImageError> {
+                process_two_images! {
+                    multiply(src_view, dst_view, cpu_extensions);
+                }
+                Ok(())
+            }
+
+            fn multiply_alpha_inplace(
+                image_view: &mut impl ImageViewMut<Pixel = Self>,
+                cpu_extensions: CpuExtensions,
+                _alpha_position: AlphaPosition,
+            ) -> Result<(), ImageError> {
+                process_one_images! {
+                    multiply_inplace(image_view, cpu_extensions);
+                }
+                Ok(())
+            }
+
+            fn divide_alpha(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                cpu_extensions: CpuExtensions,
+                _alpha_position: AlphaPosition,
+            ) -> Result<(), ImageError> {
+                process_two_images! {
+                    divide(src_view, dst_view, cpu_extensions);
+                }
+                Ok(())
+            }
+
+            fn divide_alpha_inplace(
+                image_view: &mut impl ImageViewMut<Pixel = Self>,
+                cpu_extensions: CpuExtensions,
+                _alpha_position: AlphaPosition,
+            ) -> Result<(), ImageError> {
+                process_one_images! {
+                    divide_inplace(image_view, cpu_extensions);
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_alpha_mul_div!(F64x2);
+impl_alpha_mul_div!(F64x4);
+
+// Only the native implementation exists for `f64` components,
+// so CPU extensions are ignored.
+
+fn multiply<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::multiply_alpha(src_view, dst_view);
+}
+
+fn multiply_inplace<T: InnerPixel<Component = f64>>(
+    image_view: &mut impl ImageViewMut<Pixel = T>,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::multiply_alpha_inplace(image_view);
+}
+
+fn divide<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::divide_alpha(src_view, dst_view);
+}
+
+fn divide_inplace<T: InnerPixel<Component = f64>>(
+    image_view: &mut impl ImageViewMut<Pixel = T>,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::divide_alpha_inplace(image_view);
+}
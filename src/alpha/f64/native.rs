@@ -0,0 +1,74 @@
+use crate::pixels::InnerPixel;
+use crate::{ImageView, ImageViewMut};
+
+pub(crate) fn multiply_alpha<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+) {
+    let src_rows = src_view.iter_rows(0);
+    let dst_rows = dst_view.iter_rows_mut(0);
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        dst_row.copy_from_slice(src_row);
+        multiply_alpha_row_inplace(dst_row);
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace<T: InnerPixel<Component = f64>>(
+    image_view: &mut impl ImageViewMut<Pixel = T>,
+) {
+    for row in image_view.iter_rows_mut(0) {
+        multiply_alpha_row_inplace(row);
+    }
+}
+
+#[inline(always)]
+fn multiply_alpha_row_inplace<T: InnerPixel<Component = f64>>(row: &mut [T]) {
+    let count = T::count_of_components();
+    for pixel in T::components_mut(row).chunks_exact_mut(count) {
+        let (colors, alpha) = pixel.split_at_mut(count - 1);
+        let alpha = alpha[0];
+        for c in colors {
+            *c *= alpha;
+        }
+    }
+}
+
+// Divide
+
+pub(crate) fn divide_alpha<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+) {
+    let src_rows = src_view.iter_rows(0);
+    let dst_rows = dst_view.iter_rows_mut(0);
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        dst_row.copy_from_slice(src_row);
+        divide_alpha_row_inplace(dst_row);
+    }
+}
+
+pub(crate) fn divide_alpha_inplace<T: InnerPixel<Component = f64>>(
+    image_view: &mut impl ImageViewMut<Pixel = T>,
+) {
+    for row in image_view.iter_rows_mut(0) {
+        divide_alpha_row_inplace(row);
+    }
+}
+
+#[inline(always)]
+fn divide_alpha_row_inplace<T: InnerPixel<Component = f64>>(row: &mut [T]) {
+    let count = T::count_of_components();
+    for pixel in T::components_mut(row).chunks_exact_mut(count) {
+        let alpha = pixel[count - 1];
+        if alpha == 0. {
+            pixel.fill(0.);
+        } else {
+            let recip_alpha = 1. / alpha;
+            for c in &mut pixel[..count - 1] {
+                *c *= recip_alpha;
+            }
+        }
+    }
+}
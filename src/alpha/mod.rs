@@ -1,28 +1,34 @@
-use crate::{pixels, CpuExtensions, ImageError, ImageView, ImageViewMut};
+use crate::{pixels, AlphaPosition, CpuExtensions, ImageError, ImageView, ImageViewMut};
 
 #[macro_use]
 mod common;
 pub(crate) mod errors;
 
+mod u16x4;
 mod u8x4;
 cfg_if::cfg_if! {
     if #[cfg(not(feature = "only_u8x4"))] {
         mod u16x2;
-        mod u16x4;
         mod u8x2;
         mod f32x2;
         mod f32x4;
+        mod f16;
+        mod f64;
     }
 }
 
 pub(crate) trait AlphaMulDiv: pixels::InnerPixel {
     /// Multiplies RGB-channels of source image by alpha-channel and store
     /// result into destination image.
+    ///
+    /// The position of alpha-channel is used only by pixels with four components.
+    /// Other pixel types must be used with [AlphaPosition::Last].
     #[allow(unused_variables)]
     fn multiply_alpha(
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         Err(ImageError::UnsupportedPixelType)
     }
@@ -32,6 +38,7 @@ pub(crate) trait AlphaMulDiv: pixels::InnerPixel {
     fn multiply_alpha_inplace(
         image_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         Err(ImageError::UnsupportedPixelType)
     }
@@ -43,6 +50,7 @@ pub(crate) trait AlphaMulDiv: pixels::InnerPixel {
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         Err(ImageError::UnsupportedPixelType)
     }
@@ -52,6 +60,7 @@ pub(crate) trait AlphaMulDiv: pixels::InnerPixel {
     fn divide_alpha_inplace(
         image_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         Err(ImageError::UnsupportedPixelType)
     }
@@ -64,3 +73,14 @@ impl AlphaMulDiv for pixels::U16x3 {}
 impl AlphaMulDiv for pixels::I32 {}
 impl AlphaMulDiv for pixels::F32 {}
 impl AlphaMulDiv for pixels::F32x3 {}
+impl AlphaMulDiv for pixels::F16 {}
+impl AlphaMulDiv for pixels::F16x3 {}
+impl AlphaMulDiv for pixels::F64 {}
+impl AlphaMulDiv for pixels::F64x3 {}
+impl AlphaMulDiv for pixels::I32x2 {}
+impl AlphaMulDiv for pixels::I32x3 {}
+impl AlphaMulDiv for pixels::I32x4 {}
+impl AlphaMulDiv for pixels::U32 {}
+impl AlphaMulDiv for pixels::U32x2 {}
+impl AlphaMulDiv for pixels::U32x3 {}
+impl AlphaMulDiv for pixels::U32x4 {}
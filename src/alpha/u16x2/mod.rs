@@ -1,6 +1,6 @@
 use super::AlphaMulDiv;
 use crate::pixels::U16x2;
-use crate::{CpuExtensions, ImageError, ImageView, ImageViewMut};
+use crate::{AlphaPosition, CpuExtensions, ImageError, ImageView, ImageViewMut};
 
 #[cfg(target_arch = "x86_64")]
 mod avx2;
@@ -19,6 +19,7 @@ impl AlphaMulDiv for P {
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        _alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         process_two_images! {
             multiple(src_view, dst_view, cpu_extensions);
@@ -29,6 +30,7 @@ impl AlphaMulDiv for P {
     fn multiply_alpha_inplace(
         image_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        _alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         process_one_images! {
             multiply_inplace(image_view, cpu_extensions);
@@ -40,6 +42,7 @@ impl AlphaMulDiv for P {
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        _alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         process_two_images! {
             divide(src_view, dst_view, cpu_extensions);
@@ -50,6 +53,7 @@ impl AlphaMulDiv for P {
     fn divide_alpha_inplace(
         image_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        _alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         process_one_images! {
             divide_inplace(image_view, cpu_extensions);
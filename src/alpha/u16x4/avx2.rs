@@ -6,7 +6,7 @@ use crate::utils::foreach_with_pre_reading;
 use crate::{ImageView, ImageViewMut};
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn multiply_alpha(
+pub(crate) unsafe fn multiply_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U16x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U16x4>,
 ) {
@@ -14,20 +14,25 @@ pub(crate) unsafe fn multiply_alpha(
     let dst_rows = dst_view.iter_rows_mut(0);
 
     for (src_row, dst_row) in src_rows.zip(dst_rows) {
-        multiply_alpha_row(src_row, dst_row);
+        multiply_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn multiply_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U16x4>) {
+pub(crate) unsafe fn multiply_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U16x4>,
+) {
     for row in image_view.iter_rows_mut(0) {
-        multiply_alpha_row_inplace(row);
+        multiply_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[inline]
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn multiply_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4]) {
+pub(crate) unsafe fn multiply_alpha_row<const ALPHA_FIRST: bool>(
+    src_row: &[U16x4],
+    dst_row: &mut [U16x4],
+) {
     let src_chunks = src_row.chunks_exact(4);
     let src_remainder = src_chunks.remainder();
     let mut dst_chunks = dst_row.chunks_exact_mut(4);
@@ -40,20 +45,20 @@ pub(crate) unsafe fn multiply_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4]
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = multiply_alpha_4_pixels(pixels);
+            pixels = multiply_alpha_4_pixels::<ALPHA_FIRST>(pixels);
             _mm256_storeu_si256(dst_ptr, pixels);
         },
     );
 
     if !src_remainder.is_empty() {
         let dst_reminder = dst_chunks.into_remainder();
-        sse4::multiply_alpha_row(src_remainder, dst_reminder);
+        sse4::multiply_alpha_row::<ALPHA_FIRST>(src_remainder, dst_reminder);
     }
 }
 
 #[inline]
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn multiply_alpha_row_inplace(row: &mut [U16x4]) {
+pub(crate) unsafe fn multiply_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U16x4]) {
     let mut chunks = row.chunks_exact_mut(4);
     foreach_with_pre_reading(
         &mut chunks,
@@ -63,19 +68,24 @@ pub(crate) unsafe fn multiply_alpha_row_inplace(row: &mut [U16x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = multiply_alpha_4_pixels(pixels);
+            pixels = multiply_alpha_4_pixels::<ALPHA_FIRST>(pixels);
             _mm256_storeu_si256(dst_ptr, pixels);
         },
     );
     let reminder = chunks.into_remainder();
     if !reminder.is_empty() {
-        sse4::multiply_alpha_row_inplace(reminder);
+        sse4::multiply_alpha_row_inplace::<ALPHA_FIRST>(reminder);
     }
 }
 
 #[inline]
 #[target_feature(enable = "avx2")]
-unsafe fn multiply_alpha_4_pixels(pixels: __m256i) -> __m256i {
+unsafe fn multiply_alpha_4_pixels<const ALPHA_FIRST: bool>(pixels: __m256i) -> __m256i {
+    let pixels = if ALPHA_FIRST {
+        move_alpha_to_last(pixels)
+    } else {
+        pixels
+    };
     let zero = _mm256_setzero_si256();
     let half = _mm256_set1_epi32(0x8000);
 
@@ -105,13 +115,18 @@ unsafe fn multiply_alpha_4_pixels(pixels: __m256i) -> __m256i {
     let dst_i32_hi = _mm256_add_epi32(src_i32_hi, _mm256_srli_epi32::<16>(src_i32_hi));
     let dst_i32_hi = _mm256_srli_epi32::<16>(dst_i32_hi);
 
-    _mm256_packus_epi32(dst_i32_lo, dst_i32_hi)
+    let res_pixels = _mm256_packus_epi32(dst_i32_lo, dst_i32_hi);
+    if ALPHA_FIRST {
+        move_alpha_to_first(res_pixels)
+    } else {
+        res_pixels
+    }
 }
 
 // Divide
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn divide_alpha(
+pub(crate) unsafe fn divide_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U16x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U16x4>,
 ) {
@@ -119,19 +134,24 @@ pub(crate) unsafe fn divide_alpha(
     let dst_rows = dst_view.iter_rows_mut(0);
 
     for (src_row, dst_row) in src_rows.zip(dst_rows) {
-        divide_alpha_row(src_row, dst_row);
+        divide_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn divide_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U16x4>) {
+pub(crate) unsafe fn divide_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U16x4>,
+) {
     for row in image_view.iter_rows_mut(0) {
-        divide_alpha_row_inplace(row);
+        divide_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn divide_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4]) {
+pub(crate) unsafe fn divide_alpha_row<const ALPHA_FIRST: bool>(
+    src_row: &[U16x4],
+    dst_row: &mut [U16x4],
+) {
     let src_chunks = src_row.chunks_exact(4);
     let src_remainder = src_chunks.remainder();
     let mut dst_chunks = dst_row.chunks_exact_mut(4);
@@ -144,7 +164,7 @@ pub(crate) unsafe fn divide_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4])
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = divide_alpha_4_pixels(pixels);
+            pixels = divide_alpha_4_pixels::<ALPHA_FIRST>(pixels);
             _mm256_storeu_si256(dst_ptr, pixels);
         },
     );
@@ -159,7 +179,7 @@ pub(crate) unsafe fn divide_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4])
 
         let mut dst_pixels = [U16x4::new([0, 0, 0, 0]); 4];
         let mut pixels = _mm256_loadu_si256(src_pixels.as_ptr() as *const __m256i);
-        pixels = divide_alpha_4_pixels(pixels);
+        pixels = divide_alpha_4_pixels::<ALPHA_FIRST>(pixels);
         _mm256_storeu_si256(dst_pixels.as_mut_ptr() as *mut __m256i, pixels);
 
         dst_pixels
@@ -170,7 +190,7 @@ pub(crate) unsafe fn divide_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4])
 }
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U16x4]) {
+pub(crate) unsafe fn divide_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U16x4]) {
     let mut chunks = row.chunks_exact_mut(4);
     foreach_with_pre_reading(
         &mut chunks,
@@ -180,7 +200,7 @@ pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U16x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = divide_alpha_4_pixels(pixels);
+            pixels = divide_alpha_4_pixels::<ALPHA_FIRST>(pixels);
             _mm256_storeu_si256(dst_ptr, pixels);
         },
     );
@@ -195,7 +215,7 @@ pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U16x4]) {
 
         let mut dst_pixels = [U16x4::new([0, 0, 0, 0]); 4];
         let mut pixels = _mm256_loadu_si256(src_pixels.as_ptr() as *const __m256i);
-        pixels = divide_alpha_4_pixels(pixels);
+        pixels = divide_alpha_4_pixels::<ALPHA_FIRST>(pixels);
         _mm256_storeu_si256(dst_pixels.as_mut_ptr() as *mut __m256i, pixels);
 
         dst_pixels.iter().zip(reminder).for_each(|(s, d)| *d = *s);
@@ -204,7 +224,12 @@ pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U16x4]) {
 
 #[inline]
 #[target_feature(enable = "avx2")]
-unsafe fn divide_alpha_4_pixels(pixels: __m256i) -> __m256i {
+unsafe fn divide_alpha_4_pixels<const ALPHA_FIRST: bool>(pixels: __m256i) -> __m256i {
+    let pixels = if ALPHA_FIRST {
+        move_alpha_to_last(pixels)
+    } else {
+        pixels
+    };
     let zero = _mm256_setzero_si256();
     let alpha_mask = _mm256_set1_epi64x(0xffff000000000000u64 as i64);
     let alpha_max = _mm256_set1_ps(65535.0);
@@ -240,5 +265,34 @@ unsafe fn divide_alpha_4_pixels(pixels: __m256i) -> __m256i {
     // All negative values will be stored as 0.
     let two_pixels_i16x16 = _mm256_packus_epi32(divided_pix0_i32x8, divided_pix1_i32x8);
     let alpha = _mm256_and_si256(pixels, alpha_mask);
-    _mm256_blendv_epi8(two_pixels_i16x16, alpha, alpha_mask)
+    let res_pixels = _mm256_blendv_epi8(two_pixels_i16x16, alpha, alpha_mask);
+    if ALPHA_FIRST {
+        move_alpha_to_first(res_pixels)
+    } else {
+        res_pixels
+    }
+}
+
+/// Converts pixels from ARGB into RGBA order of components.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn move_alpha_to_last(pixels: __m256i) -> __m256i {
+    #[rustfmt::skip]
+    let shuffle = _mm256_set_epi8(
+        9, 8, 15, 14, 13, 12, 11, 10, 1, 0, 7, 6, 5, 4, 3, 2,
+        9, 8, 15, 14, 13, 12, 11, 10, 1, 0, 7, 6, 5, 4, 3, 2,
+    );
+    _mm256_shuffle_epi8(pixels, shuffle)
+}
+
+/// Converts pixels from RGBA into ARGB order of components.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn move_alpha_to_first(pixels: __m256i) -> __m256i {
+    #[rustfmt::skip]
+    let shuffle = _mm256_set_epi8(
+        13, 12, 11, 10, 9, 8, 15, 14, 5, 4, 3, 2, 1, 0, 7, 6,
+        13, 12, 11, 10, 9, 8, 15, 14, 5, 4, 3, 2, 1, 0, 7, 6,
+    );
+    _mm256_shuffle_epi8(pixels, shuffle)
 }
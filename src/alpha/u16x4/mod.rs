@@ -1,6 +1,6 @@
 use super::AlphaMulDiv;
 use crate::pixels::U16x4;
-use crate::{CpuExtensions, ImageError, ImageView, ImageViewMut};
+use crate::{AlphaPosition, CpuExtensions, ImageError, ImageView, ImageViewMut};
 
 #[cfg(target_arch = "x86_64")]
 mod avx2;
@@ -19,9 +19,19 @@ impl AlphaMulDiv for P {
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
-        process_two_images! {
-            multiple(src_view, dst_view, cpu_extensions);
+        match alpha_position {
+            AlphaPosition::Last => {
+                process_two_images! {
+                    multiple::<false>(src_view, dst_view, cpu_extensions);
+                }
+            }
+            AlphaPosition::First => {
+                process_two_images! {
+                    multiple::<true>(src_view, dst_view, cpu_extensions);
+                }
+            }
         }
         Ok(())
     }
@@ -29,9 +39,19 @@ impl AlphaMulDiv for P {
     fn multiply_alpha_inplace(
         image_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
-        process_one_images! {
-            multiply_inplace(image_view, cpu_extensions);
+        match alpha_position {
+            AlphaPosition::Last => {
+                process_one_images! {
+                    multiply_inplace::<false>(image_view, cpu_extensions);
+                }
+            }
+            AlphaPosition::First => {
+                process_one_images! {
+                    multiply_inplace::<true>(image_view, cpu_extensions);
+                }
+            }
         }
         Ok(())
     }
@@ -40,9 +60,19 @@ impl AlphaMulDiv for P {
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
-        process_two_images! {
-            divide(src_view, dst_view, cpu_extensions);
+        match alpha_position {
+            AlphaPosition::Last => {
+                process_two_images! {
+                    divide::<false>(src_view, dst_view, cpu_extensions);
+                }
+            }
+            AlphaPosition::First => {
+                process_two_images! {
+                    divide::<true>(src_view, dst_view, cpu_extensions);
+                }
+            }
         }
         Ok(())
     }
@@ -50,74 +80,98 @@ impl AlphaMulDiv for P {
     fn divide_alpha_inplace(
         image_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
-        process_one_images! {
-            divide_inplace(image_view, cpu_extensions);
+        match alpha_position {
+            AlphaPosition::Last => {
+                process_one_images! {
+                    divide_inplace::<false>(image_view, cpu_extensions);
+                }
+            }
+            AlphaPosition::First => {
+                process_one_images! {
+                    divide_inplace::<true>(image_view, cpu_extensions);
+                }
+            }
         }
         Ok(())
     }
 }
 
-fn multiple(
+fn multiple<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = P>,
     dst_view: &mut impl ImageViewMut<Pixel = P>,
     cpu_extensions: CpuExtensions,
 ) {
     match cpu_extensions {
         #[cfg(target_arch = "x86_64")]
-        CpuExtensions::Avx2 => unsafe { avx2::multiply_alpha(src_view, dst_view) },
+        CpuExtensions::Avx2 => unsafe { avx2::multiply_alpha::<ALPHA_FIRST>(src_view, dst_view) },
         #[cfg(target_arch = "x86_64")]
-        CpuExtensions::Sse4_1 => unsafe { sse4::multiply_alpha(src_view, dst_view) },
+        CpuExtensions::Sse4_1 => unsafe { sse4::multiply_alpha::<ALPHA_FIRST>(src_view, dst_view) },
         #[cfg(target_arch = "aarch64")]
-        CpuExtensions::Neon => unsafe { neon::multiply_alpha(src_view, dst_view) },
+        CpuExtensions::Neon if !ALPHA_FIRST => unsafe { neon::multiply_alpha(src_view, dst_view) },
         #[cfg(target_arch = "wasm32")]
-        CpuExtensions::Simd128 => unsafe { wasm32::multiply_alpha(src_view, dst_view) },
-        _ => native::multiply_alpha(src_view, dst_view),
+        CpuExtensions::Simd128 if !ALPHA_FIRST => unsafe {
+            wasm32::multiply_alpha(src_view, dst_view)
+        },
+        _ => native::multiply_alpha::<ALPHA_FIRST>(src_view, dst_view),
     }
 }
 
-fn multiply_inplace(image_view: &mut impl ImageViewMut<Pixel = P>, cpu_extensions: CpuExtensions) {
+fn multiply_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = P>,
+    cpu_extensions: CpuExtensions,
+) {
     match cpu_extensions {
         #[cfg(target_arch = "x86_64")]
-        CpuExtensions::Avx2 => unsafe { avx2::multiply_alpha_inplace(image_view) },
+        CpuExtensions::Avx2 => unsafe { avx2::multiply_alpha_inplace::<ALPHA_FIRST>(image_view) },
         #[cfg(target_arch = "x86_64")]
-        CpuExtensions::Sse4_1 => unsafe { sse4::multiply_alpha_inplace(image_view) },
+        CpuExtensions::Sse4_1 => unsafe { sse4::multiply_alpha_inplace::<ALPHA_FIRST>(image_view) },
         #[cfg(target_arch = "aarch64")]
-        CpuExtensions::Neon => unsafe { neon::multiply_alpha_inplace(image_view) },
+        CpuExtensions::Neon if !ALPHA_FIRST => unsafe { neon::multiply_alpha_inplace(image_view) },
         #[cfg(target_arch = "wasm32")]
-        CpuExtensions::Simd128 => unsafe { wasm32::multiply_alpha_inplace(image_view) },
-        _ => native::multiply_alpha_inplace(image_view),
+        CpuExtensions::Simd128 if !ALPHA_FIRST => unsafe {
+            wasm32::multiply_alpha_inplace(image_view)
+        },
+        _ => native::multiply_alpha_inplace::<ALPHA_FIRST>(image_view),
     }
 }
 
-fn divide(
+fn divide<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = P>,
     dst_view: &mut impl ImageViewMut<Pixel = P>,
     cpu_extensions: CpuExtensions,
 ) {
     match cpu_extensions {
         #[cfg(target_arch = "x86_64")]
-        CpuExtensions::Avx2 => unsafe { avx2::divide_alpha(src_view, dst_view) },
+        CpuExtensions::Avx2 => unsafe { avx2::divide_alpha::<ALPHA_FIRST>(src_view, dst_view) },
         #[cfg(target_arch = "x86_64")]
-        CpuExtensions::Sse4_1 => unsafe { sse4::divide_alpha(src_view, dst_view) },
+        CpuExtensions::Sse4_1 => unsafe { sse4::divide_alpha::<ALPHA_FIRST>(src_view, dst_view) },
         #[cfg(target_arch = "aarch64")]
-        CpuExtensions::Neon => unsafe { neon::divide_alpha(src_view, dst_view) },
+        CpuExtensions::Neon if !ALPHA_FIRST => unsafe { neon::divide_alpha(src_view, dst_view) },
         #[cfg(target_arch = "wasm32")]
-        CpuExtensions::Simd128 => unsafe { wasm32::divide_alpha(src_view, dst_view) },
-        _ => native::divide_alpha(src_view, dst_view),
+        CpuExtensions::Simd128 if !ALPHA_FIRST => unsafe {
+            wasm32::divide_alpha(src_view, dst_view)
+        },
+        _ => native::divide_alpha::<ALPHA_FIRST>(src_view, dst_view),
     }
 }
 
-fn divide_inplace(image_view: &mut impl ImageViewMut<Pixel = P>, cpu_extensions: CpuExtensions) {
+fn divide_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = P>,
+    cpu_extensions: CpuExtensions,
+) {
     match cpu_extensions {
         #[cfg(target_arch = "x86_64")]
-        CpuExtensions::Avx2 => unsafe { avx2::divide_alpha_inplace(image_view) },
+        CpuExtensions::Avx2 => unsafe { avx2::divide_alpha_inplace::<ALPHA_FIRST>(image_view) },
         #[cfg(target_arch = "x86_64")]
-        CpuExtensions::Sse4_1 => unsafe { sse4::divide_alpha_inplace(image_view) },
+        CpuExtensions::Sse4_1 => unsafe { sse4::divide_alpha_inplace::<ALPHA_FIRST>(image_view) },
         #[cfg(target_arch = "aarch64")]
-        CpuExtensions::Neon => unsafe { neon::divide_alpha_inplace(image_view) },
+        CpuExtensions::Neon if !ALPHA_FIRST => unsafe { neon::divide_alpha_inplace(image_view) },
         #[cfg(target_arch = "wasm32")]
-        CpuExtensions::Simd128 => unsafe { wasm32::divide_alpha_inplace(image_view) },
-        _ => native::divide_alpha_inplace(image_view),
+        CpuExtensions::Simd128 if !ALPHA_FIRST => unsafe {
+            wasm32::divide_alpha_inplace(image_view)
+        },
+        _ => native::divide_alpha_inplace::<ALPHA_FIRST>(image_view),
     }
 }
@@ -1,8 +1,8 @@
-use crate::alpha::common::{div_and_clip16, mul_div_65535, RECIP_ALPHA16};
+use crate::alpha::common::{component_indexes, div_and_clip16, mul_div_65535, RECIP_ALPHA16};
 use crate::pixels::U16x4;
 use crate::{ImageView, ImageViewMut};
 
-pub(crate) fn multiply_alpha(
+pub(crate) fn multiply_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U16x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U16x4>,
 ) {
@@ -10,48 +10,51 @@ pub(crate) fn multiply_alpha(
     let dst_rows = dst_view.iter_rows_mut(0);
 
     for (src_row, dst_row) in src_rows.zip(dst_rows) {
-        multiply_alpha_row(src_row, dst_row);
+        multiply_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
-pub(crate) fn multiply_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U16x4>) {
+pub(crate) fn multiply_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U16x4>,
+) {
     for row in image_view.iter_rows_mut(0) {
-        multiply_alpha_row_inplace(row);
+        multiply_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[inline(always)]
-pub(crate) fn multiply_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4]) {
+pub(crate) fn multiply_alpha_row<const ALPHA_FIRST: bool>(
+    src_row: &[U16x4],
+    dst_row: &mut [U16x4],
+) {
+    let (alpha_index, color_indexes) = component_indexes(ALPHA_FIRST);
     for (src_pixel, dst_pixel) in src_row.iter().zip(dst_row) {
-        let components: [u16; 4] = src_pixel.0;
-        let alpha = components[3];
-        dst_pixel.0 = [
-            mul_div_65535(components[0], alpha),
-            mul_div_65535(components[1], alpha),
-            mul_div_65535(components[2], alpha),
-            alpha,
-        ];
+        let mut components: [u16; 4] = src_pixel.0;
+        let alpha = components[alpha_index];
+        for i in color_indexes {
+            components[i] = mul_div_65535(components[i], alpha);
+        }
+        dst_pixel.0 = components;
     }
 }
 
 #[inline(always)]
-pub(crate) fn multiply_alpha_row_inplace(row: &mut [U16x4]) {
+pub(crate) fn multiply_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U16x4]) {
+    let (alpha_index, color_indexes) = component_indexes(ALPHA_FIRST);
     for pixel in row {
-        let components: [u16; 4] = pixel.0;
-        let alpha = components[3];
-        pixel.0 = [
-            mul_div_65535(components[0], alpha),
-            mul_div_65535(components[1], alpha),
-            mul_div_65535(components[2], alpha),
-            alpha,
-        ];
+        let mut components: [u16; 4] = pixel.0;
+        let alpha = components[alpha_index];
+        for i in color_indexes {
+            components[i] = mul_div_65535(components[i], alpha);
+        }
+        pixel.0 = components;
     }
 }
 
 // Divide
 
 #[inline]
-pub(crate) fn divide_alpha(
+pub(crate) fn divide_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U16x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U16x4>,
 ) {
@@ -59,46 +62,44 @@ pub(crate) fn divide_alpha(
     let dst_rows = dst_view.iter_rows_mut(0);
 
     for (src_row, dst_row) in src_rows.zip(dst_rows) {
-        divide_alpha_row(src_row, dst_row);
+        divide_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
 #[inline]
-pub(crate) fn divide_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U16x4>) {
+pub(crate) fn divide_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U16x4>,
+) {
     for row in image_view.iter_rows_mut(0) {
-        divide_alpha_row_inplace(row);
+        divide_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[inline(always)]
-pub(crate) fn divide_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4]) {
+pub(crate) fn divide_alpha_row<const ALPHA_FIRST: bool>(src_row: &[U16x4], dst_row: &mut [U16x4]) {
+    let (alpha_index, color_indexes) = component_indexes(ALPHA_FIRST);
     src_row
         .iter()
         .zip(dst_row)
         .for_each(|(src_pixel, dst_pixel)| {
-            let components: [u16; 4] = src_pixel.0;
-            let alpha = components[3];
-            let recip_alpha = RECIP_ALPHA16[alpha as usize];
-            dst_pixel.0 = [
-                div_and_clip16(components[0], recip_alpha),
-                div_and_clip16(components[1], recip_alpha),
-                div_and_clip16(components[2], recip_alpha),
-                alpha,
-            ];
+            let mut components: [u16; 4] = src_pixel.0;
+            let recip_alpha = RECIP_ALPHA16[components[alpha_index] as usize];
+            for i in color_indexes {
+                components[i] = div_and_clip16(components[i], recip_alpha);
+            }
+            dst_pixel.0 = components;
         });
 }
 
 #[inline(always)]
-pub(crate) fn divide_alpha_row_inplace(row: &mut [U16x4]) {
+pub(crate) fn divide_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U16x4]) {
+    let (alpha_index, color_indexes) = component_indexes(ALPHA_FIRST);
     row.iter_mut().for_each(|pixel| {
-        let components: [u16; 4] = pixel.0;
-        let alpha = components[3];
-        let recip_alpha = RECIP_ALPHA16[alpha as usize];
-        pixel.0 = [
-            div_and_clip16(components[0], recip_alpha),
-            div_and_clip16(components[1], recip_alpha),
-            div_and_clip16(components[2], recip_alpha),
-            alpha,
-        ];
+        let mut components: [u16; 4] = pixel.0;
+        let recip_alpha = RECIP_ALPHA16[components[alpha_index] as usize];
+        for i in color_indexes {
+            components[i] = div_and_clip16(components[i], recip_alpha);
+        }
+        pixel.0 = components;
     });
 }
@@ -6,7 +6,7 @@ use crate::utils::foreach_with_pre_reading;
 use crate::{ImageView, ImageViewMut};
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn multiply_alpha(
+pub(crate) unsafe fn multiply_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U16x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U16x4>,
 ) {
@@ -14,20 +14,25 @@ pub(crate) unsafe fn multiply_alpha(
     let dst_rows = dst_view.iter_rows_mut(0);
 
     for (src_row, dst_row) in src_rows.zip(dst_rows) {
-        multiply_alpha_row(src_row, dst_row);
+        multiply_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn multiply_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U16x4>) {
+pub(crate) unsafe fn multiply_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U16x4>,
+) {
     for row in image_view.iter_rows_mut(0) {
-        multiply_alpha_row_inplace(row);
+        multiply_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn multiply_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4]) {
+pub(crate) unsafe fn multiply_alpha_row<const ALPHA_FIRST: bool>(
+    src_row: &[U16x4],
+    dst_row: &mut [U16x4],
+) {
     let src_chunks = src_row.chunks_exact(2);
     let src_remainder = src_chunks.remainder();
     let mut dst_chunks = dst_row.chunks_exact_mut(2);
@@ -40,20 +45,20 @@ pub(crate) unsafe fn multiply_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4]
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = multiply_alpha_2_pixels(pixels);
+            pixels = multiply_alpha_2_pixels::<ALPHA_FIRST>(pixels);
             _mm_storeu_si128(dst_ptr, pixels);
         },
     );
 
     if !src_remainder.is_empty() {
         let dst_reminder = dst_chunks.into_remainder();
-        native::multiply_alpha_row(src_remainder, dst_reminder);
+        native::multiply_alpha_row::<ALPHA_FIRST>(src_remainder, dst_reminder);
     }
 }
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn multiply_alpha_row_inplace(row: &mut [U16x4]) {
+pub(crate) unsafe fn multiply_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U16x4]) {
     let mut chunks = row.chunks_exact_mut(2);
     foreach_with_pre_reading(
         &mut chunks,
@@ -63,20 +68,25 @@ pub(crate) unsafe fn multiply_alpha_row_inplace(row: &mut [U16x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = multiply_alpha_2_pixels(pixels);
+            pixels = multiply_alpha_2_pixels::<ALPHA_FIRST>(pixels);
             _mm_storeu_si128(dst_ptr, pixels);
         },
     );
 
     let remainder = chunks.into_remainder();
     if !remainder.is_empty() {
-        native::multiply_alpha_row_inplace(remainder);
+        native::multiply_alpha_row_inplace::<ALPHA_FIRST>(remainder);
     }
 }
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-unsafe fn multiply_alpha_2_pixels(pixels: __m128i) -> __m128i {
+unsafe fn multiply_alpha_2_pixels<const ALPHA_FIRST: bool>(pixels: __m128i) -> __m128i {
+    let pixels = if ALPHA_FIRST {
+        move_alpha_to_last(pixels)
+    } else {
+        pixels
+    };
     let zero = _mm_setzero_si128();
     let half = _mm_set1_epi32(0x8000);
     const MAX_A: i64 = 0xffff000000000000u64 as i64;
@@ -102,13 +112,18 @@ unsafe fn multiply_alpha_2_pixels(pixels: __m128i) -> __m128i {
     let dst_i32_hi = _mm_add_epi32(src_i32_hi, _mm_srli_epi32::<16>(src_i32_hi));
     let dst_i32_hi = _mm_srli_epi32::<16>(dst_i32_hi);
 
-    _mm_packus_epi32(dst_i32_lo, dst_i32_hi)
+    let res_pixels = _mm_packus_epi32(dst_i32_lo, dst_i32_hi);
+    if ALPHA_FIRST {
+        move_alpha_to_first(res_pixels)
+    } else {
+        res_pixels
+    }
 }
 
 // Divide
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn divide_alpha(
+pub(crate) unsafe fn divide_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U16x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U16x4>,
 ) {
@@ -116,19 +131,24 @@ pub(crate) unsafe fn divide_alpha(
     let dst_rows = dst_view.iter_rows_mut(0);
 
     for (src_row, dst_row) in src_rows.zip(dst_rows) {
-        divide_alpha_row(src_row, dst_row);
+        divide_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn divide_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U16x4>) {
+pub(crate) unsafe fn divide_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U16x4>,
+) {
     for row in image_view.iter_rows_mut(0) {
-        divide_alpha_row_inplace(row);
+        divide_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn divide_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4]) {
+pub(crate) unsafe fn divide_alpha_row<const ALPHA_FIRST: bool>(
+    src_row: &[U16x4],
+    dst_row: &mut [U16x4],
+) {
     let src_chunks = src_row.chunks_exact(2);
     let src_remainder = src_chunks.remainder();
     let mut dst_chunks = dst_row.chunks_exact_mut(2);
@@ -141,7 +161,7 @@ pub(crate) unsafe fn divide_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4])
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = divide_alpha_2_pixels(pixels);
+            pixels = divide_alpha_2_pixels::<ALPHA_FIRST>(pixels);
             _mm_storeu_si128(dst_ptr, pixels);
         },
     );
@@ -151,7 +171,7 @@ pub(crate) unsafe fn divide_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4])
         let mut dst_pixels = [U16x4::new([0, 0, 0, 0]); 2];
 
         let mut pixels = _mm_loadu_si128(src_pixels.as_ptr() as *const __m128i);
-        pixels = divide_alpha_2_pixels(pixels);
+        pixels = divide_alpha_2_pixels::<ALPHA_FIRST>(pixels);
         _mm_storeu_si128(dst_pixels.as_mut_ptr() as *mut __m128i, pixels);
 
         let dst_reminder = dst_chunks.into_remainder();
@@ -162,7 +182,7 @@ pub(crate) unsafe fn divide_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4])
 }
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U16x4]) {
+pub(crate) unsafe fn divide_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U16x4]) {
     let mut chunks = row.chunks_exact_mut(2);
     foreach_with_pre_reading(
         &mut chunks,
@@ -172,7 +192,7 @@ pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U16x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = divide_alpha_2_pixels(pixels);
+            pixels = divide_alpha_2_pixels::<ALPHA_FIRST>(pixels);
             _mm_storeu_si128(dst_ptr, pixels);
         },
     );
@@ -183,7 +203,7 @@ pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U16x4]) {
         let mut dst_pixels = [U16x4::new([0, 0, 0, 0]); 2];
 
         let mut pixels = _mm_loadu_si128(src_pixels.as_ptr() as *const __m128i);
-        pixels = divide_alpha_2_pixels(pixels);
+        pixels = divide_alpha_2_pixels::<ALPHA_FIRST>(pixels);
         _mm_storeu_si128(dst_pixels.as_mut_ptr() as *mut __m128i, pixels);
         *pixel = dst_pixels[0];
     }
@@ -191,7 +211,12 @@ pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U16x4]) {
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-unsafe fn divide_alpha_2_pixels(pixels: __m128i) -> __m128i {
+unsafe fn divide_alpha_2_pixels<const ALPHA_FIRST: bool>(pixels: __m128i) -> __m128i {
+    let pixels = if ALPHA_FIRST {
+        move_alpha_to_last(pixels)
+    } else {
+        pixels
+    };
     let zero = _mm_setzero_si128();
     let alpha_mask = _mm_set1_epi64x(0xffff000000000000u64 as i64);
     let alpha_max = _mm_set1_ps(65535.0);
@@ -218,5 +243,26 @@ unsafe fn divide_alpha_2_pixels(pixels: __m128i) -> __m128i {
 
     let two_pixels_i16x8 = _mm_packus_epi32(divided_pix0_i32x4, divided_pix1_i32x4);
     let alpha = _mm_and_si128(pixels, alpha_mask);
-    _mm_blendv_epi8(two_pixels_i16x8, alpha, alpha_mask)
+    let res_pixels = _mm_blendv_epi8(two_pixels_i16x8, alpha, alpha_mask);
+    if ALPHA_FIRST {
+        move_alpha_to_first(res_pixels)
+    } else {
+        res_pixels
+    }
+}
+
+/// Converts pixels from ARGB into RGBA order of components.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn move_alpha_to_last(pixels: __m128i) -> __m128i {
+    let shuffle = _mm_set_epi8(9, 8, 15, 14, 13, 12, 11, 10, 1, 0, 7, 6, 5, 4, 3, 2);
+    _mm_shuffle_epi8(pixels, shuffle)
+}
+
+/// Converts pixels from RGBA into ARGB order of components.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn move_alpha_to_first(pixels: __m128i) -> __m128i {
+    let shuffle = _mm_set_epi8(13, 12, 11, 10, 9, 8, 15, 14, 5, 4, 3, 2, 1, 0, 7, 6);
+    _mm_shuffle_epi8(pixels, shuffle)
 }
@@ -45,7 +45,7 @@ unsafe fn multiply_alpha_row(src_row: &[U16x4], dst_row: &mut [U16x4]) {
 
     if !src_remainder.is_empty() {
         let dst_reminder = dst_chunks.into_remainder();
-        native::multiply_alpha_row(src_remainder, dst_reminder);
+        native::multiply_alpha_row::<false>(src_remainder, dst_reminder);
     }
 }
 
@@ -68,7 +68,7 @@ unsafe fn multiply_alpha_row_inplace(row: &mut [U16x4]) {
 
     let remainder = chunks.into_remainder();
     if !remainder.is_empty() {
-        native::multiply_alpha_row_inplace(remainder);
+        native::multiply_alpha_row_inplace::<false>(remainder);
     }
 }
 
@@ -1,7 +1,7 @@
 use super::AlphaMulDiv;
 use crate::cpu_extensions::CpuExtensions;
 use crate::pixels::U8x2;
-use crate::{ImageError, ImageView, ImageViewMut};
+use crate::{AlphaPosition, ImageError, ImageView, ImageViewMut};
 
 #[cfg(target_arch = "x86_64")]
 mod avx2;
@@ -20,6 +20,7 @@ impl AlphaMulDiv for P {
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        _alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         process_two_images! {
             multiple(src_view, dst_view, cpu_extensions);
@@ -30,6 +31,7 @@ impl AlphaMulDiv for P {
     fn multiply_alpha_inplace(
         image_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        _alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         process_one_images! {
             multiply_inplace(image_view, cpu_extensions);
@@ -41,6 +43,7 @@ impl AlphaMulDiv for P {
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        _alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         process_two_images! {
             divide(src_view, dst_view, cpu_extensions);
@@ -51,6 +54,7 @@ impl AlphaMulDiv for P {
     fn divide_alpha_inplace(
         image_view: &mut impl ImageViewMut<Pixel = Self>,
         cpu_extensions: CpuExtensions,
+        _alpha_position: AlphaPosition,
     ) -> Result<(), ImageError> {
         process_one_images! {
             divide_inplace(image_view, cpu_extensions);
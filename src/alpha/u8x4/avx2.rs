@@ -6,28 +6,30 @@ use crate::utils::foreach_with_pre_reading;
 use crate::{simd_utils, ImageView, ImageViewMut};
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn multiply_alpha(
+pub(crate) unsafe fn multiply_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U8x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U8x4>,
 ) {
     let src_rows = src_view.iter_rows(0);
     let dst_rows = dst_view.iter_rows_mut(0);
     for (src_row, dst_row) in src_rows.zip(dst_rows) {
-        multiply_alpha_row(src_row, dst_row);
+        multiply_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn multiply_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U8x4>) {
+pub(crate) unsafe fn multiply_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U8x4>,
+) {
     let rows = image_view.iter_rows_mut(0);
     for row in rows {
-        multiply_alpha_row_inplace(row);
+        multiply_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[inline]
 #[target_feature(enable = "avx2")]
-unsafe fn multiply_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
+unsafe fn multiply_alpha_row<const ALPHA_FIRST: bool>(src_row: &[U8x4], dst_row: &mut [U8x4]) {
     let src_chunks = src_row.chunks_exact(8);
     let src_tail = src_chunks.remainder();
     let mut dst_chunks = dst_row.chunks_exact_mut(8);
@@ -40,19 +42,19 @@ unsafe fn multiply_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = multiply_alpha_8_pixels(pixels);
+            pixels = multiply_alpha_8_pixels::<ALPHA_FIRST>(pixels);
             _mm256_storeu_si256(dst_ptr, pixels);
         },
     );
     if !src_tail.is_empty() {
         let dst_tail = dst_chunks.into_remainder();
-        sse4::multiply_alpha_row(src_tail, dst_tail);
+        sse4::multiply_alpha_row::<ALPHA_FIRST>(src_tail, dst_tail);
     }
 }
 
 #[inline]
 #[target_feature(enable = "avx2")]
-unsafe fn multiply_alpha_row_inplace(row: &mut [U8x4]) {
+unsafe fn multiply_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U8x4]) {
     let mut chunks = row.chunks_exact_mut(8);
     foreach_with_pre_reading(
         &mut chunks,
@@ -62,19 +64,24 @@ unsafe fn multiply_alpha_row_inplace(row: &mut [U8x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = multiply_alpha_8_pixels(pixels);
+            pixels = multiply_alpha_8_pixels::<ALPHA_FIRST>(pixels);
             _mm256_storeu_si256(dst_ptr, pixels);
         },
     );
     let tail = chunks.into_remainder();
     if !tail.is_empty() {
-        sse4::multiply_alpha_row_inplace(tail);
+        sse4::multiply_alpha_row_inplace::<ALPHA_FIRST>(tail);
     }
 }
 
 #[inline]
 #[target_feature(enable = "avx2")]
-unsafe fn multiply_alpha_8_pixels(pixels: __m256i) -> __m256i {
+unsafe fn multiply_alpha_8_pixels<const ALPHA_FIRST: bool>(pixels: __m256i) -> __m256i {
+    let pixels = if ALPHA_FIRST {
+        move_alpha_to_last(pixels)
+    } else {
+        pixels
+    };
     let zero = _mm256_setzero_si256();
     let half = _mm256_set1_epi16(128);
 
@@ -101,13 +108,18 @@ unsafe fn multiply_alpha_8_pixels(pixels: __m256i) -> __m256i {
     let pix2 = _mm256_add_epi16(pix2, _mm256_srli_epi16::<8>(pix2));
     let pix2 = _mm256_srli_epi16::<8>(pix2);
 
-    _mm256_packus_epi16(pix1, pix2)
+    let res_pixels = _mm256_packus_epi16(pix1, pix2);
+    if ALPHA_FIRST {
+        move_alpha_to_first(res_pixels)
+    } else {
+        res_pixels
+    }
 }
 
 // Divide
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn divide_alpha(
+pub(crate) unsafe fn divide_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U8x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U8x4>,
 ) {
@@ -115,20 +127,22 @@ pub(crate) unsafe fn divide_alpha(
     let dst_rows = dst_view.iter_rows_mut(0);
     let rows = src_rows.zip(dst_rows);
     for (src_row, dst_row) in rows {
-        divide_alpha_row(src_row, dst_row);
+        divide_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
 #[target_feature(enable = "avx2")]
-pub(crate) unsafe fn divide_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U8x4>) {
+pub(crate) unsafe fn divide_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U8x4>,
+) {
     let rows = image_view.iter_rows_mut(0);
     for row in rows {
-        divide_alpha_row_inplace(row);
+        divide_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[target_feature(enable = "avx2")]
-unsafe fn divide_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
+unsafe fn divide_alpha_row<const ALPHA_FIRST: bool>(src_row: &[U8x4], dst_row: &mut [U8x4]) {
     let src_chunks = src_row.chunks_exact(8);
     let src_remainder = src_chunks.remainder();
     let mut dst_chunks = dst_row.chunks_exact_mut(8);
@@ -141,19 +155,19 @@ unsafe fn divide_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = divide_alpha_8_pixels(pixels);
+            pixels = divide_alpha_8_pixels::<ALPHA_FIRST>(pixels);
             _mm256_storeu_si256(dst_ptr, pixels);
         },
     );
 
     if !src_remainder.is_empty() {
         let dst_reminder = dst_chunks.into_remainder();
-        sse4::divide_alpha_row(src_remainder, dst_reminder);
+        sse4::divide_alpha_row::<ALPHA_FIRST>(src_remainder, dst_reminder);
     }
 }
 
 #[target_feature(enable = "avx2")]
-unsafe fn divide_alpha_row_inplace(row: &mut [U8x4]) {
+unsafe fn divide_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U8x4]) {
     let mut chunks = row.chunks_exact_mut(8);
     foreach_with_pre_reading(
         &mut chunks,
@@ -163,20 +177,25 @@ unsafe fn divide_alpha_row_inplace(row: &mut [U8x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = divide_alpha_8_pixels(pixels);
+            pixels = divide_alpha_8_pixels::<ALPHA_FIRST>(pixels);
             _mm256_storeu_si256(dst_ptr, pixels);
         },
     );
 
     let tail = chunks.into_remainder();
     if !tail.is_empty() {
-        sse4::divide_alpha_row_inplace(tail);
+        sse4::divide_alpha_row_inplace::<ALPHA_FIRST>(tail);
     }
 }
 
 #[inline]
 #[target_feature(enable = "avx2")]
-unsafe fn divide_alpha_8_pixels(pixels: __m256i) -> __m256i {
+unsafe fn divide_alpha_8_pixels<const ALPHA_FIRST: bool>(pixels: __m256i) -> __m256i {
+    let pixels = if ALPHA_FIRST {
+        move_alpha_to_last(pixels)
+    } else {
+        pixels
+    };
     let zero = _mm256_setzero_si256();
     let alpha_mask = _mm256_set1_epi32(0xff000000u32 as i32);
     #[rustfmt::skip]
@@ -220,5 +239,34 @@ unsafe fn divide_alpha_8_pixels(pixels: __m256i) -> __m256i {
 
     let alpha = _mm256_and_si256(pixels, alpha_mask);
     let rgb = _mm256_packus_epi16(res_components_lo_i16, res_components_hi_i16);
-    _mm256_blendv_epi8(rgb, alpha, alpha_mask)
+    let res_pixels = _mm256_blendv_epi8(rgb, alpha, alpha_mask);
+    if ALPHA_FIRST {
+        move_alpha_to_first(res_pixels)
+    } else {
+        res_pixels
+    }
+}
+
+/// Converts pixels from ARGB into RGBA order of components.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn move_alpha_to_last(pixels: __m256i) -> __m256i {
+    #[rustfmt::skip]
+    let shuffle = _mm256_set_epi8(
+        12, 15, 14, 13, 8, 11, 10, 9, 4, 7, 6, 5, 0, 3, 2, 1,
+        12, 15, 14, 13, 8, 11, 10, 9, 4, 7, 6, 5, 0, 3, 2, 1,
+    );
+    _mm256_shuffle_epi8(pixels, shuffle)
+}
+
+/// Converts pixels from RGBA into ARGB order of components.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn move_alpha_to_first(pixels: __m256i) -> __m256i {
+    #[rustfmt::skip]
+    let shuffle = _mm256_set_epi8(
+        14, 13, 12, 15, 10, 9, 8, 11, 6, 5, 4, 7, 2, 1, 0, 3,
+        14, 13, 12, 15, 10, 9, 8, 11, 6, 5, 4, 7, 2, 1, 0, 3,
+    );
+    _mm256_shuffle_epi8(pixels, shuffle)
 }
@@ -6,7 +6,7 @@ use crate::utils::foreach_with_pre_reading;
 use crate::{ImageView, ImageViewMut};
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn multiply_alpha(
+pub(crate) unsafe fn multiply_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U8x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U8x4>,
 ) {
@@ -14,21 +14,26 @@ pub(crate) unsafe fn multiply_alpha(
     let dst_rows = dst_view.iter_rows_mut(0);
     let rows = src_rows.zip(dst_rows);
     for (src_row, dst_row) in rows {
-        multiply_alpha_row(src_row, dst_row);
+        multiply_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn multiply_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U8x4>) {
+pub(crate) unsafe fn multiply_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U8x4>,
+) {
     let rows = image_view.iter_rows_mut(0);
     for row in rows {
-        multiply_alpha_row_inplace(row);
+        multiply_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn multiply_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
+pub(crate) unsafe fn multiply_alpha_row<const ALPHA_FIRST: bool>(
+    src_row: &[U8x4],
+    dst_row: &mut [U8x4],
+) {
     let src_chunks = src_row.chunks_exact(4);
     let src_remainder = src_chunks.remainder();
     let mut dst_chunks = dst_row.chunks_exact_mut(4);
@@ -41,37 +46,42 @@ pub(crate) unsafe fn multiply_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4])
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = multiply_alpha_4_pixels(pixels);
+            pixels = multiply_alpha_4_pixels::<ALPHA_FIRST>(pixels);
             _mm_storeu_si128(dst_ptr, pixels);
         },
     );
 
     if !src_remainder.is_empty() {
         let dst_reminder = dst_chunks.into_remainder();
-        native::multiply_alpha_row(src_remainder, dst_reminder);
+        native::multiply_alpha_row::<ALPHA_FIRST>(src_remainder, dst_reminder);
     }
 }
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn multiply_alpha_row_inplace(row: &mut [U8x4]) {
+pub(crate) unsafe fn multiply_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U8x4]) {
     let mut chunks = row.chunks_exact_mut(4);
     // Using a simple for-loop in this case is faster than implementation with pre-reading
     for chunk in &mut chunks {
         let mut pixels = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
-        pixels = multiply_alpha_4_pixels(pixels);
+        pixels = multiply_alpha_4_pixels::<ALPHA_FIRST>(pixels);
         _mm_storeu_si128(chunk.as_mut_ptr() as *mut __m128i, pixels);
     }
 
     let tail = chunks.into_remainder();
     if !tail.is_empty() {
-        native::multiply_alpha_row_inplace(tail);
+        native::multiply_alpha_row_inplace::<ALPHA_FIRST>(tail);
     }
 }
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-unsafe fn multiply_alpha_4_pixels(pixels: __m128i) -> __m128i {
+unsafe fn multiply_alpha_4_pixels<const ALPHA_FIRST: bool>(pixels: __m128i) -> __m128i {
+    let pixels = if ALPHA_FIRST {
+        move_alpha_to_last(pixels)
+    } else {
+        pixels
+    };
     let zero = _mm_setzero_si128();
     let half = _mm_set1_epi16(128);
 
@@ -94,13 +104,18 @@ unsafe fn multiply_alpha_4_pixels(pixels: __m128i) -> __m128i {
     let pix2 = _mm_add_epi16(pix2, _mm_srli_epi16::<8>(pix2));
     let pix2 = _mm_srli_epi16::<8>(pix2);
 
-    _mm_packus_epi16(pix1, pix2)
+    let res_pixels = _mm_packus_epi16(pix1, pix2);
+    if ALPHA_FIRST {
+        move_alpha_to_first(res_pixels)
+    } else {
+        res_pixels
+    }
 }
 
 // Divide
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn divide_alpha(
+pub(crate) unsafe fn divide_alpha<const ALPHA_FIRST: bool>(
     src_view: &impl ImageView<Pixel = U8x4>,
     dst_view: &mut impl ImageViewMut<Pixel = U8x4>,
 ) {
@@ -108,21 +123,26 @@ pub(crate) unsafe fn divide_alpha(
     let dst_rows = dst_view.iter_rows_mut(0);
     let rows = src_rows.zip(dst_rows);
     for (src_row, dst_row) in rows {
-        divide_alpha_row(src_row, dst_row);
+        divide_alpha_row::<ALPHA_FIRST>(src_row, dst_row);
     }
 }
 
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn divide_alpha_inplace(image_view: &mut impl ImageViewMut<Pixel = U8x4>) {
+pub(crate) unsafe fn divide_alpha_inplace<const ALPHA_FIRST: bool>(
+    image_view: &mut impl ImageViewMut<Pixel = U8x4>,
+) {
     let rows = image_view.iter_rows_mut(0);
     for row in rows {
-        divide_alpha_row_inplace(row);
+        divide_alpha_row_inplace::<ALPHA_FIRST>(row);
     }
 }
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn divide_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
+pub(crate) unsafe fn divide_alpha_row<const ALPHA_FIRST: bool>(
+    src_row: &[U8x4],
+    dst_row: &mut [U8x4],
+) {
     let src_chunks = src_row.chunks_exact(4);
     let src_remainder = src_chunks.remainder();
     let mut dst_chunks = dst_row.chunks_exact_mut(4);
@@ -135,7 +155,7 @@ pub(crate) unsafe fn divide_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = divide_alpha_4_pixels(pixels);
+            pixels = divide_alpha_4_pixels::<ALPHA_FIRST>(pixels);
             _mm_storeu_si128(dst_ptr, pixels);
         },
     );
@@ -150,7 +170,7 @@ pub(crate) unsafe fn divide_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
 
         let mut dst_buffer = [U8x4::new([0; 4]); 4];
         let src_pixels = _mm_loadu_si128(src_buffer.as_ptr() as *const __m128i);
-        let dst_pixels = divide_alpha_4_pixels(src_pixels);
+        let dst_pixels = divide_alpha_4_pixels::<ALPHA_FIRST>(src_pixels);
         _mm_storeu_si128(dst_buffer.as_mut_ptr() as *mut __m128i, dst_pixels);
 
         dst_buffer
@@ -162,7 +182,7 @@ pub(crate) unsafe fn divide_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U8x4]) {
+pub(crate) unsafe fn divide_alpha_row_inplace<const ALPHA_FIRST: bool>(row: &mut [U8x4]) {
     let mut chunks = row.chunks_exact_mut(4);
     foreach_with_pre_reading(
         &mut chunks,
@@ -172,7 +192,7 @@ pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U8x4]) {
             (pixels, dst_ptr)
         },
         |(mut pixels, dst_ptr)| {
-            pixels = divide_alpha_4_pixels(pixels);
+            pixels = divide_alpha_4_pixels::<ALPHA_FIRST>(pixels);
             _mm_storeu_si128(dst_ptr, pixels);
         },
     );
@@ -187,7 +207,7 @@ pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U8x4]) {
 
         let mut dst_buffer = [U8x4::new([0; 4]); 4];
         let src_pixels = _mm_loadu_si128(src_buffer.as_ptr() as *const __m128i);
-        let dst_pixels = divide_alpha_4_pixels(src_pixels);
+        let dst_pixels = divide_alpha_4_pixels::<ALPHA_FIRST>(src_pixels);
         _mm_storeu_si128(dst_buffer.as_mut_ptr() as *mut __m128i, dst_pixels);
 
         dst_buffer.iter().zip(tail).for_each(|(s, d)| *d = *s);
@@ -196,7 +216,12 @@ pub(crate) unsafe fn divide_alpha_row_inplace(row: &mut [U8x4]) {
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-unsafe fn divide_alpha_4_pixels(src_pixels: __m128i) -> __m128i {
+unsafe fn divide_alpha_4_pixels<const ALPHA_FIRST: bool>(src_pixels: __m128i) -> __m128i {
+    let src_pixels = if ALPHA_FIRST {
+        move_alpha_to_last(src_pixels)
+    } else {
+        src_pixels
+    };
     let zero = _mm_setzero_si128();
     let alpha_mask = _mm_set1_epi32(0xff000000u32 as i32);
     let shuffle1 = _mm_set_epi8(5, 4, 5, 4, 5, 4, 5, 4, 1, 0, 1, 0, 1, 0, 1, 0);
@@ -232,5 +257,26 @@ unsafe fn divide_alpha_4_pixels(src_pixels: __m128i) -> __m128i {
     let alpha = _mm_and_si128(src_pixels, alpha_mask);
     let rgba = _mm_packus_epi16(res_components_lo_i16, res_components_hi_i16);
 
-    _mm_blendv_epi8(rgba, alpha, alpha_mask)
+    let res_pixels = _mm_blendv_epi8(rgba, alpha, alpha_mask);
+    if ALPHA_FIRST {
+        move_alpha_to_first(res_pixels)
+    } else {
+        res_pixels
+    }
+}
+
+/// Converts pixels from ARGB into RGBA order of components.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn move_alpha_to_last(pixels: __m128i) -> __m128i {
+    let shuffle = _mm_set_epi8(12, 15, 14, 13, 8, 11, 10, 9, 4, 7, 6, 5, 0, 3, 2, 1);
+    _mm_shuffle_epi8(pixels, shuffle)
+}
+
+/// Converts pixels from RGBA into ARGB order of components.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn move_alpha_to_first(pixels: __m128i) -> __m128i {
+    let shuffle = _mm_set_epi8(14, 13, 12, 15, 10, 9, 8, 11, 6, 5, 4, 7, 2, 1, 0, 3);
+    _mm_shuffle_epi8(pixels, shuffle)
 }
@@ -39,7 +39,7 @@ unsafe fn multiply_alpha_row(src_row: &[U8x4], dst_row: &mut [U8x4]) {
 
     if !src_remainder.is_empty() {
         let dst_reminder = dst_chunks.into_remainder();
-        native::multiply_alpha_row(src_remainder, dst_reminder);
+        native::multiply_alpha_row::<false>(src_remainder, dst_reminder);
     }
 }
 
@@ -56,7 +56,7 @@ unsafe fn multiply_alpha_row_inplace(row: &mut [U8x4]) {
 
     let tail = chunks.into_remainder();
     if !tail.is_empty() {
-        native::multiply_alpha_row_inplace(tail);
+        native::multiply_alpha_row_inplace::<false>(tail);
     }
 }
 
@@ -130,14 +130,10 @@ unsafe fn array_assume_init<T, const N: usize>(array: [MaybeUninit<T>; N]) -> [T
     // * `MaybeUninit<T>` and T are guaranteed to have the same layout
     // * `MaybeUninit` does not drop, so there are no double-frees
     // And thus the conversion is safe
-    let ret = unsafe {
+    unsafe {
         // core::intrinsics::assert_inhabited::<[T; N]>();
         (&array as *const _ as *const [T; N]).read()
-    };
-
-    // FIXME: required to avoid `~const Destruct` bound
-    core::mem::forget(array);
-    ret
+    }
 }
 
 /// Version of [`iter_next_chunk`] using a passed-in slice in order to avoid
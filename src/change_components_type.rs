@@ -1,6 +1,7 @@
 use crate::pixels::{
-    F32x2, F32x3, F32x4, InnerPixel, IntoPixelComponent, U16x2, U16x3, U16x4, U8x2, U8x3, U8x4,
-    F32, I32, U16, U8,
+    F16x2, F16x3, F16x4, F32x2, F32x3, F32x4, F64x2, F64x3, F64x4, I32x2, I32x3, I32x4, InnerPixel,
+    IntoPixelComponent, U16x2, U16x3, U16x4, U32x2, U32x3, U32x4, U8x2, U8x3, U8x4, F16, F32, F64,
+    I32, U16, U32, U8,
 };
 use crate::{
     try_pixel_type, DifferentDimensionsError, ImageView, ImageViewMut, IntoImageView,
@@ -39,28 +40,39 @@ pub fn change_type_of_pixel_components(
             (PT::U8, U8),
             (PT::U16, U16),
             (PT::I32, I32),
-            (PT::F32, F32)
+            (PT::F32, F32),
+            (PT::F16, F16),
+            (PT::U32, U32)
         ),
         PixelType::U8x2 => map_dst!(
             U8x2,
             dst_pixel_type,
             (PT::U8x2, U8x2),
             (PT::U16x2, U16x2),
-            (PT::F32x2, F32x2)
+            (PT::F32x2, F32x2),
+            (PT::F16x2, F16x2),
+            (PT::I32x2, I32x2),
+            (PT::U32x2, U32x2)
         ),
         PixelType::U8x3 => map_dst!(
             U8x3,
             dst_pixel_type,
             (PT::U8x3, U8x3),
             (PT::U16x3, U16x3),
-            (PT::F32x3, F32x3)
+            (PT::F32x3, F32x3),
+            (PT::F16x3, F16x3),
+            (PT::I32x3, I32x3),
+            (PT::U32x3, U32x3)
         ),
         PixelType::U8x4 => map_dst!(
             U8x4,
             dst_pixel_type,
             (PT::U8x4, U8x4),
             (PT::U16x4, U16x4),
-            (PT::F32x4, F32x4)
+            (PT::F32x4, F32x4),
+            (PT::F16x4, F16x4),
+            (PT::I32x4, I32x4),
+            (PT::U32x4, U32x4)
         ),
         PixelType::U16 => map_dst!(
             U16,
@@ -68,28 +80,39 @@ pub fn change_type_of_pixel_components(
             (PT::U8, U8),
             (PT::U16, U16),
             (PT::I32, I32),
-            (PT::F32, F32)
+            (PT::F32, F32),
+            (PT::F16, F16),
+            (PT::U32, U32)
         ),
         PixelType::U16x2 => map_dst!(
             U16x2,
             dst_pixel_type,
             (PT::U8x2, U8x2),
             (PT::U16x2, U16x2),
-            (PT::F32x2, F32x2)
+            (PT::F32x2, F32x2),
+            (PT::F16x2, F16x2),
+            (PT::I32x2, I32x2),
+            (PT::U32x2, U32x2)
         ),
         PixelType::U16x3 => map_dst!(
             U16x3,
             dst_pixel_type,
             (PT::U8x3, U8x3),
             (PT::U16x3, U16x3),
-            (PT::F32x3, F32x3)
+            (PT::F32x3, F32x3),
+            (PT::F16x3, F16x3),
+            (PT::I32x3, I32x3),
+            (PT::U32x3, U32x3)
         ),
         PixelType::U16x4 => map_dst!(
             U16x4,
             dst_pixel_type,
             (PT::U8x4, U8x4),
             (PT::U16x4, U16x4),
-            (PT::F32x4, F32x4)
+            (PT::F32x4, F32x4),
+            (PT::F16x4, F16x4),
+            (PT::I32x4, I32x4),
+            (PT::U32x4, U32x4)
         ),
         PixelType::I32 => map_dst!(
             I32,
@@ -97,7 +120,8 @@ pub fn change_type_of_pixel_components(
             (PT::U8, U8),
             (PT::U16, U16),
             (PT::I32, I32),
-            (PT::F32, F32)
+            (PT::F32, F32),
+            (PT::F64, F64)
         ),
         PixelType::F32 => map_dst!(
             F32,
@@ -105,28 +129,170 @@ pub fn change_type_of_pixel_components(
             (PT::U8, U8),
             (PT::U16, U16),
             (PT::I32, I32),
-            (PT::F32, F32)
+            (PT::F32, F32),
+            (PT::F16, F16),
+            (PT::F64, F64),
+            (PT::U32, U32)
         ),
         PixelType::F32x2 => map_dst!(
             F32x2,
             dst_pixel_type,
             (PT::U8x2, U8x2),
             (PT::U16x2, U16x2),
-            (PT::F32x2, F32x2)
+            (PT::F32x2, F32x2),
+            (PT::F16x2, F16x2),
+            (PT::F64x2, F64x2),
+            (PT::I32x2, I32x2),
+            (PT::U32x2, U32x2)
         ),
         PixelType::F32x3 => map_dst!(
             F32x3,
             dst_pixel_type,
             (PT::U8x3, U8x3),
             (PT::U16x3, U16x3),
-            (PT::F32x3, F32x3)
+            (PT::F32x3, F32x3),
+            (PT::F16x3, F16x3),
+            (PT::F64x3, F64x3),
+            (PT::I32x3, I32x3),
+            (PT::U32x3, U32x3)
         ),
         PixelType::F32x4 => map_dst!(
             F32x4,
             dst_pixel_type,
             (PT::U8x4, U8x4),
             (PT::U16x4, U16x4),
-            (PT::F32x4, F32x4)
+            (PT::F32x4, F32x4),
+            (PT::F16x4, F16x4),
+            (PT::F64x4, F64x4),
+            (PT::I32x4, I32x4),
+            (PT::U32x4, U32x4)
+        ),
+        PixelType::F16 => map_dst!(
+            F16,
+            dst_pixel_type,
+            (PT::U8, U8),
+            (PT::U16, U16),
+            (PT::F32, F32),
+            (PT::F16, F16)
+        ),
+        PixelType::F16x2 => map_dst!(
+            F16x2,
+            dst_pixel_type,
+            (PT::U8x2, U8x2),
+            (PT::U16x2, U16x2),
+            (PT::F32x2, F32x2),
+            (PT::F16x2, F16x2)
+        ),
+        PixelType::F16x3 => map_dst!(
+            F16x3,
+            dst_pixel_type,
+            (PT::U8x3, U8x3),
+            (PT::U16x3, U16x3),
+            (PT::F32x3, F32x3),
+            (PT::F16x3, F16x3)
+        ),
+        PixelType::F16x4 => map_dst!(
+            F16x4,
+            dst_pixel_type,
+            (PT::U8x4, U8x4),
+            (PT::U16x4, U16x4),
+            (PT::F32x4, F32x4),
+            (PT::F16x4, F16x4)
+        ),
+        PixelType::F64 => map_dst!(
+            F64,
+            dst_pixel_type,
+            (PT::I32, I32),
+            (PT::F32, F32),
+            (PT::F64, F64),
+            (PT::U32, U32)
+        ),
+        PixelType::F64x2 => map_dst!(
+            F64x2,
+            dst_pixel_type,
+            (PT::F32x2, F32x2),
+            (PT::F64x2, F64x2),
+            (PT::I32x2, I32x2),
+            (PT::U32x2, U32x2)
+        ),
+        PixelType::F64x3 => map_dst!(
+            F64x3,
+            dst_pixel_type,
+            (PT::F32x3, F32x3),
+            (PT::F64x3, F64x3),
+            (PT::I32x3, I32x3),
+            (PT::U32x3, U32x3)
+        ),
+        PixelType::F64x4 => map_dst!(
+            F64x4,
+            dst_pixel_type,
+            (PT::F32x4, F32x4),
+            (PT::F64x4, F64x4),
+            (PT::I32x4, I32x4),
+            (PT::U32x4, U32x4)
+        ),
+        PixelType::I32x2 => map_dst!(
+            I32x2,
+            dst_pixel_type,
+            (PT::U8x2, U8x2),
+            (PT::U16x2, U16x2),
+            (PT::I32x2, I32x2),
+            (PT::F32x2, F32x2),
+            (PT::F64x2, F64x2)
+        ),
+        PixelType::I32x3 => map_dst!(
+            I32x3,
+            dst_pixel_type,
+            (PT::U8x3, U8x3),
+            (PT::U16x3, U16x3),
+            (PT::I32x3, I32x3),
+            (PT::F32x3, F32x3),
+            (PT::F64x3, F64x3)
+        ),
+        PixelType::I32x4 => map_dst!(
+            I32x4,
+            dst_pixel_type,
+            (PT::U8x4, U8x4),
+            (PT::U16x4, U16x4),
+            (PT::I32x4, I32x4),
+            (PT::F32x4, F32x4),
+            (PT::F64x4, F64x4)
+        ),
+        PixelType::U32 => map_dst!(
+            U32,
+            dst_pixel_type,
+            (PT::U8, U8),
+            (PT::U16, U16),
+            (PT::U32, U32),
+            (PT::F32, F32),
+            (PT::F64, F64)
+        ),
+        PixelType::U32x2 => map_dst!(
+            U32x2,
+            dst_pixel_type,
+            (PT::U8x2, U8x2),
+            (PT::U16x2, U16x2),
+            (PT::U32x2, U32x2),
+            (PT::F32x2, F32x2),
+            (PT::F64x2, F64x2)
+        ),
+        PixelType::U32x3 => map_dst!(
+            U32x3,
+            dst_pixel_type,
+            (PT::U8x3, U8x3),
+            (PT::U16x3, U16x3),
+            (PT::U32x3, U32x3),
+            (PT::F32x3, F32x3),
+            (PT::F64x3, F64x3)
+        ),
+        PixelType::U32x4 => map_dst!(
+            U32x4,
+            dst_pixel_type,
+            (PT::U8x4, U8x4),
+            (PT::U16x4, U16x4),
+            (PT::U32x4, U32x4),
+            (PT::F32x4, F32x4),
+            (PT::F64x4, F64x4)
         ),
     }
 
@@ -0,0 +1,331 @@
+use core::fmt::{Debug, Formatter};
+
+#[allow(unused_imports)]
+// It is used to implement floats in no_std
+use crate::compat::*;
+use crate::pixels::{
+    F16x2, F16x3, F16x4, F32x2, F32x3, F32x4, F64x2, F64x3, F64x4, I32x2, I32x3, I32x4, InnerPixel,
+    IntoPixelComponent, PixelType, U16x2, U16x3, U16x4, U32x2, U32x3, U32x4, U8x2, U8x3, U8x4, F16,
+    F32, F64, I32, U16, U32, U8,
+};
+use crate::{AlphaPosition, ImageView, ImageViewMut, PixelComponentMapper, PixelTrait};
+
+use super::mappers;
+
+/// Color space of source and destination images.
+///
+/// If the color space is not linear, [Resizer](crate::Resizer) converts
+/// the source image into linear color space with higher precision
+/// of pixel components, resizes it and converts the result back into
+/// the color space and pixel type of the destination image.
+/// Images are converted by bands of rows right before the first pass
+/// of convolution and right after the last one, so full-size copies
+/// of images in linear color space are not allocated.
+///
+/// The alpha channel is never mapped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ColorSpace {
+    /// Images are resized as is, without any conversion.
+    #[default]
+    Linear,
+    /// Images are in the sRGB color space.
+    Srgb,
+    /// Images are encoded with gamma 2.2.
+    Gamma22,
+}
+
+type MapFunction = fn(f32) -> f32;
+
+impl ColorSpace {
+    /// Returns functions to map values into linear color space and back.
+    fn map_functions(self) -> (MapFunction, MapFunction) {
+        match self {
+            Self::Linear => (core::convert::identity, core::convert::identity),
+            Self::Srgb => (mappers::srgb_to_linear, mappers::linear_to_srgb),
+            Self::Gamma22 => (mappers::gamma_into_linear, mappers::linear_into_gamma),
+        }
+    }
+}
+
+/// Returns the type of pixels of an image that is used to store
+/// the source image after conversion into linear color space.
+pub(crate) fn linear_pixel_type(pixel_type: PixelType) -> Option<PixelType> {
+    use PixelType as PT;
+
+    match pixel_type {
+        PT::U8 | PT::U16 => Some(PT::U16),
+        PT::U8x2 | PT::U16x2 => Some(PT::U16x2),
+        PT::U8x3 | PT::U16x3 => Some(PT::U16x3),
+        PT::U8x4 | PT::U16x4 => Some(PT::U16x4),
+        PT::F32 | PT::F32x2 | PT::F32x3 | PT::F32x4 => Some(pixel_type),
+        PT::F16 => Some(PT::F32),
+        PT::F16x2 => Some(PT::F32x2),
+        PT::F16x3 => Some(PT::F32x3),
+        PT::F16x4 => Some(PT::F32x4),
+        _ => None,
+    }
+}
+
+/// Converter of images into linear color space and back.
+pub(crate) struct ColorSpaceConverter {
+    color_space: ColorSpace,
+    /// The alpha channel at this position isn't mapped.
+    alpha_position: AlphaPosition,
+    mapper: Option<Box<PixelComponentMapper>>,
+}
+
+impl ColorSpaceConverter {
+    pub fn new(color_space: ColorSpace) -> Self {
+        Self {
+            color_space,
+            alpha_position: AlphaPosition::Last,
+            mapper: None,
+        }
+    }
+
+    #[inline]
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
+    #[inline]
+    pub fn set_alpha_position(&mut self, alpha_position: AlphaPosition) {
+        self.alpha_position = alpha_position;
+    }
+
+    /// Returns a mapper with tables for integer pixel components.
+    /// The tables are created at the first call.
+    fn mapper(&mut self) -> &PixelComponentMapper {
+        let color_space = self.color_space;
+        self.mapper.get_or_insert_with(|| {
+            let (forward, backward) = color_space.map_functions();
+            Box::new(PixelComponentMapper::new(forward, backward))
+        })
+    }
+}
+
+impl Clone for ColorSpaceConverter {
+    fn clone(&self) -> Self {
+        // Mapping tables are big, so they are created again on demand.
+        let mut converter = Self::new(self.color_space);
+        converter.alpha_position = self.alpha_position;
+        converter
+    }
+}
+
+impl Debug for ColorSpaceConverter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ColorSpaceConverter")
+            .field("color_space", &self.color_space)
+            .field("alpha_position", &self.alpha_position)
+            .finish_non_exhaustive()
+    }
+}
+
+pub(crate) trait ColorSpaceMapping: InnerPixel {
+    /// Type of pixels to store an image in linear color space.
+    type Linear: PixelTrait;
+
+    fn into_linear(
+        converter: &mut ColorSpaceConverter,
+        src_view: &impl ImageView<Pixel = Self>,
+        dst_view: &mut impl ImageViewMut<Pixel = Self::Linear>,
+    );
+
+    fn from_linear(
+        converter: &mut ColorSpaceConverter,
+        src_view: &impl ImageView<Pixel = Self::Linear>,
+        dst_view: &mut impl ImageViewMut<Pixel = Self>,
+    );
+}
+
+macro_rules! integer_mapping {
+    ($pixel: ty, $linear: ty, $forward_table: ident, $backward_table: ident) => {
+        impl ColorSpaceMapping for $pixel {
+            type Linear = $linear;
+
+            fn into_linear(
+                converter: &mut ColorSpaceConverter,
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self::Linear>,
+            ) {
+                let alpha_position = converter.alpha_position;
+                let tables = &converter.mapper().forward_mapping_tables;
+                tables.$forward_table.map_image_with_alpha_position_typed(
+                    src_view,
+                    dst_view,
+                    alpha_position,
+                );
+            }
+
+            fn from_linear(
+                converter: &mut ColorSpaceConverter,
+                src_view: &impl ImageView<Pixel = Self::Linear>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+            ) {
+                let alpha_position = converter.alpha_position;
+                let tables = &converter.mapper().backward_mapping_tables;
+                tables.$backward_table.map_image_with_alpha_position_typed(
+                    src_view,
+                    dst_view,
+                    alpha_position,
+                );
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "only_u8x4"))]
+integer_mapping!(U8, U16, u8_u16, u16_u8);
+#[cfg(not(feature = "only_u8x4"))]
+integer_mapping!(U8x2, U16x2, u8_u16, u16_u8);
+#[cfg(not(feature = "only_u8x4"))]
+integer_mapping!(U8x3, U16x3, u8_u16, u16_u8);
+integer_mapping!(U8x4, U16x4, u8_u16, u16_u8);
+#[cfg(not(feature = "only_u8x4"))]
+integer_mapping!(U16, U16, u16_u16, u16_u16);
+#[cfg(not(feature = "only_u8x4"))]
+integer_mapping!(U16x2, U16x2, u16_u16, u16_u16);
+#[cfg(not(feature = "only_u8x4"))]
+integer_mapping!(U16x3, U16x3, u16_u16, u16_u16);
+integer_mapping!(U16x4, U16x4, u16_u16, u16_u16);
+
+/// Maps components of an image with floating-point components
+/// with help of the function for `f32` values.
+#[cfg(not(feature = "only_u8x4"))]
+fn map_float_image<S, D>(
+    src_view: &impl ImageView<Pixel = S>,
+    dst_view: &mut impl ImageViewMut<Pixel = D>,
+    map_func: fn(f32) -> f32,
+) where
+    S: InnerPixel<Component: IntoPixelComponent<f32>>,
+    D: InnerPixel<CountOfComponents = S::CountOfComponents>,
+    f32: IntoPixelComponent<D::Component>,
+{
+    let count_of_components = S::count_of_components();
+    // Don't map alpha channel
+    let has_alpha = count_of_components == 2 || count_of_components == 4;
+    for (s_row, d_row) in src_view.iter_rows(0).zip(dst_view.iter_rows_mut(0)) {
+        let s_comp = S::components(s_row);
+        let d_comp = D::components_mut(d_row);
+        for (i, (&s, d)) in s_comp.iter().zip(d_comp).enumerate() {
+            let s: f32 = s.into_component();
+            *d = if has_alpha && (i + 1) % count_of_components == 0 {
+                s
+            } else if s < 0. {
+                // Mirror the function for negative values.
+                -map_func(-s)
+            } else {
+                map_func(s)
+            }
+            .into_component();
+        }
+    }
+}
+
+#[allow(unused_macros)]
+macro_rules! float_mapping {
+    ($pixel: ty, $linear: ty) => {
+        impl ColorSpaceMapping for $pixel {
+            type Linear = $linear;
+
+            fn into_linear(
+                converter: &mut ColorSpaceConverter,
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self::Linear>,
+            ) {
+                let (forward, _) = converter.color_space.map_functions();
+                map_float_image(src_view, dst_view, forward);
+            }
+
+            fn from_linear(
+                converter: &mut ColorSpaceConverter,
+                src_view: &impl ImageView<Pixel = Self::Linear>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+            ) {
+                let (_, backward) = converter.color_space.map_functions();
+                map_float_image(src_view, dst_view, backward);
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "only_u8x4"))]
+float_mapping!(F32, F32);
+#[cfg(not(feature = "only_u8x4"))]
+float_mapping!(F32x2, F32x2);
+#[cfg(not(feature = "only_u8x4"))]
+float_mapping!(F32x3, F32x3);
+#[cfg(not(feature = "only_u8x4"))]
+float_mapping!(F32x4, F32x4);
+#[cfg(not(feature = "only_u8x4"))]
+float_mapping!(F16, F32);
+#[cfg(not(feature = "only_u8x4"))]
+float_mapping!(F16x2, F32x2);
+#[cfg(not(feature = "only_u8x4"))]
+float_mapping!(F16x3, F32x3);
+#[cfg(not(feature = "only_u8x4"))]
+float_mapping!(F16x4, F32x4);
+
+/// Pixels with `i32`, `u32` and `f64` components don't have
+/// a color space, so they are copied as is.
+#[allow(unused_macros)]
+macro_rules! copy_mapping {
+    ($pixel: ty) => {
+        impl ColorSpaceMapping for $pixel {
+            type Linear = $pixel;
+
+            fn into_linear(
+                _converter: &mut ColorSpaceConverter,
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self::Linear>,
+            ) {
+                copy_image(src_view, dst_view);
+            }
+
+            fn from_linear(
+                _converter: &mut ColorSpaceConverter,
+                src_view: &impl ImageView<Pixel = Self::Linear>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+            ) {
+                copy_image(src_view, dst_view);
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(I32);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(I32x2);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(I32x3);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(I32x4);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(U32);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(U32x2);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(U32x3);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(U32x4);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(F64);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(F64x2);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(F64x3);
+#[cfg(not(feature = "only_u8x4"))]
+copy_mapping!(F64x4);
+
+#[cfg(not(feature = "only_u8x4"))]
+fn copy_image<P: InnerPixel>(
+    src_view: &impl ImageView<Pixel = P>,
+    dst_view: &mut impl ImageViewMut<Pixel = P>,
+) {
+    for (s_row, d_row) in src_view.iter_rows(0).zip(dst_view.iter_rows_mut(0)) {
+        d_row.copy_from_slice(s_row);
+    }
+}
@@ -3,11 +3,11 @@
 use crate::compat::*;
 use crate::PixelComponentMapper;
 
-fn gamma_into_linear(input: f32) -> f32 {
+pub(crate) fn gamma_into_linear(input: f32) -> f32 {
     input.powf(2.2)
 }
 
-fn linear_into_gamma(input: f32) -> f32 {
+pub(crate) fn linear_into_gamma(input: f32) -> f32 {
     input.powf(1.0 / 2.2)
 }
 
@@ -18,7 +18,7 @@ pub fn create_gamma_22_mapper() -> PixelComponentMapper {
 
 /// https://en.wikipedia.org/wiki/SRGB#From_sRGB_to_CIE_XYZ
 /// http://www.ericbrasseur.org/gamma.html?i=2#formulas
-fn srgb_to_linear(input: f32) -> f32 {
+pub(crate) fn srgb_to_linear(input: f32) -> f32 {
     if input < 0.04045 {
         input / 12.92
     } else {
@@ -29,7 +29,7 @@ fn srgb_to_linear(input: f32) -> f32 {
 
 /// https://en.wikipedia.org/wiki/SRGB#From_CIE_XYZ_to_sRGB
 /// http://www.ericbrasseur.org/gamma.html?i=2#formulas
-fn linear_to_srgb(input: f32) -> f32 {
+pub(crate) fn linear_to_srgb(input: f32) -> f32 {
     if input < 0.0031308 {
         12.92 * input
     } else {
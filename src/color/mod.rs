@@ -9,10 +9,11 @@ use crate::pixels::{
     U8x3, U8x4, Values, U16, U8,
 };
 use crate::{
-    try_pixel_type, ImageView, ImageViewMut, IntoImageView, IntoImageViewMut, MappingError,
-    PixelTrait,
+    try_pixel_type, AlphaPosition, ImageView, ImageViewMut, IntoImageView, IntoImageViewMut,
+    MappingError, PixelTrait,
 };
 
+pub(crate) mod color_space;
 pub(crate) mod mappers;
 
 trait FromF32 {
@@ -68,12 +69,19 @@ where
         }
     }
 
-    fn map_with_gaps<In>(&self, src_buffer: &[In], dst_buffer: &mut [Out], gap_step: usize)
-    where
+    /// Maps all components except every `gap_step`-th component
+    /// starting from the component with index `gap_index`.
+    fn map_with_gaps<In>(
+        &self,
+        src_buffer: &[In],
+        dst_buffer: &mut [Out],
+        gap_step: usize,
+        gap_index: usize,
+    ) where
         In: IntoPixelComponent<Out> + Into<usize>,
     {
         for (i, (&src, dst)) in src_buffer.iter().zip(dst_buffer).enumerate() {
-            if (i + 1) % gap_step != 0 {
+            if i % gap_step != gap_index {
                 *dst = self.0[src.into()];
             } else {
                 *dst = src.into_component();
@@ -105,20 +113,35 @@ where
             + Into<usize>,
         D: PixelTrait<Component = Out, CountOfComponents = S::CountOfComponents>,
     {
-        let (src_view, dst_view) =
+        let (src_view, mut dst_view) =
             match (src_image.image_view::<S>(), dst_image.image_view_mut::<D>()) {
                 (Some(src_view), Some(dst_view)) => (src_view, dst_view),
                 _ => return Err(MappingError::UnsupportedCombinationOfImageTypes),
             };
 
-        self.map_image_typed(src_view, dst_view);
+        self.map_image_typed(&src_view, &mut dst_view);
         Ok(())
     }
 
     pub fn map_image_typed<S, D>(
         &self,
-        src_view: impl ImageView<Pixel = S>,
-        mut dst_view: impl ImageViewMut<Pixel = D>,
+        src_view: &impl ImageView<Pixel = S>,
+        dst_view: &mut impl ImageViewMut<Pixel = D>,
+    ) where
+        S: InnerPixel,
+        <S as InnerPixel>::Component: PixelComponent<CountOfComponentValues = Values<SIZE>>
+            + IntoPixelComponent<Out>
+            + Into<usize>,
+        D: InnerPixel<Component = Out, CountOfComponents = S::CountOfComponents>,
+    {
+        self.map_image_with_alpha_position_typed(src_view, dst_view, AlphaPosition::Last);
+    }
+
+    pub(crate) fn map_image_with_alpha_position_typed<S, D>(
+        &self,
+        src_view: &impl ImageView<Pixel = S>,
+        dst_view: &mut impl ImageViewMut<Pixel = D>,
+        alpha_position: AlphaPosition,
     ) where
         S: InnerPixel,
         <S as InnerPixel>::Component: PixelComponent<CountOfComponentValues = Values<SIZE>>
@@ -130,8 +153,14 @@ where
             let s_comp = S::components(s_row);
             let d_comp = D::components_mut(d_row);
             match S::CountOfComponents::count() {
-                2 => self.map_with_gaps(s_comp, d_comp, 2), // Don't map alpha channel
-                4 => self.map_with_gaps(s_comp, d_comp, 4), // Don't map alpha channel
+                // Don't map alpha channel
+                count @ (2 | 4) => {
+                    let alpha_index = match alpha_position {
+                        AlphaPosition::Last => count - 1,
+                        AlphaPosition::First => 0,
+                    };
+                    self.map_with_gaps(s_comp, d_comp, count, alpha_index)
+                }
                 _ => self.map(s_comp, d_comp),
             }
         }
@@ -181,7 +210,8 @@ struct MappingTablesGroup {
 /// This structure holds tables for mapping values of pixel's
 /// components in forward and backward directions.
 ///
-/// All pixel types except `I32` and `F32xN` are supported.
+/// All pixel types except `I32xN`, `U32xN`, `F32xN`, `F16xN` and `F64xN`
+/// are supported.
 ///
 /// Source and destination images may have different bit depth of one
 /// pixel component.
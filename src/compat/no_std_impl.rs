@@ -1,5 +1,8 @@
+pub use alloc::borrow::Cow;
 pub use alloc::borrow::ToOwned;
 pub use alloc::boxed::Box;
+#[cfg(target_has_atomic = "ptr")]
+pub use alloc::sync::Arc;
 pub use alloc::vec;
 pub use alloc::vec::Vec;
 
@@ -19,6 +22,15 @@ cfg_if::cfg_if! {
         pub fn has_avx2() -> bool {
             cpuid_avx2::get()
         }
+
+        pub fn has_f16c() -> bool {
+            // `cpufeatures` doesn't detect F16C, so the bit 29 of ECX
+            // returned by CPUID with EAX=1 is checked. Instructions of F16C
+            // also require support of AVX by OS that is checked with AVX2.
+            #[allow(unused_unsafe)] // `__cpuid()` is unsafe in old versions of Rust
+            let ecx = unsafe { core::arch::x86_64::__cpuid(1).ecx };
+            has_avx2() && ecx & (1 << 29) != 0
+        }
     }
 }
 
@@ -1,5 +1,8 @@
+pub use std::borrow::Cow;
 pub use std::borrow::ToOwned;
 pub use std::boxed::Box;
+#[cfg(target_has_atomic = "ptr")]
+pub use std::sync::Arc;
 pub use std::vec;
 pub use std::vec::Vec;
 
@@ -12,6 +15,10 @@ cfg_if::cfg_if! {
         pub fn has_avx2() -> bool {
             std::is_x86_feature_detected!("avx2")
         }
+
+        pub fn has_f16c() -> bool {
+            std::is_x86_feature_detected!("f16c")
+        }
     }
 }
 
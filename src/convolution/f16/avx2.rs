@@ -0,0 +1,52 @@
+use core::arch::x86_64::*;
+
+use super::F16Converter;
+use crate::pixels::f16;
+
+/// Conversion of `f16` values with help of the F16C extension.
+///
+/// It must be used only if the CPU supports AVX2 and F16C.
+pub(crate) struct F16c;
+
+impl F16Converter for F16c {
+    #[inline]
+    fn to_f32(src: &[f16], dst: &mut [f32]) {
+        unsafe { f16_to_f32(src, dst) }
+    }
+
+    #[inline]
+    fn from_f32(src: &[f32], dst: &mut [f16]) {
+        unsafe { f32_to_f16(src, dst) }
+    }
+}
+
+#[target_feature(enable = "avx2,f16c")]
+unsafe fn f16_to_f32(src: &[f16], dst: &mut [f32]) {
+    let mut src_chunks = src.chunks_exact(8);
+    let mut dst_chunks = dst.chunks_exact_mut(8);
+    for (src_chunk, dst_chunk) in (&mut src_chunks).zip(&mut dst_chunks) {
+        let halfs = _mm_loadu_si128(src_chunk.as_ptr() as *const __m128i);
+        _mm256_storeu_ps(dst_chunk.as_mut_ptr(), _mm256_cvtph_ps(halfs));
+    }
+
+    let src_tail = src_chunks.remainder();
+    for (d, s) in dst_chunks.into_remainder().iter_mut().zip(src_tail) {
+        *d = s.to_f32();
+    }
+}
+
+#[target_feature(enable = "avx2,f16c")]
+unsafe fn f32_to_f16(src: &[f32], dst: &mut [f16]) {
+    let mut src_chunks = src.chunks_exact(8);
+    let mut dst_chunks = dst.chunks_exact_mut(8);
+    for (src_chunk, dst_chunk) in (&mut src_chunks).zip(&mut dst_chunks) {
+        let floats = _mm256_loadu_ps(src_chunk.as_ptr());
+        let halfs = _mm256_cvtps_ph::<_MM_FROUND_TO_NEAREST_INT>(floats);
+        _mm_storeu_si128(dst_chunk.as_mut_ptr() as *mut __m128i, halfs);
+    }
+
+    let src_tail = src_chunks.remainder();
+    for (d, &s) in dst_chunks.into_remainder().iter_mut().zip(src_tail) {
+        *d = f16::from_f32(s);
+    }
+}
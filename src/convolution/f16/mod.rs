@@ -0,0 +1,105 @@
+use super::{Coefficients, Convolution};
+#[allow(unused_imports)]
+use crate::compat::*;
+use crate::cpu_extensions::CpuExtensions;
+use crate::pixels::{f16, F16x2, F16x3, F16x4, InnerPixel, F16};
+use crate::{ImageView, ImageViewMut};
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+mod native;
+
+/// Converter of rows of `f16` components into `f32` components and back.
+///
+/// Convolution of pixels with `f16` components accumulates sums in `f32`,
+/// so only the conversion depends on the CPU extension.
+pub(crate) trait F16Converter {
+    fn to_f32(src: &[f16], dst: &mut [f32]);
+
+    fn from_f32(src: &[f32], dst: &mut [f16]);
+}
+
+macro_rules! impl_convolution {
+    ($pixel: ty) => {
+        impl Convolution for $pixel {
+            fn horiz_convolution(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                offset: u32,
+                coeffs: &Coefficients,
+                cpu_extensions: CpuExtensions,
+            ) {
+                debug_assert!(src_view.height() - offset >= dst_view.height());
+                let coeffs_ref = coeffs;
+
+                try_process_in_threads_h! {
+                    horiz_convolution(
+                        src_view,
+                        dst_view,
+                        offset,
+                        coeffs_ref,
+                        cpu_extensions,
+                    );
+                }
+            }
+
+            fn vert_convolution(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                offset: u32,
+                coeffs: &Coefficients,
+                cpu_extensions: CpuExtensions,
+            ) {
+                debug_assert!(src_view.width() - offset >= dst_view.width());
+                let coeffs_ref = coeffs;
+
+                try_process_in_threads_v! {
+                    vert_convolution(
+                        src_view,
+                        dst_view,
+                        offset,
+                        coeffs_ref,
+                        cpu_extensions,
+                    );
+                }
+            }
+        }
+    };
+}
+
+impl_convolution!(F16);
+impl_convolution!(F16x2);
+impl_convolution!(F16x3);
+impl_convolution!(F16x4);
+
+fn horiz_convolution<T: InnerPixel<Component = f16>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+    cpu_extensions: CpuExtensions,
+) {
+    match cpu_extensions {
+        #[cfg(target_arch = "x86_64")]
+        CpuExtensions::Avx2 if has_f16c() => {
+            native::horiz_convolution::<_, avx2::F16c>(src_view, dst_view, offset, coeffs)
+        }
+        _ => native::horiz_convolution::<_, native::Native>(src_view, dst_view, offset, coeffs),
+    }
+}
+
+fn vert_convolution<T: InnerPixel<Component = f16>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+    cpu_extensions: CpuExtensions,
+) {
+    match cpu_extensions {
+        #[cfg(target_arch = "x86_64")]
+        CpuExtensions::Avx2 if has_f16c() => {
+            native::vert_convolution::<_, avx2::F16c>(src_view, dst_view, offset, coeffs)
+        }
+        _ => native::vert_convolution::<_, native::Native>(src_view, dst_view, offset, coeffs),
+    }
+}
@@ -0,0 +1,115 @@
+use super::F16Converter;
+use crate::compat::*;
+use crate::convolution::Coefficients;
+use crate::pixels::{f16, InnerPixel};
+use crate::{ImageView, ImageViewMut};
+
+/// Number of components of a row that are accumulated at once
+/// by the vertical convolution.
+const CHUNK_SIZE: usize = 256;
+
+/// Software conversion of `f16` values.
+pub(crate) struct Native;
+
+impl F16Converter for Native {
+    #[inline]
+    fn to_f32(src: &[f16], dst: &mut [f32]) {
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d = s.to_f32();
+        }
+    }
+
+    #[inline]
+    fn from_f32(src: &[f32], dst: &mut [f16]) {
+        for (d, &s) in dst.iter_mut().zip(src) {
+            *d = f16::from_f32(s);
+        }
+    }
+}
+
+pub(crate) fn horiz_convolution<T, C>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+) where
+    T: InnerPixel<Component = f16>,
+    C: F16Converter,
+{
+    let components_count = T::count_of_components();
+    let window_size = coeffs.window_size;
+    let coeffs_f32: Vec<f32> = coeffs.values.iter().map(|&k| k as f32).collect();
+
+    let mut src_buf = vec![0f32; src_view.width() as usize * components_count];
+    let mut dst_buf = vec![0f32; dst_view.width() as usize * components_count];
+
+    let src_rows = src_view.iter_rows(offset);
+    let dst_rows = dst_view.iter_rows_mut(0);
+    for (dst_row, src_row) in dst_rows.zip(src_rows) {
+        C::to_f32(T::components(src_row), &mut src_buf);
+
+        let dst_pixels = dst_buf.chunks_exact_mut(components_count);
+        let ks_iter = coeffs_f32.chunks_exact(window_size);
+        for ((dst_pixel, ks), bound) in dst_pixels.zip(ks_iter).zip(&coeffs.bounds) {
+            let first_x_src = bound.start as usize * components_count;
+            let size = bound.size as usize;
+            let src_pixels = &src_buf[first_x_src..first_x_src + size * components_count];
+
+            dst_pixel.fill(0.);
+            for (&k, src_pixel) in ks[..size]
+                .iter()
+                .zip(src_pixels.chunks_exact(components_count))
+            {
+                for (d, &s) in dst_pixel.iter_mut().zip(src_pixel) {
+                    *d += s * k;
+                }
+            }
+        }
+
+        C::from_f32(&dst_buf, T::components_mut(dst_row));
+    }
+}
+
+pub(crate) fn vert_convolution<T, C>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+) where
+    T: InnerPixel<Component = f16>,
+    C: F16Converter,
+{
+    debug_assert_eq!(coeffs.bounds.len(), dst_view.height() as usize);
+
+    let coefficients_chunks = coeffs.get_chunks();
+    let src_x_initial = offset as usize * T::count_of_components();
+    let mut ks: Vec<f32> = Vec::with_capacity(coeffs.window_size);
+    let mut src_buf = [0f32; CHUNK_SIZE];
+    let mut sums = [0f32; CHUNK_SIZE];
+
+    let dst_rows = dst_view.iter_rows_mut(0);
+    for (coeffs_chunk, dst_row) in coefficients_chunks.into_iter().zip(dst_rows) {
+        ks.clear();
+        ks.extend(coeffs_chunk.values.iter().map(|&k| k as f32));
+
+        let mut x_src = src_x_initial;
+        for dst_chunk in T::components_mut(dst_row).chunks_mut(CHUNK_SIZE) {
+            let len = dst_chunk.len();
+            let sums = &mut sums[..len];
+            let src_buf = &mut src_buf[..len];
+            sums.fill(0.);
+
+            let src_rows = src_view.iter_rows(coeffs_chunk.start);
+            for (&k, src_row) in ks.iter().zip(src_rows) {
+                let src_components = &T::components(src_row)[x_src..x_src + len];
+                C::to_f32(src_components, src_buf);
+                for (s, &c) in sums.iter_mut().zip(src_buf.iter()) {
+                    *s += c * k;
+                }
+            }
+
+            C::from_f32(sums, dst_chunk);
+            x_src += len;
+        }
+    }
+}
@@ -48,11 +48,10 @@ unsafe fn horiz_convolution_rows<const ROWS_COUNT: usize>(
     let mut ba_buf = [0f64; 2];
 
     for (dst_x, coeffs_chunk) in coefficients_chunks.iter().enumerate() {
-        let mut x: usize = coeffs_chunk.start as usize;
         let mut rg_sums = [_mm_set1_pd(0.); ROWS_COUNT];
         let mut ba_sums = [_mm_set1_pd(0.); ROWS_COUNT];
 
-        for &k in coeffs_chunk.values {
+        for (x, &k) in (coeffs_chunk.start as usize..).zip(coeffs_chunk.values) {
             let coeffs_f64x2 = _mm_set1_pd(k);
 
             for r in 0..ROWS_COUNT {
@@ -62,7 +61,6 @@ unsafe fn horiz_convolution_rows<const ROWS_COUNT: usize>(
                 let ba_f64x2 = _mm_cvtps_pd(_mm_movehl_ps(pixel, pixel));
                 ba_sums[r] = _mm_add_pd(ba_sums[r], _mm_mul_pd(ba_f64x2, coeffs_f64x2));
             }
-            x += 1;
         }
 
         for i in 0..ROWS_COUNT {
@@ -0,0 +1,86 @@
+use core::arch::x86_64::*;
+
+use crate::convolution::{Coefficients, CoefficientsChunk};
+use crate::pixels::InnerPixel;
+use crate::{ImageView, ImageViewMut};
+
+pub(crate) fn vert_convolution<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    debug_assert_eq!(coeffs.bounds.len(), dst_view.height() as usize);
+
+    let coefficients_chunks = coeffs.get_chunks();
+    let src_x = offset as usize * T::count_of_components();
+
+    let dst_rows = dst_view.iter_rows_mut(0);
+    for (dst_row, coeffs_chunk) in dst_rows.zip(coefficients_chunks) {
+        unsafe {
+            vert_convolution_into_one_row_f64(src_view, dst_row, src_x, coeffs_chunk);
+        }
+    }
+}
+
+/// Sums of products are calculated in the same order as in the native
+/// implementation and without FMA, so results are equal.
+#[target_feature(enable = "avx2")]
+unsafe fn vert_convolution_into_one_row_f64<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_row: &mut [T],
+    mut src_x: usize,
+    coeffs_chunk: CoefficientsChunk,
+) {
+    let mut dst_f64 = T::components_mut(dst_row);
+
+    let mut dst_chunks = dst_f64.chunks_exact_mut(16);
+    for dst_chunk in &mut dst_chunks {
+        multiply_components_of_rows::<_, 4>(src_view, src_x, coeffs_chunk, dst_chunk);
+        src_x += 16;
+    }
+
+    dst_f64 = dst_chunks.into_remainder();
+    dst_chunks = dst_f64.chunks_exact_mut(4);
+    for dst_chunk in &mut dst_chunks {
+        multiply_components_of_rows::<_, 1>(src_view, src_x, coeffs_chunk, dst_chunk);
+        src_x += 4;
+    }
+
+    dst_f64 = dst_chunks.into_remainder();
+    if !dst_f64.is_empty() {
+        dst_f64.fill(0.);
+        let src_rows = src_view.iter_rows(coeffs_chunk.start);
+        for (&k, src_row) in coeffs_chunk.values.iter().zip(src_rows) {
+            let src_components = T::components(src_row).get_unchecked(src_x..);
+            for (d, &s) in dst_f64.iter_mut().zip(src_components) {
+                *d += s * k;
+            }
+        }
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn multiply_components_of_rows<T: InnerPixel<Component = f64>, const SUMS_COUNT: usize>(
+    src_view: &impl ImageView<Pixel = T>,
+    src_x: usize,
+    coeffs_chunk: CoefficientsChunk,
+    dst_chunk: &mut [f64],
+) {
+    let mut sums = [_mm256_setzero_pd(); SUMS_COUNT];
+    let src_rows = src_view.iter_rows(coeffs_chunk.start);
+    for (&k, src_row) in coeffs_chunk.values.iter().zip(src_rows) {
+        let coeff = _mm256_set1_pd(k);
+        let src_ptr = T::components(src_row).as_ptr().add(src_x);
+        for (i, sum) in sums.iter_mut().enumerate() {
+            let pixels = _mm256_loadu_pd(src_ptr.add(i * 4));
+            *sum = _mm256_add_pd(*sum, _mm256_mul_pd(pixels, coeff));
+        }
+    }
+
+    let dst_ptr = dst_chunk.as_mut_ptr();
+    for (i, &sum) in sums.iter().enumerate() {
+        _mm256_storeu_pd(dst_ptr.add(i * 4), sum);
+    }
+}
@@ -0,0 +1,85 @@
+use super::{Coefficients, Convolution};
+use crate::cpu_extensions::CpuExtensions;
+use crate::pixels::{F64x2, F64x3, F64x4, InnerPixel, F64};
+use crate::{ImageView, ImageViewMut};
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+mod native;
+
+macro_rules! impl_convolution {
+    ($pixel: ty) => {
+        impl Convolution for $pixel {
+            fn horiz_convolution(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                offset: u32,
+                coeffs: &Coefficients,
+                cpu_extensions: CpuExtensions,
+            ) {
+                debug_assert!(src_view.height() - offset >= dst_view.height());
+                let coeffs_ref = coeffs;
+
+                try_process_in_threads_h! {
+                    horiz_convolution(
+                        src_view,
+                        dst_view,
+                        offset,
+                        coeffs_ref,
+                        cpu_extensions,
+                    );
+                }
+            }
+
+            fn vert_convolution(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                offset: u32,
+                coeffs: &Coefficients,
+                cpu_extensions: CpuExtensions,
+            ) {
+                debug_assert!(src_view.width() - offset >= dst_view.width());
+                let coeffs_ref = coeffs;
+
+                try_process_in_threads_v! {
+                    vert_convolution(
+                        src_view,
+                        dst_view,
+                        offset,
+                        coeffs_ref,
+                        cpu_extensions,
+                    );
+                }
+            }
+        }
+    };
+}
+
+impl_convolution!(F64);
+impl_convolution!(F64x2);
+impl_convolution!(F64x3);
+impl_convolution!(F64x4);
+
+fn horiz_convolution<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::horiz_convolution(src_view, dst_view, offset, coeffs);
+}
+
+fn vert_convolution<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+    cpu_extensions: CpuExtensions,
+) {
+    match cpu_extensions {
+        #[cfg(target_arch = "x86_64")]
+        CpuExtensions::Avx2 => avx2::vert_convolution(src_view, dst_view, offset, coeffs),
+        _ => native::vert_convolution(src_view, dst_view, offset, coeffs),
+    }
+}
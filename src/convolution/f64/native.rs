@@ -0,0 +1,55 @@
+use crate::convolution::Coefficients;
+use crate::pixels::InnerPixel;
+use crate::{ImageView, ImageViewMut};
+
+pub(crate) fn horiz_convolution<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let components_count = T::count_of_components();
+    let coefficients_chunks = coeffs.get_chunks();
+    let src_rows = src_view.iter_rows(offset);
+    let dst_rows = dst_view.iter_rows_mut(0);
+    for (dst_row, src_row) in dst_rows.zip(src_rows) {
+        let src_components = T::components(src_row);
+        let dst_pixels = T::components_mut(dst_row).chunks_exact_mut(components_count);
+        for (dst_pixel, coeffs_chunk) in dst_pixels.zip(&coefficients_chunks) {
+            let first_x_src = coeffs_chunk.start as usize * components_count;
+            let src_pixels = src_components[first_x_src..].chunks_exact(components_count);
+            dst_pixel.fill(0.);
+            for (&k, src_pixel) in coeffs_chunk.values.iter().zip(src_pixels) {
+                for (d, &s) in dst_pixel.iter_mut().zip(src_pixel) {
+                    *d += s * k;
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn vert_convolution<T: InnerPixel<Component = f64>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    debug_assert_eq!(coeffs.bounds.len(), dst_view.height() as usize);
+
+    let coefficients_chunks = coeffs.get_chunks();
+    let x_src = offset as usize * T::count_of_components();
+
+    let dst_rows = dst_view.iter_rows_mut(0);
+    for (coeffs_chunk, dst_row) in coefficients_chunks.into_iter().zip(dst_rows) {
+        // Sums are accumulated directly in the destination row.
+        let dst_components = T::components_mut(dst_row);
+        dst_components.fill(0.);
+        let src_rows = src_view.iter_rows(coeffs_chunk.start);
+        for (&k, src_row) in coeffs_chunk.values.iter().zip(src_rows) {
+            let src_components = &T::components(src_row)[x_src..x_src + dst_components.len()];
+            for (d, &s) in dst_components.iter_mut().zip(src_components) {
+                *d += s * k;
+            }
+        }
+    }
+}
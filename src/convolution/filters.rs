@@ -7,13 +7,52 @@ use thiserror::Error;
 // It is used to implement floats in no_std
 use crate::compat::*;
 
-type FilterFn = fn(f64) -> f64;
+/// Filter function that can be shared between threads.
+#[cfg(target_has_atomic = "ptr")]
+pub type SharedFilterFn = Arc<DynFilterFn>;
+
+/// Filter function that may be a closure with captured state.
+pub type DynFilterFn = dyn Fn(f64) -> f64 + Send + Sync;
+
+#[derive(Clone)]
+enum FilterFn {
+    Pointer(fn(f64) -> f64),
+    Static(&'static DynFilterFn),
+    #[cfg(target_has_atomic = "ptr")]
+    Shared(SharedFilterFn),
+}
+
+impl FilterFn {
+    #[inline]
+    fn call(&self, x: f64) -> f64 {
+        match self {
+            Self::Pointer(func) => func(x),
+            Self::Static(func) => func(x),
+            #[cfg(target_has_atomic = "ptr")]
+            Self::Shared(func) => func(x),
+        }
+    }
+
+    /// Returns `true` if both values refer to the same function.
+    fn is_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Pointer(a), Self::Pointer(b)) => *a as usize == *b as usize,
+            (Self::Static(a), Self::Static(b)) => core::ptr::addr_eq(*a, *b),
+            #[cfg(target_has_atomic = "ptr")]
+            (Self::Shared(a), Self::Shared(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
 
 /// Description of custom filter for image convolution.
-#[derive(Clone, Copy)]
+///
+/// Cloning of a filter is cheap, the function of filters created by
+/// [Filter::from_shared_fn] is shared between clones.
+#[derive(Clone)]
 pub struct Filter {
     /// Name of filter
-    name: &'static str,
+    name: Cow<'static, str>,
     /// Filter function
     func: FilterFn,
     /// Minimal "radius" of kernel in pixels
@@ -22,7 +61,7 @@ pub struct Filter {
 
 impl PartialEq for Filter {
     fn eq(&self, other: &Self) -> bool {
-        self.support == other.support && self.name == other.name
+        self.support == other.support && self.name == other.name && self.func.is_same(&other.func)
     }
 }
 
@@ -52,6 +91,51 @@ impl Filter {
     /// * `support` - Minimal "radius" of kernel in pixels
     pub fn new(
         name: &'static str,
+        func: fn(f64) -> f64,
+        support: f64,
+    ) -> Result<Self, CreateFilterError> {
+        Self::with_fn(name.into(), FilterFn::Pointer(func), support)
+    }
+
+    /// Creates a filter with a function that lives until the end of
+    /// the program, e.g. a closure stored in a static variable.
+    ///
+    /// Unlike [Filter::from_shared_fn], this method doesn't require atomics,
+    /// so it is available on all `no_std` targets.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of filter
+    /// * `func` - Filter function, it may be a closure with captured state
+    /// * `support` - Minimal "radius" of kernel in pixels
+    pub fn from_static_fn(
+        name: impl Into<Cow<'static, str>>,
+        func: &'static DynFilterFn,
+        support: f64,
+    ) -> Result<Self, CreateFilterError> {
+        Self::with_fn(name.into(), FilterFn::Static(func), support)
+    }
+
+    /// Creates a filter from a closure built at runtime,
+    /// e.g. from a configuration. The function and the name
+    /// are freed when the last clone of the filter is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of filter
+    /// * `func` - Filter function, it may be a closure with captured state
+    /// * `support` - Minimal "radius" of kernel in pixels
+    #[cfg(target_has_atomic = "ptr")]
+    pub fn from_shared_fn(
+        name: impl Into<Cow<'static, str>>,
+        func: SharedFilterFn,
+        support: f64,
+    ) -> Result<Self, CreateFilterError> {
+        Self::with_fn(name.into(), FilterFn::Shared(func), support)
+    }
+
+    fn with_fn(
+        name: Cow<'static, str>,
         func: FilterFn,
         support: f64,
     ) -> Result<Self, CreateFilterError> {
@@ -67,8 +151,8 @@ impl Filter {
     }
 
     /// Name of filter
-    pub fn name(&self) -> &'static str {
-        self.name
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     /// Minimal "radius" of kernel in pixels
@@ -78,7 +162,7 @@ impl Filter {
 }
 
 /// Type of filter used for image convolution.
-#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Default, Clone, Debug, PartialEq)]
 #[non_exhaustive]
 pub enum FilterType {
     /// Each pixel of source image contributes to one pixel of the
@@ -123,6 +207,35 @@ pub enum FilterType {
     /// Minimal kernel size 6x6 px.
     #[default]
     Lanczos3,
+    /// Lanczos filter with the given number of lobes.
+    /// `Lanczos(3)` is equal to `Lanczos3`.
+    ///
+    /// Minimal kernel size 2Nx2N px.
+    Lanczos(u8),
+    /// Family of bicubic filters with parameters `B` and `C` described by
+    /// Mitchell and Netravali. `Cubic { b: 1. / 3., c: 1. / 3. }` is equal
+    /// to `Mitchell` and `Cubic { b: 0., c: 0.5 }` is equal to `CatmullRom`.
+    ///
+    /// Minimal kernel size 4x4 px.
+    Cubic { b: f64, c: f64 },
+    /// Gaussian filter with the given standard deviation.
+    /// `GaussianWithSigma { sigma: 0.5 }` is equal to `Gaussian`.
+    ///
+    /// Minimal kernel size 12σx12σ px.
+    GaussianWithSigma { sigma: f64 },
+    /// Sinc filter windowed by Kaiser window with the given
+    /// shape parameter `alpha` and the radius `support`.
+    ///
+    /// Minimal kernel size (2 * support)x(2 * support) px.
+    Kaiser { alpha: f64, support: f64 },
+    /// Sinc filter windowed by Blackman window.
+    ///
+    /// Minimal kernel size 6x6 px.
+    Blackman,
+    /// Sinc filter windowed by Welch window.
+    ///
+    /// Minimal kernel size 6x6 px.
+    Welch,
     /// Custom filter function.
     ///
     /// # Examples
@@ -159,21 +272,70 @@ pub enum FilterType {
     Custom(Filter),
 }
 
-/// Returns reference to filter function and value of `filter_support`.
-#[inline]
-pub(crate) fn get_filter_func(filter_type: FilterType) -> (FilterFn, f64) {
+impl FilterType {
+    /// Returns `false` if parameters of the filter are out of range.
+    pub(crate) fn is_valid(&self) -> bool {
+        match *self {
+            Self::Lanczos(lobes) => lobes > 0,
+            Self::Cubic { b, c } => b.is_finite() && c.is_finite(),
+            Self::GaussianWithSigma { sigma } => sigma.is_finite() && sigma > 0.,
+            Self::Kaiser { alpha, support } => {
+                alpha.is_finite() && alpha >= 0. && support.is_finite() && support > 0.
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Returns value of `filter_support`.
+pub(crate) fn get_filter_support(filter_type: &FilterType) -> f64 {
     match filter_type {
-        FilterType::Box => (box_filter, 0.5),
-        FilterType::Bilinear => (bilinear_filter, 1.0),
-        FilterType::Hamming => (hamming_filter, 1.0),
-        FilterType::CatmullRom => (catmul_filter, 2.0),
-        FilterType::Mitchell => (mitchell_filter, 2.0),
-        FilterType::Gaussian => (gaussian_filter, 3.0),
-        FilterType::Lanczos3 => (lanczos_filter, 3.0),
-        FilterType::Custom(custom) => (custom.func, custom.support),
+        FilterType::Box => 0.5,
+        FilterType::Bilinear | FilterType::Hamming => 1.0,
+        FilterType::CatmullRom | FilterType::Mitchell | FilterType::Cubic { .. } => 2.0,
+        FilterType::Gaussian | FilterType::Lanczos3 => 3.0,
+        FilterType::Lanczos(lobes) => *lobes as f64,
+        FilterType::GaussianWithSigma { sigma } => sigma * 6.,
+        FilterType::Kaiser { support, .. } => *support,
+        FilterType::Blackman | FilterType::Welch => 3.0,
+        FilterType::Custom(custom) => custom.support,
     }
 }
 
+/// Returns filter function and value of `filter_support`.
+#[inline]
+pub(crate) fn get_filter_func(filter_type: FilterType) -> (impl Fn(f64) -> f64 + Send + Sync, f64) {
+    let support = get_filter_support(&filter_type);
+    let func = move |x: f64| match &filter_type {
+        FilterType::Box => box_filter(x),
+        FilterType::Bilinear => bilinear_filter(x),
+        FilterType::Hamming => hamming_filter(x),
+        FilterType::CatmullRom => catmul_filter(x),
+        FilterType::Mitchell => mitchell_filter(x),
+        FilterType::Gaussian => gaussian_filter(x),
+        FilterType::Lanczos3 => lanczos_filter(x),
+        FilterType::Lanczos(_) => windowed_sinc(x, support, sinc_filter),
+        FilterType::Cubic { b, c } => cubic_filter(x, *b, *c),
+        FilterType::GaussianWithSigma { sigma } => {
+            if (-support..support).contains(&x) {
+                gaussian(x, *sigma)
+            } else {
+                0.0
+            }
+        }
+        FilterType::Kaiser { alpha, support } => {
+            windowed_sinc(x, *support, |t| bessel_i0(alpha * (1. - t * t).sqrt()))
+                / bessel_i0(*alpha)
+        }
+        FilterType::Blackman => windowed_sinc(x, support, |t| {
+            0.42 + 0.5 * (PI * t).cos() + 0.08 * (2. * PI * t).cos()
+        }),
+        FilterType::Welch => windowed_sinc(x, support, |t| 1. - t * t),
+        FilterType::Custom(custom) => custom.func.call(x),
+    };
+    (func, support)
+}
+
 #[inline]
 fn box_filter(x: f64) -> f64 {
     if x > -0.5 && x <= 0.5 {
@@ -270,3 +432,43 @@ fn lanczos_filter(x: f64) -> f64 {
         0.0
     }
 }
+
+/// Sinc function multiplied by the window function.
+/// The window function receives `x` divided by `support`.
+#[inline]
+fn windowed_sinc(x: f64, support: f64, window: impl Fn(f64) -> f64) -> f64 {
+    if (-support..support).contains(&x) {
+        sinc_filter(x) * window(x / support)
+    } else {
+        0.0
+    }
+}
+
+/// Mitchell–Netravali family of bicubic filters
+/// https://en.wikipedia.org/wiki/Mitchell%E2%80%93Netravali_filters
+#[inline]
+fn cubic_filter(mut x: f64, b: f64, c: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        ((12. - 9. * b - 6. * c) * x + (-18. + 12. * b + 6. * c)) * x * x / 6. + (6. - 2. * b) / 6.
+    } else if x < 2.0 {
+        (((-b - 6. * c) * x + (6. * b + 30. * c)) * x + (-12. * b - 48. * c)) * x / 6.
+            + (8. * b + 24. * c) / 6.
+    } else {
+        0.0
+    }
+}
+
+/// Modified Bessel function of the first kind of order zero.
+fn bessel_i0(x: f64) -> f64 {
+    let quarter_x2 = x * x / 4.;
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut k = 1.0;
+    while term > sum * 1e-16 {
+        term *= quarter_x2 / (k * k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
@@ -0,0 +1,122 @@
+use super::{Coefficients, Convolution};
+use crate::pixels::{I32x2, I32x3, I32x4, InnerPixel, U32x2, U32x3, U32x4, I32, U32};
+use crate::sampler::SampleComponent;
+use crate::{CpuExtensions, ImageView, ImageViewMut};
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+mod native;
+#[cfg(target_arch = "x86_64")]
+mod sse4;
+
+macro_rules! impl_convolution {
+    ($pixel: ty, $horiz_fn: ident, $vert_fn: ident) => {
+        impl Convolution for $pixel {
+            fn horiz_convolution(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                offset: u32,
+                coeffs: &Coefficients,
+                cpu_extensions: CpuExtensions,
+            ) {
+                debug_assert!(src_view.height() - offset >= dst_view.height());
+                let coeffs_ref = coeffs;
+
+                try_process_in_threads_h! {
+                    $horiz_fn(
+                        src_view,
+                        dst_view,
+                        offset,
+                        coeffs_ref,
+                        cpu_extensions,
+                    );
+                }
+            }
+
+            fn vert_convolution(
+                src_view: &impl ImageView<Pixel = Self>,
+                dst_view: &mut impl ImageViewMut<Pixel = Self>,
+                offset: u32,
+                coeffs: &Coefficients,
+                cpu_extensions: CpuExtensions,
+            ) {
+                debug_assert!(src_view.width() - offset >= dst_view.width());
+
+                let coeffs_ref = coeffs;
+
+                try_process_in_threads_v! {
+                    $vert_fn(
+                        src_view,
+                        dst_view,
+                        offset,
+                        coeffs_ref,
+                        cpu_extensions,
+                    );
+                }
+            }
+        }
+    };
+}
+
+impl_convolution!(I32, horiz_convolution_i32, vert_convolution_i32);
+impl_convolution!(I32x2, horiz_convolution, vert_convolution);
+impl_convolution!(I32x3, horiz_convolution, vert_convolution);
+impl_convolution!(I32x4, horiz_convolution, vert_convolution);
+impl_convolution!(U32, horiz_convolution, vert_convolution);
+impl_convolution!(U32x2, horiz_convolution, vert_convolution);
+impl_convolution!(U32x3, horiz_convolution, vert_convolution);
+impl_convolution!(U32x4, horiz_convolution, vert_convolution);
+
+fn horiz_convolution_i32(
+    src_view: &impl ImageView<Pixel = I32>,
+    dst_view: &mut impl ImageViewMut<Pixel = I32>,
+    offset: u32,
+    coefficients: &Coefficients,
+    cpu_extensions: CpuExtensions,
+) {
+    match cpu_extensions {
+        #[cfg(target_arch = "x86_64")]
+        CpuExtensions::Avx2 => avx2::horiz_convolution(src_view, dst_view, offset, coefficients),
+        #[cfg(target_arch = "x86_64")]
+        CpuExtensions::Sse4_1 => sse4::horiz_convolution(src_view, dst_view, offset, coefficients),
+        _ => native::horiz_convolution(src_view, dst_view, offset, coefficients),
+    }
+}
+
+fn vert_convolution_i32(
+    src_view: &impl ImageView<Pixel = I32>,
+    dst_view: &mut impl ImageViewMut<Pixel = I32>,
+    offset: u32,
+    coefficients: &Coefficients,
+    cpu_extensions: CpuExtensions,
+) {
+    match cpu_extensions {
+        #[cfg(target_arch = "x86_64")]
+        CpuExtensions::Avx2 => avx2::vert_convolution(src_view, dst_view, offset, coefficients),
+        #[cfg(target_arch = "x86_64")]
+        CpuExtensions::Sse4_1 => sse4::vert_convolution(src_view, dst_view, offset, coefficients),
+        _ => native::vert_convolution(src_view, dst_view, offset, coefficients),
+    }
+}
+
+#[inline(always)]
+fn horiz_convolution<T: InnerPixel<Component: SampleComponent>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coefficients: &Coefficients,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::horiz_convolution(src_view, dst_view, offset, coefficients);
+}
+
+#[inline(always)]
+fn vert_convolution<T: InnerPixel<Component: SampleComponent>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coefficients: &Coefficients,
+    _cpu_extensions: CpuExtensions,
+) {
+    native::vert_convolution(src_view, dst_view, offset, coefficients);
+}
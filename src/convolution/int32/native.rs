@@ -0,0 +1,78 @@
+#[allow(unused_imports)]
+// It is used to implement floats in no_std
+use crate::compat::*;
+use crate::convolution::{Coefficients, CoefficientsChunk};
+use crate::pixels::InnerPixel;
+use crate::sampler::SampleComponent;
+use crate::{ImageView, ImageViewMut};
+
+pub(crate) fn horiz_convolution<T: InnerPixel<Component: SampleComponent>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let coefficients_chunks = coeffs.get_chunks();
+    let src_rows = src_view.iter_rows(offset);
+    let dst_rows = dst_view.iter_rows_mut(0);
+    for (dst_row, src_row) in dst_rows.zip(src_rows) {
+        horiz_convolution_one_row(src_row, dst_row, &coefficients_chunks);
+    }
+}
+
+#[inline(always)]
+pub(crate) fn horiz_convolution_one_row<T: InnerPixel<Component: SampleComponent>>(
+    src_row: &[T],
+    dst_row: &mut [T],
+    coefficients_chunks: &[CoefficientsChunk],
+) {
+    let components_count = T::count_of_components();
+    let src_components = T::components(src_row);
+    let dst_pixels = T::components_mut(dst_row).chunks_exact_mut(components_count);
+    for (dst_pixel, coeffs_chunk) in dst_pixels.zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize * components_count;
+        let src_pixels = unsafe { src_components.get_unchecked(first_x_src..) };
+        for (i, dst_component) in dst_pixel.iter_mut().enumerate() {
+            let mut ss = 0.;
+            let src_pixels = src_pixels.chunks_exact(components_count);
+            for (&k, src_pixel) in coeffs_chunk.values.iter().zip(src_pixels) {
+                ss += src_pixel[i].to_f64() * k;
+            }
+            *dst_component = SampleComponent::from_f64(ss);
+        }
+    }
+}
+
+pub(crate) fn vert_convolution<T: InnerPixel<Component: SampleComponent>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let coefficients_chunks = coeffs.get_chunks();
+    let dst_rows = dst_view.iter_rows_mut(0);
+    let start_src_x = offset as usize * T::count_of_components();
+    for (&coeffs_chunk, dst_row) in coefficients_chunks.iter().zip(dst_rows) {
+        let dst_components = T::components_mut(dst_row);
+        vert_convolution_into_one_row(src_view, dst_components, start_src_x, coeffs_chunk);
+    }
+}
+
+#[inline(always)]
+pub(crate) fn vert_convolution_into_one_row<T: InnerPixel<Component: SampleComponent>>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_components: &mut [T::Component],
+    start_src_x: usize,
+    coeffs_chunk: CoefficientsChunk,
+) {
+    let first_y_src = coeffs_chunk.start;
+    for (src_x, dst_component) in (start_src_x..).zip(dst_components.iter_mut()) {
+        let mut ss = 0.;
+        let src_rows = src_view.iter_rows(first_y_src);
+        for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+            let src_component = unsafe { T::components(src_row).get_unchecked(src_x) };
+            ss += src_component.to_f64() * k;
+        }
+        *dst_component = SampleComponent::from_f64(ss);
+    }
+}
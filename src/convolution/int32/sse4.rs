@@ -0,0 +1,208 @@
+use core::arch::x86_64::*;
+
+use super::native;
+use crate::convolution::{Coefficients, CoefficientsChunk};
+use crate::pixels::{InnerPixel, I32};
+use crate::{simd_utils, ImageView, ImageViewMut};
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_view: &impl ImageView<Pixel = I32>,
+    dst_view: &mut impl ImageViewMut<Pixel = I32>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let coefficients_chunks = coeffs.get_chunks();
+    let dst_height = dst_view.height();
+
+    let src_iter = src_view.iter_4_rows(offset, dst_height + offset);
+    let dst_iter = dst_view.iter_4_rows_mut();
+    for (src_rows, dst_rows) in src_iter.zip(dst_iter) {
+        unsafe {
+            horiz_convolution_four_rows(src_rows, dst_rows, &coefficients_chunks);
+        }
+    }
+
+    let yy = dst_height - dst_height % 4;
+    let src_rows = src_view.iter_rows(yy + offset);
+    let dst_rows = dst_view.iter_rows_mut(yy);
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        native::horiz_convolution_one_row(src_row, dst_row, &coefficients_chunks);
+    }
+}
+
+/// Every lane of the sums belongs to its own row, so sums are
+/// calculated in the same order as in the native implementation.
+///
+/// For safety, it is necessary to ensure the following conditions:
+/// - length of all rows in src_rows must be equal
+/// - length of all rows in dst_rows must be equal
+/// - coefficients_chunks.len() == dst_rows.0.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.0.len()
+#[target_feature(enable = "sse4.1")]
+unsafe fn horiz_convolution_four_rows(
+    src_rows: [&[I32]; 4],
+    dst_rows: [&mut [I32]; 4],
+    coefficients_chunks: &[CoefficientsChunk],
+) {
+    for (dst_x, chunk) in coefficients_chunks.iter().enumerate() {
+        let mut x = chunk.start as usize;
+        // Sums of rows 0, 1 and rows 2, 3
+        let mut sums = [_mm_setzero_pd(); 2];
+
+        let coeffs_by_4 = chunk.values.chunks_exact(4);
+        let coeffs = coeffs_by_4.remainder();
+        for k in coeffs_by_4 {
+            let columns = load_columns(src_rows, x);
+            for (&coeff, column) in k.iter().zip(columns) {
+                mul_add(&mut sums, column, _mm_set1_pd(coeff));
+            }
+            x += 4;
+        }
+
+        for &coeff in coeffs {
+            let column = _mm_set_epi32(
+                src_rows[3].get_unchecked(x).0,
+                src_rows[2].get_unchecked(x).0,
+                src_rows[1].get_unchecked(x).0,
+                src_rows[0].get_unchecked(x).0,
+            );
+            mul_add(&mut sums, column, _mm_set1_pd(coeff));
+            x += 1;
+        }
+
+        let res = _mm_unpacklo_epi64(round_to_i32(sums[0]), round_to_i32(sums[1]));
+        dst_rows[0].get_unchecked_mut(dst_x).0 = _mm_extract_epi32::<0>(res);
+        dst_rows[1].get_unchecked_mut(dst_x).0 = _mm_extract_epi32::<1>(res);
+        dst_rows[2].get_unchecked_mut(dst_x).0 = _mm_extract_epi32::<2>(res);
+        dst_rows[3].get_unchecked_mut(dst_x).0 = _mm_extract_epi32::<3>(res);
+    }
+}
+
+/// Loads four pixels from every row and returns columns of them.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn load_columns(src_rows: [&[I32]; 4], x: usize) -> [__m128i; 4] {
+    let r0 = simd_utils::loadu_si128(src_rows[0], x);
+    let r1 = simd_utils::loadu_si128(src_rows[1], x);
+    let r2 = simd_utils::loadu_si128(src_rows[2], x);
+    let r3 = simd_utils::loadu_si128(src_rows[3], x);
+
+    let r01_lo = _mm_unpacklo_epi32(r0, r1); // r0[0] r1[0] r0[1] r1[1]
+    let r23_lo = _mm_unpacklo_epi32(r2, r3); // r2[0] r3[0] r2[1] r3[1]
+    let r01_hi = _mm_unpackhi_epi32(r0, r1); // r0[2] r1[2] r0[3] r1[3]
+    let r23_hi = _mm_unpackhi_epi32(r2, r3); // r2[2] r3[2] r2[3] r3[3]
+    [
+        _mm_unpacklo_epi64(r01_lo, r23_lo),
+        _mm_unpackhi_epi64(r01_lo, r23_lo),
+        _mm_unpacklo_epi64(r01_hi, r23_hi),
+        _mm_unpackhi_epi64(r01_hi, r23_hi),
+    ]
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_view: &impl ImageView<Pixel = I32>,
+    dst_view: &mut impl ImageViewMut<Pixel = I32>,
+    offset: u32,
+    coeffs: &Coefficients,
+) {
+    let coefficients_chunks = coeffs.get_chunks();
+    let src_x = offset as usize;
+
+    let dst_rows = dst_view.iter_rows_mut(0);
+    for (dst_row, &coeffs_chunk) in dst_rows.zip(&coefficients_chunks) {
+        unsafe {
+            vert_convolution_into_one_row(src_view, dst_row, src_x, coeffs_chunk);
+        }
+    }
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn vert_convolution_into_one_row(
+    src_view: &impl ImageView<Pixel = I32>,
+    dst_row: &mut [I32],
+    mut src_x: usize,
+    coeffs_chunk: CoefficientsChunk,
+) {
+    let mut dst_i32 = I32::components_mut(dst_row);
+
+    let mut dst_chunks = dst_i32.chunks_exact_mut(8);
+    for dst_chunk in &mut dst_chunks {
+        multiply_components_of_rows::<2>(src_view, src_x, coeffs_chunk, dst_chunk);
+        src_x += 8;
+    }
+
+    dst_i32 = dst_chunks.into_remainder();
+    dst_chunks = dst_i32.chunks_exact_mut(4);
+    for dst_chunk in &mut dst_chunks {
+        multiply_components_of_rows::<1>(src_view, src_x, coeffs_chunk, dst_chunk);
+        src_x += 4;
+    }
+
+    dst_i32 = dst_chunks.into_remainder();
+    if !dst_i32.is_empty() {
+        native::vert_convolution_into_one_row(src_view, dst_i32, src_x, coeffs_chunk);
+    }
+}
+
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn multiply_components_of_rows<const SUMS_COUNT: usize>(
+    src_view: &impl ImageView<Pixel = I32>,
+    src_x: usize,
+    coeffs_chunk: CoefficientsChunk,
+    dst_chunk: &mut [i32],
+) {
+    // Every item contains sums of four components
+    let mut sums = [[_mm_setzero_pd(); 2]; SUMS_COUNT];
+
+    let src_rows = src_view.iter_rows(coeffs_chunk.start);
+    for (&k, src_row) in coeffs_chunk.values.iter().zip(src_rows) {
+        let coeff = _mm_set1_pd(k);
+        for (i, sum) in sums.iter_mut().enumerate() {
+            let source = simd_utils::loadu_si128(src_row, src_x + i * 4);
+            mul_add(sum, source, coeff);
+        }
+    }
+
+    let mut dst_ptr = dst_chunk.as_mut_ptr() as *mut __m128i;
+    for sum in sums {
+        let res = _mm_unpacklo_epi64(round_to_i32(sum[0]), round_to_i32(sum[1]));
+        _mm_storeu_si128(dst_ptr, res);
+        dst_ptr = dst_ptr.add(1);
+    }
+}
+
+/// Converts four `i32` values into `f64`, multiplies them by the coefficient
+/// and adds the products to the sums.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn mul_add(sums: &mut [__m128d; 2], values: __m128i, coeff: __m128d) {
+    let lo = _mm_cvtepi32_pd(values);
+    let hi = _mm_cvtepi32_pd(_mm_unpackhi_epi64(values, values));
+    sums[0] = _mm_add_pd(sums[0], _mm_mul_pd(lo, coeff));
+    sums[1] = _mm_add_pd(sums[1], _mm_mul_pd(hi, coeff));
+}
+
+/// Rounds values half away from zero and clamps them to the range of `i32`,
+/// exactly like the native implementation does.
+/// Results are stored into the lower half of the returned register.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn round_to_i32(values: __m128d) -> __m128i {
+    let sign_mask = _mm_set1_pd(-0.);
+    let truncated = _mm_round_pd::<{ _MM_FROUND_TO_ZERO | _MM_FROUND_NO_EXC }>(values);
+    // Fractional parts of values are calculated without errors.
+    let abs_fract = _mm_andnot_pd(sign_mask, _mm_sub_pd(values, truncated));
+    let away_from_zero = _mm_cmpge_pd(abs_fract, _mm_set1_pd(0.5));
+    let one = _mm_or_pd(_mm_and_pd(values, sign_mask), _mm_set1_pd(1.));
+    let rounded = _mm_add_pd(truncated, _mm_and_pd(away_from_zero, one));
+    let clamped = _mm_min_pd(
+        _mm_max_pd(rounded, _mm_set1_pd(i32::MIN as f64)),
+        _mm_set1_pd(i32::MAX as f64),
+    );
+    // NaN is converted into zero like the `as` operator does.
+    let not_nan = _mm_cmpord_pd(values, values);
+    _mm_cvtpd_epi32(_mm_and_pd(clamped, not_nan))
+}
@@ -0,0 +1,89 @@
+use core::arch::x86_64::*;
+
+use super::native;
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn add_row_u8(sums: &mut [u32], src_row: &[u8]) {
+    let len = sums.len().min(src_row.len());
+    let mut i = 0;
+    while i + 16 <= len {
+        let src = _mm_loadu_si128(src_row.as_ptr().add(i) as *const __m128i);
+        let sum_ptr = sums.as_mut_ptr().add(i) as *mut __m256i;
+        let components = _mm256_cvtepu8_epi32(src);
+        _mm256_storeu_si256(
+            sum_ptr,
+            _mm256_add_epi32(_mm256_loadu_si256(sum_ptr), components),
+        );
+        let sum_ptr = sum_ptr.add(1);
+        let components = _mm256_cvtepu8_epi32(_mm_srli_si128::<8>(src));
+        _mm256_storeu_si256(
+            sum_ptr,
+            _mm256_add_epi32(_mm256_loadu_si256(sum_ptr), components),
+        );
+        i += 16;
+    }
+    native::add_row(&mut sums[i..len], &src_row[i..len]);
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn add_row_u16(sums: &mut [u64], src_row: &[u16]) {
+    let len = sums.len().min(src_row.len());
+    let mut i = 0;
+    while i + 8 <= len {
+        let src = _mm_loadu_si128(src_row.as_ptr().add(i) as *const __m128i);
+        let sum_ptr = sums.as_mut_ptr().add(i) as *mut __m256i;
+        let components = _mm256_cvtepu16_epi64(src);
+        _mm256_storeu_si256(
+            sum_ptr,
+            _mm256_add_epi64(_mm256_loadu_si256(sum_ptr), components),
+        );
+        let sum_ptr = sum_ptr.add(1);
+        let components = _mm256_cvtepu16_epi64(_mm_srli_si128::<8>(src));
+        _mm256_storeu_si256(
+            sum_ptr,
+            _mm256_add_epi64(_mm256_loadu_si256(sum_ptr), components),
+        );
+        i += 8;
+    }
+    native::add_row(&mut sums[i..len], &src_row[i..len]);
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn add_row_i32(sums: &mut [i64], src_row: &[i32]) {
+    let len = sums.len().min(src_row.len());
+    let mut i = 0;
+    while i + 8 <= len {
+        let src = _mm256_loadu_si256(src_row.as_ptr().add(i) as *const __m256i);
+        let sum_ptr = sums.as_mut_ptr().add(i) as *mut __m256i;
+        let components = _mm256_cvtepi32_epi64(_mm256_castsi256_si128(src));
+        _mm256_storeu_si256(
+            sum_ptr,
+            _mm256_add_epi64(_mm256_loadu_si256(sum_ptr), components),
+        );
+        let sum_ptr = sum_ptr.add(1);
+        let components = _mm256_cvtepi32_epi64(_mm256_extracti128_si256::<1>(src));
+        _mm256_storeu_si256(
+            sum_ptr,
+            _mm256_add_epi64(_mm256_loadu_si256(sum_ptr), components),
+        );
+        i += 8;
+    }
+    native::add_row(&mut sums[i..len], &src_row[i..len]);
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn add_row_f32(sums: &mut [f64], src_row: &[f32]) {
+    let len = sums.len().min(src_row.len());
+    let mut i = 0;
+    while i + 8 <= len {
+        let src = _mm256_loadu_ps(src_row.as_ptr().add(i));
+        let sum_ptr = sums.as_mut_ptr().add(i);
+        let components = _mm256_cvtps_pd(_mm256_castps256_ps128(src));
+        _mm256_storeu_pd(sum_ptr, _mm256_add_pd(_mm256_loadu_pd(sum_ptr), components));
+        let sum_ptr = sum_ptr.add(4);
+        let components = _mm256_cvtps_pd(_mm256_extractf128_ps::<1>(src));
+        _mm256_storeu_pd(sum_ptr, _mm256_add_pd(_mm256_loadu_pd(sum_ptr), components));
+        i += 8;
+    }
+    native::add_row(&mut sums[i..len], &src_row[i..len]);
+}
@@ -0,0 +1,469 @@
+use core::ops::AddAssign;
+
+use crate::compat::*;
+use crate::images::TypedCroppedImageMut;
+use crate::pixels::{f16, InnerPixel};
+use crate::{CpuExtensions, ImageView, ImageViewMut};
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+mod native;
+#[cfg(target_arch = "x86_64")]
+mod sse4;
+
+/// Maximal number of source pixels in a block averaged by the integer box filter.
+/// Sums of components of a block must not overflow types of these sums.
+pub(crate) const MAX_BLOCK_SIZE: u32 = 1 << 24;
+
+/// Number of sums of components calculated at once by the generic
+/// implementation. Sums are kept in the CPU cache.
+const CHUNK_LEN: usize = 2048;
+
+/// Divider of sums of components by the number of pixels in a block.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BlockDivider {
+    count: u32,
+    recip: f64,
+    /// `ceil(2^48 / count)`
+    mul: u64,
+}
+
+impl BlockDivider {
+    fn new(count: u32) -> Self {
+        Self {
+            count,
+            recip: 1. / count as f64,
+            mul: (1u64 << 48).div_ceil(count as u64),
+        }
+    }
+
+    /// Returns `floor(n / count + 0.5)` for an integer `0 <= n < 2^16 * count`.
+    ///
+    /// Multiplication by `ceil(2^48 / count)` with the following shift
+    /// gives the exact result of integer division if `n * count < 2^48`.
+    #[inline(always)]
+    fn round_div_u64(&self, n: u64) -> u64 {
+        let n = n + (self.count / 2) as u64;
+        if self.count <= 1 << 16 {
+            (n * self.mul) >> 48
+        } else {
+            n / self.count as u64
+        }
+    }
+
+    /// Returns `floor(n / count + 0.5)` for an integer `n`.
+    ///
+    /// `n + count / 2` is divided by multiplying by the reciprocal
+    /// of `count` instead of slow integer division. The result is exact
+    /// because `(n + count / 2 + 0.5) / count` differs from integers
+    /// by at least `0.5 / count`.
+    #[inline(always)]
+    fn round_div_f64(&self, n: f64) -> f64 {
+        ((n + (self.count / 2) as f64 + 0.5) * self.recip).floor()
+    }
+}
+
+/// Component of a pixel that can be averaged by the integer box filter.
+pub(crate) trait BoxComponent: Copy + Send + Sync + 'static {
+    /// Type of sums of components of a block of source pixels.
+    type Sum: Copy + Default + Send + Sync + AddAssign;
+
+    fn to_sum(self) -> Self::Sum;
+
+    /// Adds components of the source row to the sums.
+    fn add_row(sums: &mut [Self::Sum], src_row: &[Self], cpu_extensions: CpuExtensions);
+
+    /// Returns the average value of components of a block of source pixels.
+    fn average(sum: Self::Sum, divider: &BlockDivider) -> Self;
+
+    /// Averages blocks of source pixels into the beginning of
+    /// the destination row with help of SIMD instructions.
+    /// Returns the number of calculated components.
+    fn average_rows_simd(
+        _src_rows: &[&[Self]],
+        _dst_row: &mut [Self],
+        _components_count: usize,
+        _factor_x: usize,
+        _divider: &BlockDivider,
+        _cpu_extensions: CpuExtensions,
+    ) -> usize {
+        0
+    }
+}
+
+impl BoxComponent for u8 {
+    type Sum = u32;
+
+    #[inline(always)]
+    fn to_sum(self) -> u32 {
+        self as u32
+    }
+
+    fn add_row(sums: &mut [u32], src_row: &[u8], cpu_extensions: CpuExtensions) {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => unsafe { avx2::add_row_u8(sums, src_row) },
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => unsafe { sse4::add_row_u8(sums, src_row) },
+            _ => native::add_row(sums, src_row),
+        }
+    }
+
+    #[inline(always)]
+    fn average(sum: u32, divider: &BlockDivider) -> Self {
+        divider.round_div_u64(sum as u64) as u8
+    }
+
+    fn average_rows_simd(
+        src_rows: &[&[u8]],
+        dst_row: &mut [u8],
+        components_count: usize,
+        factor_x: usize,
+        divider: &BlockDivider,
+        cpu_extensions: CpuExtensions,
+    ) -> usize {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 | CpuExtensions::Sse4_1 => {
+                sse4::average_rows_u8(src_rows, dst_row, components_count, factor_x, divider)
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl BoxComponent for u16 {
+    type Sum = u64;
+
+    #[inline(always)]
+    fn to_sum(self) -> u64 {
+        self as u64
+    }
+
+    fn add_row(sums: &mut [u64], src_row: &[u16], cpu_extensions: CpuExtensions) {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => unsafe { avx2::add_row_u16(sums, src_row) },
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => unsafe { sse4::add_row_u16(sums, src_row) },
+            _ => native::add_row(sums, src_row),
+        }
+    }
+
+    #[inline(always)]
+    fn average(sum: u64, divider: &BlockDivider) -> Self {
+        divider.round_div_u64(sum) as u16
+    }
+}
+
+impl BoxComponent for i32 {
+    type Sum = i64;
+
+    #[inline(always)]
+    fn to_sum(self) -> i64 {
+        self as i64
+    }
+
+    fn add_row(sums: &mut [i64], src_row: &[i32], cpu_extensions: CpuExtensions) {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => unsafe { avx2::add_row_i32(sums, src_row) },
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => unsafe { sse4::add_row_i32(sums, src_row) },
+            _ => native::add_row(sums, src_row),
+        }
+    }
+
+    #[inline(always)]
+    fn average(sum: i64, divider: &BlockDivider) -> Self {
+        divider.round_div_f64(sum as f64) as i32
+    }
+}
+
+impl BoxComponent for u32 {
+    type Sum = u64;
+
+    #[inline(always)]
+    fn to_sum(self) -> u64 {
+        self as u64
+    }
+
+    fn add_row(sums: &mut [u64], src_row: &[u32], _cpu_extensions: CpuExtensions) {
+        native::add_row(sums, src_row)
+    }
+
+    #[inline(always)]
+    fn average(sum: u64, divider: &BlockDivider) -> Self {
+        divider.round_div_f64(sum as f64) as u32
+    }
+}
+
+impl BoxComponent for f32 {
+    type Sum = f64;
+
+    #[inline(always)]
+    fn to_sum(self) -> f64 {
+        self as f64
+    }
+
+    fn add_row(sums: &mut [f64], src_row: &[f32], cpu_extensions: CpuExtensions) {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => unsafe { avx2::add_row_f32(sums, src_row) },
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => unsafe { sse4::add_row_f32(sums, src_row) },
+            _ => native::add_row(sums, src_row),
+        }
+    }
+
+    #[inline(always)]
+    fn average(sum: f64, divider: &BlockDivider) -> Self {
+        (sum / divider.count as f64) as f32
+    }
+}
+
+impl BoxComponent for f64 {
+    type Sum = f64;
+
+    #[inline(always)]
+    fn to_sum(self) -> f64 {
+        self
+    }
+
+    fn add_row(sums: &mut [f64], src_row: &[f64], _cpu_extensions: CpuExtensions) {
+        native::add_row(sums, src_row)
+    }
+
+    #[inline(always)]
+    fn average(sum: f64, divider: &BlockDivider) -> Self {
+        sum / divider.count as f64
+    }
+}
+
+impl BoxComponent for f16 {
+    type Sum = f64;
+
+    #[inline(always)]
+    fn to_sum(self) -> f64 {
+        self.to_f64()
+    }
+
+    fn add_row(sums: &mut [f64], src_row: &[f16], _cpu_extensions: CpuExtensions) {
+        native::add_row(sums, src_row)
+    }
+
+    #[inline(always)]
+    fn average(sum: f64, divider: &BlockDivider) -> Self {
+        f16::from_f64(sum / divider.count as f64)
+    }
+}
+
+/// Averages blocks of `factor_x`x`factor_y` source pixels, starting from
+/// the pixel (`left`, `top`), into pixels of the destination image.
+fn box_average<'a, T>(
+    src_view: &'a impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    (left, top): (u32, u32),
+    (factor_x, factor_y): (u32, u32),
+    cpu_extensions: CpuExtensions,
+) where
+    T: InnerPixel<Component: BoxComponent>,
+{
+    debug_assert!(src_view.width() - left >= dst_view.width() * factor_x);
+    debug_assert!(src_view.height() - top >= dst_view.height() * factor_y);
+
+    let components_count = T::count_of_components();
+    let first_component = left as usize * components_count;
+    let src_len = (dst_view.width() * factor_x) as usize * components_count;
+    let divider = BlockDivider::new(factor_x * factor_y);
+
+    let average_row = |y: u32, dst_row: &mut [T], buffers: &mut RowBuffers<'a, T::Component>| {
+        buffers.src_rows.clear();
+        let src_rows = src_view
+            .iter_rows(top + y * factor_y)
+            .take(factor_y as usize)
+            .map(|row| &T::components(row)[first_component..first_component + src_len]);
+        buffers.src_rows.extend(src_rows);
+        average_rows(
+            &buffers.src_rows,
+            T::components_mut(dst_row),
+            components_count,
+            factor_x as usize,
+            &divider,
+            &mut buffers.sums,
+            cpu_extensions,
+        );
+    };
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        let mut dst_rows: Vec<(u32, &mut [T])> = (0..).zip(dst_view.iter_rows_mut(0)).collect();
+        dst_rows
+            .par_iter_mut()
+            .for_each_init(RowBuffers::default, |buffers, (y, dst_row)| {
+                average_row(*y, dst_row, buffers)
+            });
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        let mut buffers = RowBuffers::default();
+        for (y, dst_row) in (0..).zip(dst_view.iter_rows_mut(0)) {
+            average_row(y, dst_row, &mut buffers);
+        }
+    }
+}
+
+/// Reduces the region of `width`x`height` source pixels, starting from
+/// the pixel (`left`, `top`), by integer factors like `Image.reduce()`
+/// in Pillow does.
+///
+/// Every destination pixel is the average of the block of `factor_x`x`factor_y`
+/// source pixels. If sizes of the region are not multiples of factors,
+/// blocks at the right and bottom edges of the region are smaller and
+/// their pixels are averaged over the pixels inside the region only.
+/// Sizes of the destination image must be equal to
+/// `ceil(width / factor_x)`x`ceil(height / factor_y)`.
+pub(crate) fn box_reduce<T>(
+    src_view: &impl ImageView<Pixel = T>,
+    dst_view: &mut impl ImageViewMut<Pixel = T>,
+    (left, top): (u32, u32),
+    (width, height): (u32, u32),
+    (factor_x, factor_y): (u32, u32),
+    cpu_extensions: CpuExtensions,
+) where
+    T: InnerPixel<Component: BoxComponent>,
+{
+    debug_assert_eq!(dst_view.width(), width.div_ceil(factor_x));
+    debug_assert_eq!(dst_view.height(), height.div_ceil(factor_y));
+
+    let (full_width, full_height) = (width / factor_x, height / factor_y);
+    let (rest_x, rest_y) = (width % factor_x, height % factor_y);
+    let mut average_part = |(dst_left, dst_top), (dst_width, dst_height), factors| {
+        if dst_width == 0 || dst_height == 0 {
+            return;
+        }
+        let mut dst_part =
+            TypedCroppedImageMut::from_ref(dst_view, dst_left, dst_top, dst_width, dst_height)
+                .unwrap();
+        let src_position = (left + dst_left * factor_x, top + dst_top * factor_y);
+        box_average(
+            src_view,
+            &mut dst_part,
+            src_position,
+            factors,
+            cpu_extensions,
+        );
+    };
+
+    average_part((0, 0), (full_width, full_height), (factor_x, factor_y));
+    if rest_x > 0 {
+        average_part((full_width, 0), (1, full_height), (rest_x, factor_y));
+    }
+    if rest_y > 0 {
+        average_part((0, full_height), (full_width, 1), (factor_x, rest_y));
+    }
+    if rest_x > 0 && rest_y > 0 {
+        average_part((full_width, full_height), (1, 1), (rest_x, rest_y));
+    }
+}
+
+struct RowBuffers<'a, C: BoxComponent> {
+    src_rows: Vec<&'a [C]>,
+    sums: Vec<C::Sum>,
+}
+
+impl<C: BoxComponent> Default for RowBuffers<'_, C> {
+    fn default() -> Self {
+        Self {
+            src_rows: Vec::new(),
+            sums: Vec::new(),
+        }
+    }
+}
+
+/// Averages blocks of pixels from the source rows into the destination row.
+fn average_rows<C: BoxComponent>(
+    src_rows: &[&[C]],
+    dst_row: &mut [C],
+    components_count: usize,
+    factor_x: usize,
+    divider: &BlockDivider,
+    sums: &mut Vec<C::Sum>,
+    cpu_extensions: CpuExtensions,
+) {
+    let processed = C::average_rows_simd(
+        src_rows,
+        dst_row,
+        components_count,
+        factor_x,
+        divider,
+        cpu_extensions,
+    );
+    let dst_row = &mut dst_row[processed..];
+    if dst_row.is_empty() {
+        return;
+    }
+    let src_offset = processed * factor_x;
+
+    // Implementations for the most used factors with sizes of blocks
+    // known at compile time.
+    macro_rules! average_rows_for {
+        ($($cc:literal, $factor:literal);*) => {
+            match (components_count, factor_x, src_rows.len()) {
+                $(
+                    ($cc, $factor, $factor) => {
+                        let src_rows: [&[C]; $factor] =
+                            core::array::from_fn(|i| &src_rows[i][src_offset..]);
+                        native::average_rows::<C, $cc, $factor, $factor>(
+                            src_rows, dst_row, divider,
+                        );
+                        return;
+                    }
+                )*
+                _ => {}
+            }
+        };
+    }
+    average_rows_for!(
+        1, 2; 2, 2; 3, 2; 4, 2;
+        1, 3; 2, 3; 3, 3; 4, 3;
+        1, 4; 2, 4; 3, 4; 4, 4
+    );
+
+    // Rows are processed by chunks to keep sums of columns in the CPU cache.
+    let block_len = factor_x * components_count;
+    let blocks_in_chunk = (CHUNK_LEN / block_len).max(1);
+    let src_chunk_len = blocks_in_chunk * block_len;
+    let dst_chunk_len = blocks_in_chunk * components_count;
+    for (i, dst_chunk) in dst_row.chunks_mut(dst_chunk_len).enumerate() {
+        let chunk_start = src_offset + i * src_chunk_len;
+        let chunk_len = dst_chunk.len() / components_count * block_len;
+
+        // Sums of components in columns of blocks
+        sums.clear();
+        sums.resize(chunk_len, Default::default());
+        for src_row in src_rows {
+            C::add_row(
+                sums,
+                &src_row[chunk_start..chunk_start + chunk_len],
+                cpu_extensions,
+            );
+        }
+
+        let dst_pixels = dst_chunk.chunks_exact_mut(components_count);
+        for (dst_pixel, block) in dst_pixels.zip(sums.chunks_exact(block_len)) {
+            let mut pixel_sums = [C::Sum::default(); 4];
+            let pixel_sums = &mut pixel_sums[..components_count];
+            for column_sums in block.chunks_exact(components_count) {
+                for (sum, &column_sum) in pixel_sums.iter_mut().zip(column_sums) {
+                    *sum += column_sum;
+                }
+            }
+            for (dst_component, &sum) in dst_pixel.iter_mut().zip(pixel_sums.iter()) {
+                *dst_component = C::average(sum, divider);
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use core::ops::AddAssign;
+
+use super::{BlockDivider, BoxComponent};
+
+#[inline(always)]
+pub(crate) fn add_row<C, S>(sums: &mut [S], src_row: &[C])
+where
+    C: Copy + Into<S>,
+    S: AddAssign,
+{
+    for (sum, &component) in sums.iter_mut().zip(src_row) {
+        *sum += component.into();
+    }
+}
+
+/// Averages blocks of pixels with sizes known at compile time.
+#[inline]
+pub(crate) fn average_rows<C, const CC: usize, const FX: usize, const FY: usize>(
+    src_rows: [&[C]; FY],
+    dst_row: &mut [C],
+    divider: &BlockDivider,
+) where
+    C: BoxComponent,
+{
+    for (x, dst_pixel) in dst_row.chunks_exact_mut(CC).enumerate() {
+        let block_start = x * FX * CC;
+        let mut sums = [C::Sum::default(); CC];
+        for src_row in src_rows {
+            let block = &src_row[block_start..block_start + FX * CC];
+            for src_pixel in block.chunks_exact(CC) {
+                for (sum, &component) in sums.iter_mut().zip(src_pixel) {
+                    *sum += component.to_sum();
+                }
+            }
+        }
+        for (dst_component, sum) in dst_pixel.iter_mut().zip(sums) {
+            *dst_component = C::average(sum, divider);
+        }
+    }
+}
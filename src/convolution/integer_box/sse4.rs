@@ -0,0 +1,172 @@
+use core::arch::x86_64::*;
+
+use super::{native, BlockDivider};
+
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn add_row_u8(sums: &mut [u32], src_row: &[u8]) {
+    let len = sums.len().min(src_row.len());
+    let mut i = 0;
+    while i + 16 <= len {
+        let src = _mm_loadu_si128(src_row.as_ptr().add(i) as *const __m128i);
+        for j in 0..4 {
+            let sum_ptr = sums.as_mut_ptr().add(i + j * 4) as *mut __m128i;
+            let components = match j {
+                0 => _mm_cvtepu8_epi32(src),
+                1 => _mm_cvtepu8_epi32(_mm_srli_si128::<4>(src)),
+                2 => _mm_cvtepu8_epi32(_mm_srli_si128::<8>(src)),
+                _ => _mm_cvtepu8_epi32(_mm_srli_si128::<12>(src)),
+            };
+            _mm_storeu_si128(sum_ptr, _mm_add_epi32(_mm_loadu_si128(sum_ptr), components));
+        }
+        i += 16;
+    }
+    native::add_row(&mut sums[i..len], &src_row[i..len]);
+}
+
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn add_row_u16(sums: &mut [u64], src_row: &[u16]) {
+    let len = sums.len().min(src_row.len());
+    let mut i = 0;
+    while i + 8 <= len {
+        let src = _mm_loadu_si128(src_row.as_ptr().add(i) as *const __m128i);
+        for j in 0..4 {
+            let sum_ptr = sums.as_mut_ptr().add(i + j * 2) as *mut __m128i;
+            let components = match j {
+                0 => _mm_cvtepu16_epi64(src),
+                1 => _mm_cvtepu16_epi64(_mm_srli_si128::<4>(src)),
+                2 => _mm_cvtepu16_epi64(_mm_srli_si128::<8>(src)),
+                _ => _mm_cvtepu16_epi64(_mm_srli_si128::<12>(src)),
+            };
+            _mm_storeu_si128(sum_ptr, _mm_add_epi64(_mm_loadu_si128(sum_ptr), components));
+        }
+        i += 8;
+    }
+    native::add_row(&mut sums[i..len], &src_row[i..len]);
+}
+
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn add_row_i32(sums: &mut [i64], src_row: &[i32]) {
+    let len = sums.len().min(src_row.len());
+    let mut i = 0;
+    while i + 4 <= len {
+        let src = _mm_loadu_si128(src_row.as_ptr().add(i) as *const __m128i);
+        let sum_ptr = sums.as_mut_ptr().add(i) as *mut __m128i;
+        let components = _mm_cvtepi32_epi64(src);
+        _mm_storeu_si128(sum_ptr, _mm_add_epi64(_mm_loadu_si128(sum_ptr), components));
+        let sum_ptr = sum_ptr.add(1);
+        let components = _mm_cvtepi32_epi64(_mm_srli_si128::<8>(src));
+        _mm_storeu_si128(sum_ptr, _mm_add_epi64(_mm_loadu_si128(sum_ptr), components));
+        i += 4;
+    }
+    native::add_row(&mut sums[i..len], &src_row[i..len]);
+}
+
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn add_row_f32(sums: &mut [f64], src_row: &[f32]) {
+    let len = sums.len().min(src_row.len());
+    let mut i = 0;
+    while i + 4 <= len {
+        let src = _mm_loadu_ps(src_row.as_ptr().add(i));
+        let sum_ptr = sums.as_mut_ptr().add(i);
+        let components = _mm_cvtps_pd(src);
+        _mm_storeu_pd(sum_ptr, _mm_add_pd(_mm_loadu_pd(sum_ptr), components));
+        let sum_ptr = sum_ptr.add(2);
+        let components = _mm_cvtps_pd(_mm_movehl_ps(src, src));
+        _mm_storeu_pd(sum_ptr, _mm_add_pd(_mm_loadu_pd(sum_ptr), components));
+        i += 4;
+    }
+    native::add_row(&mut sums[i..len], &src_row[i..len]);
+}
+
+/// Averages blocks of `u8` components into the beginning of the destination row.
+/// Returns the number of calculated components.
+///
+/// Only horizontal factors 2 and 4 and pixels with 1, 2 or 4 components
+/// are supported. Sums of components of a block are stored in `u16`.
+pub(crate) fn average_rows_u8(
+    src_rows: &[&[u8]],
+    dst_row: &mut [u8],
+    components_count: usize,
+    factor_x: usize,
+    divider: &BlockDivider,
+) -> usize {
+    let count = divider.count as usize;
+    if count > 256 || src_rows.is_empty() {
+        return 0;
+    }
+    // `mulhi(n, mul)` is equal to `n / count` if `n * (mul * count - 2^16) < 2^16`.
+    let mul = 0x10000usize.div_ceil(count);
+    let max_n = 255 * count + count / 2;
+    if max_n * (mul * count - 0x10000) >= 0x10000 {
+        return 0;
+    }
+    let half = (count / 2) as i16;
+    let mul = mul as u16 as i16;
+
+    macro_rules! call {
+        ($cc:literal, $fx:literal) => {
+            unsafe { average_rows_u8_p::<$cc, $fx>(src_rows, dst_row, half, mul) }
+        };
+    }
+    match (components_count, factor_x) {
+        (1, 2) => call!(1, 2),
+        (2, 2) => call!(2, 2),
+        (4, 2) => call!(4, 2),
+        (1, 4) => call!(1, 4),
+        (2, 4) => call!(2, 4),
+        (4, 4) => call!(4, 4),
+        _ => 0,
+    }
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn average_rows_u8_p<const CC: usize, const FX: usize>(
+    src_rows: &[&[u8]],
+    dst_row: &mut [u8],
+    half: i16,
+    mul: i16,
+) -> usize {
+    let dst_len = dst_row.len() / 8 * 8;
+    debug_assert!(src_rows.iter().all(|row| row.len() >= dst_len * FX));
+    let half = _mm_set1_epi16(half);
+    let mul = _mm_set1_epi16(mul);
+
+    for dst_x in (0..dst_len).step_by(8) {
+        // Sums of pairs of pixels in 16 bytes of every source row
+        let mut pair_sums = [_mm_setzero_si128(); 2];
+        for (i, pair_sum) in pair_sums.iter_mut().take(FX / 2).enumerate() {
+            let src_x = dst_x * FX + i * 16;
+            let mut lo = _mm_setzero_si128();
+            let mut hi = _mm_setzero_si128();
+            for src_row in src_rows {
+                let src = _mm_loadu_si128(src_row.as_ptr().add(src_x) as *const __m128i);
+                lo = _mm_add_epi16(lo, _mm_cvtepu8_epi16(src));
+                hi = _mm_add_epi16(hi, _mm_cvtepu8_epi16(_mm_srli_si128::<8>(src)));
+            }
+            *pair_sum = add_pairs_of_pixels::<CC>(lo, hi);
+        }
+        let sums = if FX == 4 {
+            add_pairs_of_pixels::<CC>(pair_sums[0], pair_sums[1])
+        } else {
+            pair_sums[0]
+        };
+        let averages = _mm_mulhi_epu16(_mm_add_epi16(sums, half), mul);
+        let dst_ptr = dst_row.as_mut_ptr().add(dst_x) as *mut __m128i;
+        _mm_storel_epi64(dst_ptr, _mm_packus_epi16(averages, averages));
+    }
+    dst_len
+}
+
+/// Returns sums of components of adjacent pixels from `a` and `b`
+/// in the same order as pixels are stored.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn add_pairs_of_pixels<const CC: usize>(a: __m128i, b: __m128i) -> __m128i {
+    match CC {
+        1 => _mm_hadd_epi16(a, b),
+        // Sums of low halves of 32-bit values never overflow 16 bits,
+        // so 32-bit addition gives sums of both 16-bit components.
+        2 => _mm_hadd_epi32(a, b),
+        _ => _mm_add_epi16(_mm_unpacklo_epi64(a, b), _mm_unpackhi_epi64(a, b)),
+    }
+}
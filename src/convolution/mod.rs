@@ -1,16 +1,21 @@
 pub use filters::*;
+pub(crate) use integer_box::{box_reduce, BoxComponent, MAX_BLOCK_SIZE};
 
 use crate::compat::*;
 use crate::pixels::InnerPixel;
+use crate::sampler::SampleComponent;
 use crate::{CpuExtensions, ImageView, ImageViewMut};
 
 #[macro_use]
 mod macros;
 
 mod filters;
+mod integer_box;
 #[macro_use]
 mod optimisations;
+mod u16x4;
 mod u8x4;
+mod vertical_u16;
 mod vertical_u8;
 cfg_if::cfg_if! {
     if #[cfg(not(feature = "only_u8x4"))] {
@@ -20,23 +25,23 @@ cfg_if::cfg_if! {
         mod u16x1;
         mod u16x2;
         mod u16x3;
-        mod u16x4;
-        mod i32x1;
+        mod int32;
         mod f32x1;
         mod f32x2;
         mod f32x3;
         mod f32x4;
-        mod vertical_u16;
         mod vertical_f32;
+        mod f16;
+        mod f64;
     }
 }
 
-pub(crate) trait Convolution: InnerPixel {
+pub(crate) trait Convolution: InnerPixel<Component: BoxComponent + SampleComponent> {
     fn horiz_convolution(
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         offset: u32,
-        coeffs: Coefficients,
+        coeffs: &Coefficients,
         cpu_extensions: CpuExtensions,
     );
 
@@ -44,7 +49,7 @@ pub(crate) trait Convolution: InnerPixel {
         src_view: &impl ImageView<Pixel = Self>,
         dst_view: &mut impl ImageViewMut<Pixel = Self>,
         offset: u32,
-        coeffs: Coefficients,
+        coeffs: &Coefficients,
         cpu_extensions: CpuExtensions,
     );
 }
@@ -83,16 +88,103 @@ impl Coefficients {
         }
         res
     }
+
+    /// Returns the maximal value of coefficients.
+    pub fn max_weight(&self) -> f64 {
+        self.values.iter().copied().fold(0., f64::max)
+    }
+
+    /// Copies coefficients of `count` pixels of the result starting from
+    /// the pixel `first` into `dst`. `max_weight` must be the maximal value
+    /// of all coefficients.
+    pub fn copy_part_into(&self, first: usize, count: usize, max_weight: f64, dst: &mut Self) {
+        let window_size = self.window_size;
+        dst.window_size = window_size;
+        dst.values.clear();
+        dst.values
+            .extend_from_slice(&self.values[first * window_size..(first + count) * window_size]);
+        // Fixed-point normalizers select precision by the maximal value
+        // of all coefficients. The extra window without a bound keeps
+        // this precision the same as for all coefficients.
+        dst.values.push(max_weight);
+        dst.values.resize((count + 1) * window_size, 0.);
+        dst.bounds.clear();
+        dst.bounds
+            .extend_from_slice(&self.bounds[first..first + count]);
+    }
+}
+
+/// Mode of handling pixels outside the source image if the kernel
+/// of a filter crosses the image edge.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EdgeMode {
+    /// Pixels outside the image are ignored and weights of the kernel
+    /// are renormalized by the sum of weights of the rest pixels.
+    #[default]
+    Renormalize,
+    /// Pixels outside the image are equal to the nearest edge pixel.
+    Clamp,
+    /// Pixels outside the image are mirrored relative to the image edge.
+    Mirror,
+    /// Pixels outside the image are taken from the opposite side of
+    /// the image. Resizing a tileable texture with this mode gives
+    /// a tileable texture.
+    Wrap,
+    /// Pixels outside the image are transparent black.
+    Constant,
 }
 
+impl EdgeMode {
+    /// Returns the index of a source pixel that is used instead
+    /// of the pixel `x` or `None` if the pixel must be ignored.
+    pub(crate) fn source_index(self, x: i64, size: u32) -> Option<u32> {
+        let size = size as i64;
+        if (0..size).contains(&x) {
+            return Some(x as u32);
+        }
+        match self {
+            Self::Clamp => Some(x.clamp(0, size - 1) as u32),
+            Self::Mirror => {
+                let period = size * 2;
+                let x = x.rem_euclid(period);
+                Some(if x < size { x } else { period - 1 - x } as u32)
+            }
+            // Borders with wrapped pixels are added to the source image before
+            // convolution, so the kernel never crosses edges of such an image.
+            Self::Renormalize | Self::Wrap | Self::Constant => None,
+        }
+    }
+}
+
+/// Returns the radius of the filter kernel in pixels of the source image.
+pub(crate) fn kernel_radius(
+    in0: f64,
+    in1: f64,
+    out_size: u32,
+    filter_support: f64,
+    adaptive_kernel_size: bool,
+    kernel_scale: f64,
+) -> f64 {
+    let scale = (in1 - in0) / out_size as f64;
+    if adaptive_kernel_size {
+        filter_support * scale.max(1.0) * kernel_scale
+    } else {
+        filter_support * kernel_scale
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn precompute_coefficients(
     in_size: u32,
     in0: f64, // Left/top border for cropping
     in1: f64, // Right/bottom border for cropping
     out_size: u32,
-    filter: fn(f64) -> f64,
+    filter: impl Fn(f64) -> f64,
     filter_support: f64,
     adaptive_kernel_size: bool,
+    kernel_scale: f64,
+    edge_mode: EdgeMode,
 ) -> Coefficients {
     if in_size == 0 || out_size == 0 {
         return Coefficients::default();
@@ -105,7 +197,7 @@ pub(crate) fn precompute_coefficients(
         scale.max(1.0)
     } else {
         1.0
-    };
+    } * kernel_scale;
 
     // Determine filter radius size (length of resampling filter)
     let filter_radius = filter_support * filter_scale;
@@ -118,48 +210,58 @@ pub(crate) fn precompute_coefficients(
     let count_of_coeffs = window_size * out_size as usize;
     let mut coeffs: Vec<f64> = Vec::with_capacity(count_of_coeffs);
     let mut bounds: Vec<Bound> = Vec::with_capacity(out_size as usize);
+    let mut weights: Vec<f64> = Vec::with_capacity(window_size);
 
     for out_x in 0..out_size {
         // Find the point in the input image corresponding to the center
         // of the current pixel in the output image.
         let in_center = in0 + (out_x as f64 + 0.5) * scale;
 
-        // x_min and x_max are slice bounds for the input pixels relevant
+        // x_min and x_max are bounds of the kernel in the input image.
+        let x_min = (in_center - filter_radius).floor() as i64;
+        let x_max = (in_center + filter_radius).ceil() as i64;
+        // first_x and end_x are slice bounds for the input pixels relevant
         // to the output pixel we are calculating. Pixel x is relevant
-        // if and only if (x >= x_min) && (x < x_max).
-        // Invariant: 0 <= x_min < x_max <= width
-        let x_min = (in_center - filter_radius).floor().max(0.) as u32;
-        let x_max = (in_center + filter_radius).ceil().min(in_size as f64) as u32;
+        // if and only if (x >= first_x) && (x < end_x).
+        // Invariant: 0 <= first_x < end_x <= width
+        let first_x = x_min.max(0) as u32;
+        let end_x = x_max.min(in_size as i64) as u32;
 
-        let cur_index = coeffs.len();
+        weights.clear();
+        weights.resize((end_x - first_x) as usize, 0.);
         let mut ww: f64 = 0.0;
 
         // Optimization for follow for-cycle:
         // (x + 0.5) - in_center => x - (in_center - 0.5) => x - center
         let center = in_center - 0.5;
 
-        let mut bound_start = x_min;
-        let mut bound_end = x_max;
-
-        // Calculate the weight of each input pixel from the given x-range.
+        // Calculate the weight of each input pixel from the kernel range.
         for x in x_min..x_max {
             let w: f64 = filter((x as f64 - center) * recip_filter_scale);
-            if x == bound_start && w == 0. {
-                // Don't use zero coefficients at the start of the bound;
-                bound_start += 1;
-            } else {
-                coeffs.push(w);
-                ww += w;
-            }
-        }
-        for &c in coeffs.iter().rev() {
-            if bound_end <= bound_start || c != 0. {
-                break;
+            match edge_mode.source_index(x, in_size) {
+                Some(index) => {
+                    weights[(index - first_x) as usize] += w;
+                    ww += w;
+                }
+                // Pixels outside the image are equal to zero,
+                // but their weights are used for normalization.
+                None if edge_mode == EdgeMode::Constant => ww += w,
+                None => (),
             }
-            // Don't use zero coefficients at the end of the bound;
-            bound_end -= 1;
         }
 
+        // Don't use zero coefficients at the start and the end of the bound;
+        let skip_start = weights.iter().take_while(|&&w| w == 0.).count();
+        let skip_end = weights[skip_start..]
+            .iter()
+            .rev()
+            .take_while(|&&w| w == 0.)
+            .count();
+        let bound_start = first_x + skip_start as u32;
+        let bound_end = end_x - skip_end as u32;
+
+        let cur_index = coeffs.len();
+        coeffs.extend_from_slice(&weights[skip_start..]);
         if ww != 0.0 {
             // Normalise values of weights.
             // The sum of weights must be equal to 1.0.
@@ -179,3 +281,107 @@ pub(crate) fn precompute_coefficients(
         bounds,
     }
 }
+
+/// Returns coefficients with weights equal to parts of areas of source
+/// pixels covered by destination pixels. It is a port of the algorithm
+/// used by OpenCV for the `INTER_AREA` interpolation if the image
+/// is downscaled by both axes.
+pub(crate) fn precompute_area_coefficients(
+    in_size: u32,
+    in0: f64, // Left/top border for cropping
+    in1: f64, // Right/bottom border for cropping
+    out_size: u32,
+) -> Coefficients {
+    if in_size == 0 || out_size == 0 {
+        return Coefficients::default();
+    }
+    let scale = (in1 - in0) / out_size as f64;
+    if scale <= 0. {
+        return Coefficients::default();
+    }
+    // Maximum number of source pixels covered by one destination pixel
+    let window_size = scale.ceil() as usize + 1;
+    let mut coeffs: Vec<f64> = Vec::with_capacity(window_size * out_size as usize);
+    let mut bounds: Vec<Bound> = Vec::with_capacity(out_size as usize);
+
+    for out_x in 0..out_size {
+        let fsx1 = in0 + out_x as f64 * scale;
+        let fsx2 = fsx1 + scale;
+        let cell_width = scale.min(in_size as f64 - fsx1);
+        let sx2 = (fsx2.floor() as u32).min(in_size - 1);
+        let sx1 = (fsx1.ceil() as u32).min(sx2);
+
+        let cur_index = coeffs.len();
+        let mut start = sx1;
+        // Weights of source pixels that are partially covered are
+        // ignored if they are too small, like OpenCV does.
+        if sx1 as f64 - fsx1 > 1e-3 {
+            start -= 1;
+            coeffs.push((sx1 as f64 - fsx1) / cell_width);
+        }
+        coeffs.extend((sx1..sx2).map(|_| 1. / cell_width));
+        if fsx2 - sx2 as f64 > 1e-3 {
+            coeffs.push((fsx2 - sx2 as f64).min(1.).min(cell_width) / cell_width);
+        }
+        let size = (coeffs.len() - cur_index) as u32;
+        coeffs.resize(cur_index + window_size, 0.);
+        bounds.push(Bound { start, size });
+    }
+
+    Coefficients {
+        values: coeffs,
+        window_size,
+        bounds,
+    }
+}
+
+/// Returns coefficients of linear interpolation with weights calculated
+/// like OpenCV does for the `INTER_AREA` interpolation if the image
+/// is upscaled by any axis.
+pub(crate) fn precompute_area_interpolation_coefficients(
+    in_size: u32,
+    in0: f64, // Left/top border for cropping
+    in1: f64, // Right/bottom border for cropping
+    out_size: u32,
+) -> Coefficients {
+    if in_size == 0 || out_size == 0 {
+        return Coefficients::default();
+    }
+    let scale = (in1 - in0) / out_size as f64;
+    if scale <= 0. {
+        return Coefficients::default();
+    }
+    let inv_scale = out_size as f64 / (in1 - in0);
+    let window_size = 2;
+    let mut coeffs: Vec<f64> = Vec::with_capacity(window_size * out_size as usize);
+    let mut bounds: Vec<Bound> = Vec::with_capacity(out_size as usize);
+    let first_x = in0.floor();
+    // The fractional part of the left/top border is kept separately
+    // to calculate the same weights as OpenCV does for integer borders.
+    let offset = in0 - first_x;
+    let last_x = (in1.ceil() as u32).clamp(1, in_size) - 1;
+
+    for out_x in 0..out_size {
+        let rel_x = (offset + out_x as f64 * scale).floor();
+        let mut fx = (out_x + 1) as f64 + (offset - (rel_x + 1.)) * inv_scale;
+        fx = if fx <= 0. { 0. } else { fx - fx.floor() };
+        let mut x = first_x as u32 + rel_x as u32;
+        if x >= last_x {
+            x = last_x;
+            fx = 0.;
+        }
+        if fx == 0. {
+            coeffs.extend_from_slice(&[1., 0.]);
+            bounds.push(Bound { start: x, size: 1 });
+        } else {
+            coeffs.extend_from_slice(&[1. - fx, fx]);
+            bounds.push(Bound { start: x, size: 2 });
+        }
+    }
+
+    Coefficients {
+        values: coeffs,
+        window_size,
+        bounds,
+    }
+}
@@ -49,7 +49,7 @@ pub(crate) struct Normalizer16 {
 
 impl Normalizer16 {
     #[inline]
-    pub fn new(coefficients: Coefficients) -> Self {
+    pub fn new(coefficients: &Coefficients) -> Self {
         let max_weight = coefficients
             .values
             .iter()
@@ -144,7 +144,7 @@ pub(crate) struct Normalizer32 {
 
 impl Normalizer32 {
     #[inline]
-    pub fn new(coefficients: Coefficients) -> Self {
+    pub fn new(coefficients: &Coefficients) -> Self {
         let max_weight = coefficients
             .values
             .iter()
@@ -262,9 +262,9 @@ mod tests {
     #[test]
     fn test_minimal_precision() {
         // required for some SIMD optimisations
-        assert!(Normalizer16::new(get_coefficients(0.0)).precision() >= 4);
-        assert!(Normalizer16::new(get_coefficients(2.0)).precision() >= 4);
-        assert!(Normalizer32::new(get_coefficients(0.0)).precision() >= 4);
-        assert!(Normalizer32::new(get_coefficients(2.0)).precision() >= 4);
+        assert!(Normalizer16::new(&get_coefficients(0.0)).precision() >= 4);
+        assert!(Normalizer16::new(&get_coefficients(2.0)).precision() >= 4);
+        assert!(Normalizer32::new(&get_coefficients(0.0)).precision() >= 4);
+        assert!(Normalizer32::new(&get_coefficients(2.0)).precision() >= 4);
     }
 }
@@ -95,14 +95,14 @@ pub(crate) unsafe fn vert_convolution_into_one_row_u16<T>(
             let components = T::components(s_row);
             let coeff_i64x4 = _mm256_set1_epi64x(coeff as i64);
             let source = simd_utils::loadu_si256(components, src_x);
-            for i in 0..4 {
-                let comp_i64x4 = _mm256_shuffle_epi8(source, shuffles[i]);
-                sum[i] = _mm256_add_epi64(sum[i], _mm256_mul_epi32(comp_i64x4, coeff_i64x4));
+            for (s, &shuffle) in sum.iter_mut().zip(&shuffles) {
+                let comp_i64x4 = _mm256_shuffle_epi8(source, shuffle);
+                *s = _mm256_add_epi64(*s, _mm256_mul_epi32(comp_i64x4, coeff_i64x4));
             }
         }
 
-        for i in 0..4 {
-            _mm256_storeu_si256(comp_buf.as_mut_ptr() as *mut __m256i, sum[i]);
+        for (i, &s) in sum.iter().enumerate() {
+            _mm256_storeu_si256(comp_buf.as_mut_ptr() as *mut __m256i, s);
             let component = dst_chunk.get_unchecked_mut(i * 2);
             *component = normalizer.clip(comp_buf[0]);
             let component = dst_chunk.get_unchecked_mut(i * 2 + 1);
@@ -134,14 +134,14 @@ pub(crate) unsafe fn vert_convolution_into_one_row_u16<T>(
             }
             let coeff_i64x4 = _mm256_set1_epi64x(coeff as i64);
             let source = simd_utils::loadu_si256(&buf, 0);
-            for i in 0..4 {
-                let comp_i64x4 = _mm256_shuffle_epi8(source, shuffles[i]);
-                sum[i] = _mm256_add_epi64(sum[i], _mm256_mul_epi32(comp_i64x4, coeff_i64x4));
+            for (s, &shuffle) in sum.iter_mut().zip(&shuffles) {
+                let comp_i64x4 = _mm256_shuffle_epi8(source, shuffle);
+                *s = _mm256_add_epi64(*s, _mm256_mul_epi32(comp_i64x4, coeff_i64x4));
             }
         }
 
-        for i in 0..4 {
-            _mm256_storeu_si256(comp_buf.as_mut_ptr() as *mut __m256i, sum[i]);
+        for (i, &s) in sum.iter().enumerate() {
+            _mm256_storeu_si256(comp_buf.as_mut_ptr() as *mut __m256i, s);
             let component = buf.get_unchecked_mut(i * 2);
             *component = normalizer.clip(comp_buf[0]);
             let component = buf.get_unchecked_mut(i * 2 + 1);
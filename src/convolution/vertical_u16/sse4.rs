@@ -83,9 +83,9 @@ unsafe fn vert_convolution_into_one_row_u16<T: InnerPixel<Component = u16>>(
                 let coeff_i64x2 = _mm_set1_epi64x(two_coeffs[r] as i64);
                 for x in 0..2 {
                     let source = simd_utils::loadu_si128(src_rows[r], src_x + x * 8);
-                    for i in 0..4 {
-                        let c_i64x2 = _mm_shuffle_epi8(source, c_shuffles[i]);
-                        sums[i][x] = _mm_add_epi64(sums[i][x], _mm_mul_epi32(c_i64x2, coeff_i64x2));
+                    for (sum, &c_shuffle) in sums.iter_mut().zip(&c_shuffles) {
+                        let c_i64x2 = _mm_shuffle_epi8(source, c_shuffle);
+                        sum[x] = _mm_add_epi64(sum[x], _mm_mul_epi32(c_i64x2, coeff_i64x2));
                     }
                 }
             }
@@ -99,9 +99,9 @@ unsafe fn vert_convolution_into_one_row_u16<T: InnerPixel<Component = u16>>(
 
                 for x in 0..2 {
                     let source = simd_utils::loadu_si128(components, src_x + x * 8);
-                    for i in 0..4 {
-                        let c_i64x2 = _mm_shuffle_epi8(source, c_shuffles[i]);
-                        sums[i][x] = _mm_add_epi64(sums[i][x], _mm_mul_epi32(c_i64x2, coeff_i64x2));
+                    for (sum, &c_shuffle) in sums.iter_mut().zip(&c_shuffles) {
+                        let c_i64x2 = _mm_shuffle_epi8(source, c_shuffle);
+                        sum[x] = _mm_add_epi64(sum[x], _mm_mul_epi32(c_i64x2, coeff_i64x2));
                     }
                 }
             }
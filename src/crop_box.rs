@@ -80,48 +80,34 @@ impl CropBox {
             height: crop_height,
         }
     }
-}
-
-pub(crate) struct CroppedSrcImageView<'a, T: ImageView> {
-    image_view: &'a T,
-    crop_box: CropBox,
-}
 
-impl<'a, T: ImageView> CroppedSrcImageView<'a, T> {
-    pub fn new(image_view: &'a T) -> Self {
-        Self {
-            image_view,
-            crop_box: CropBox {
-                left: 0.0,
-                top: 0.0,
-                width: image_view.width() as _,
-                height: image_view.height() as _,
-            },
-        }
-    }
-
-    pub fn crop(image_view: &'a T, crop_box: CropBox) -> Result<Self, CropBoxError> {
-        if crop_box.width < 0. || crop_box.height < 0. {
+    /// Checks that the crop box is inside an image with the given dimensions.
+    pub(crate) fn check_bounds(&self, img_width: u32, img_height: u32) -> Result<(), CropBoxError> {
+        if self.width < 0. || self.height < 0. {
             return Err(CropBoxError::WidthOrHeightLessThanZero);
         }
 
-        let img_width = image_view.width() as _;
-        let img_height = image_view.height() as _;
+        let img_width = img_width as f64;
+        let img_height = img_height as f64;
 
-        if crop_box.left >= img_width || crop_box.top >= img_height {
+        if self.left >= img_width || self.top >= img_height {
             return Err(CropBoxError::PositionIsOutOfImageBoundaries);
         }
-        let right = crop_box.left + crop_box.width;
-        let bottom = crop_box.top + crop_box.height;
+        let right = self.left + self.width;
+        let bottom = self.top + self.height;
         if right > img_width || bottom > img_height {
             return Err(CropBoxError::SizeIsOutOfImageBoundaries);
         }
-        Ok(Self {
-            image_view,
-            crop_box,
-        })
+        Ok(())
     }
+}
+
+pub(crate) struct CroppedSrcImageView<'a, T: ImageView> {
+    image_view: &'a T,
+    crop_box: CropBox,
+}
 
+impl<'a, T: ImageView> CroppedSrcImageView<'a, T> {
     pub unsafe fn crop_unchecked(image_view: &'a T, crop_box: CropBox) -> Self {
         Self {
             image_view,
@@ -5,6 +5,10 @@ use thiserror::Error;
 pub enum ImageError {
     #[error("Pixel type of image is not supported")]
     UnsupportedPixelType,
+    #[error("Alignment of image buffer doesn't match to alignment of its pixel type")]
+    InvalidBufferAlignment,
+    #[error("Position of alpha channel is not supported for pixel type of image")]
+    UnsupportedAlphaPosition,
 }
 
 #[derive(Error, Debug, Clone, Copy)]
@@ -38,6 +42,41 @@ pub enum ResizeError {
     PixelTypesAreDifferent,
     #[error("Source cropping option is invalid: {0}")]
     SrcCroppingError(#[from] CropBoxError),
+    #[error("Pixel type or dimensions of images do not match to the resize plan")]
+    ImagesDoNotMatchPlan,
+    #[error("Length of the source row is not equal to the width of the source image")]
+    InvalidRowLength,
+    #[error("All rows of the source image have already been pushed")]
+    TooManyRows,
+    #[error("Edge mode is not supported")]
+    UnsupportedEdgeMode,
+    #[error("Parameters of the filter are invalid")]
+    InvalidFilterParameters,
+    #[error("Scale of the kernel size must be a finite number greater than zero")]
+    InvalidKernelScale,
+    #[error("Reducing gap must be a finite number not less than 1.0")]
+    InvalidReducingGap,
+    #[error(
+        "Size of the cropped source image is not a multiple of the size of the destination image"
+    )]
+    NotIntegerScaleFactor,
+    #[error("Values of the transformation matrix must be finite numbers")]
+    InvalidTransformMatrix,
+    #[error("Resize algorithm is not supported by this operation")]
+    UnsupportedAlgorithm,
+    #[error("Orientation is not supported")]
+    UnsupportedOrientation,
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OrientationError {
+    #[error("Source or destination image is not supported")]
+    ImageError(#[from] ImageError),
+    #[error("Pixel type of source image does not match to destination image")]
+    PixelTypesAreDifferent,
+    #[error("Size of the destination image is not equal to the size of the oriented source image")]
+    InvalidDstSize,
 }
 
 #[derive(Error, Debug, Clone, Copy)]
@@ -1,15 +1,15 @@
-use core::ops::DerefMut;
+use core::ops::{Deref, DerefMut};
 
 use bytemuck::cast_slice_mut;
-use image::{
-    DynamicImage, GrayAlphaImage, GrayImage, ImageBuffer, Luma, LumaA, Rgb, Rgb32FImage, RgbImage,
-    Rgba, Rgba32FImage, RgbaImage,
-};
+use image::metadata::Orientation as ImageOrientation;
+use image::{DynamicImage, ImageBuffer, Luma, LumaA, Rgb, Rgba};
 
-use crate::compat::*;
 use crate::image_view::try_pixel_type;
 use crate::images::{TypedImage, TypedImageRef};
-use crate::{ImageView, ImageViewMut, IntoImageView, IntoImageViewMut, PixelTrait, PixelType};
+use crate::{
+    ImageView, ImageViewMut, IntoImageView, IntoImageViewMut, Orientation, PixelTrait, PixelType,
+    ResizeError, ResizeOptions, Resizer, SrcCropping,
+};
 
 impl IntoImageView for DynamicImage {
     fn pixel_type(&self) -> Option<PixelType> {
@@ -83,11 +83,166 @@ fn image_as_bytes_mut(image: &mut DynamicImage) -> &mut [u8] {
     }
 }
 
-// Implementations for supported versions of ImageBuffer
+impl From<ImageOrientation> for Orientation {
+    fn from(orientation: ImageOrientation) -> Self {
+        Orientation::from_exif(orientation.to_exif() as u16).unwrap_or_default()
+    }
+}
+
+impl Resizer {
+    /// Resizes the image and applies the orientation to the result,
+    /// e.g. the orientation read from EXIF by
+    /// [ImageDecoder::orientation](https://docs.rs/image/latest/image/trait.ImageDecoder.html#method.orientation).
+    ///
+    /// `dst_width` and `dst_height` are the size of the resized image
+    /// before applying the orientation. The returned image has the same
+    /// color type as the source image, its width and height are swapped
+    /// if the orientation rotates the image by 90 or 270 degrees.
+    ///
+    /// The orientation replaces [ResizeOptions::orientation], other options
+    /// are used as is, so the crop box relates to the oriented source image.
+    pub fn resize_with_image_orientation<'o>(
+        &mut self,
+        src_image: &DynamicImage,
+        orientation: ImageOrientation,
+        dst_width: u32,
+        dst_height: u32,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<DynamicImage, ResizeError> {
+        let orientation = Orientation::from(orientation);
+        let options = options
+            .into()
+            .cloned()
+            .unwrap_or_default()
+            .orientation(orientation);
+        let (dst_width, dst_height) = orientation.oriented_size(dst_width, dst_height);
+        self.resize_into_new_image(src_image, dst_width, dst_height, &options)
+    }
+
+    /// Creates a destination image with the same color type as the source
+    /// image and resizes the source image into it.
+    fn resize_into_new_image(
+        &mut self,
+        src_image: &DynamicImage,
+        dst_width: u32,
+        dst_height: u32,
+        options: &ResizeOptions,
+    ) -> Result<DynamicImage, ResizeError> {
+        try_pixel_type(src_image)?;
+        let mut dst_image = DynamicImage::new(dst_width, dst_height, src_image.color());
+        self.resize(src_image, &mut dst_image, options)?;
+        Ok(dst_image)
+    }
+}
+
+/// Extension trait to resize images from the `image` crate into new images
+/// of the same color type with help of [Resizer].
+///
+/// The trait is implemented for
+/// [DynamicImage](https://docs.rs/image/latest/image/enum.DynamicImage.html)
+/// with all color types supported by [IntoImageView], other color types
+/// give [ImageError::UnsupportedPixelType](crate::ImageError::UnsupportedPixelType).
+pub trait ResizeExt {
+    /// Resizes the image into a new image with exactly the given size.
+    fn resize_to<'o>(
+        &self,
+        width: u32,
+        height: u32,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<DynamicImage, ResizeError>;
+
+    /// Resizes the image into a new image that fits within the given bounds
+    /// and has the aspect ratio of the (cropped and oriented) source image,
+    /// like `DynamicImage::resize()` does.
+    fn resize_to_fit<'o>(
+        &self,
+        width: u32,
+        height: u32,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<DynamicImage, ResizeError>;
+
+    /// Resizes the image into a new image with exactly the given size
+    /// without distortions, like `DynamicImage::resize_to_fill()` does
+    /// (the method has another name to not be shadowed by the latter).
+    /// The parts of the source image that don't fit the aspect ratio
+    /// of the new image are cropped evenly from both sides.
+    ///
+    /// Cropping and padding options are replaced.
+    fn resize_to_cover<'o>(
+        &self,
+        width: u32,
+        height: u32,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<DynamicImage, ResizeError>;
+}
+
+impl ResizeExt for DynamicImage {
+    fn resize_to<'o>(
+        &self,
+        width: u32,
+        height: u32,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<DynamicImage, ResizeError> {
+        let options = options.into().cloned().unwrap_or_default();
+        Resizer::new().resize_into_new_image(self, width, height, &options)
+    }
+
+    fn resize_to_fit<'o>(
+        &self,
+        width: u32,
+        height: u32,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<DynamicImage, ResizeError> {
+        let options = options.into().cloned().unwrap_or_default();
+        let (src_width, src_height) = match options.cropping {
+            SrcCropping::Crop(crop_box) => (crop_box.width, crop_box.height),
+            _ => {
+                let (w, h) = options
+                    .orientation
+                    .oriented_size(self.width(), self.height());
+                (w as f64, h as f64)
+            }
+        };
+        let (width, height) = fit_dimensions(src_width, src_height, width, height);
+        Resizer::new().resize_into_new_image(self, width, height, &options)
+    }
+
+    fn resize_to_cover<'o>(
+        &self,
+        width: u32,
+        height: u32,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<DynamicImage, ResizeError> {
+        let mut options = options
+            .into()
+            .cloned()
+            .unwrap_or_default()
+            .fit_into_destination(None);
+        options.padding = None;
+        Resizer::new().resize_into_new_image(self, width, height, &options)
+    }
+}
+
+/// Returns the largest size that fits within the bounds
+/// and preserves the aspect ratio of the source size.
+fn fit_dimensions(src_width: f64, src_height: f64, width: u32, height: u32) -> (u32, u32) {
+    if src_width <= 0. || src_height <= 0. {
+        return (width, height);
+    }
+    let ratio = (width as f64 / src_width).min(height as f64 / src_height);
+    let fit = |size: f64, bound: u32| ((size * ratio).round() as u32).clamp(1, bound.max(1));
+    (fit(src_width, width), fit(src_height, height))
+}
+
+// Implementations for ImageBuffer with supported types of pixels
+// and any container of subpixels.
 
 macro_rules! impl_for_img_buffer {
-    ($img_type:tt, $pixel_type:expr) => {
-        impl IntoImageView for $img_type {
+    ($pixel:ty, $pixel_type:expr) => {
+        impl<C> IntoImageView for ImageBuffer<$pixel, C>
+        where
+            C: Deref<Target = [<$pixel as image::Pixel>::Subpixel]>,
+        {
             fn pixel_type(&self) -> Option<PixelType> {
                 Some($pixel_type)
             }
@@ -102,7 +257,7 @@ macro_rules! impl_for_img_buffer {
 
             fn image_view<P: PixelTrait>(&self) -> Option<impl ImageView<Pixel = P>> {
                 if P::pixel_type() == $pixel_type {
-                    let bytes = bytemuck::cast_slice(self.as_raw().as_ref());
+                    let bytes = bytemuck::cast_slice(self.as_raw().deref());
                     return TypedImageRef::<P>::from_buffer(self.width(), self.height(), bytes)
                         .ok();
                 }
@@ -110,7 +265,10 @@ macro_rules! impl_for_img_buffer {
             }
         }
 
-        impl IntoImageViewMut for $img_type {
+        impl<C> IntoImageViewMut for ImageBuffer<$pixel, C>
+        where
+            C: DerefMut<Target = [<$pixel as image::Pixel>::Subpixel]>,
+        {
             fn image_view_mut<P: PixelTrait>(&mut self) -> Option<impl ImageViewMut<Pixel = P>> {
                 if P::pixel_type() == $pixel_type {
                     return TypedImage::<P>::from_buffer(
@@ -126,22 +284,15 @@ macro_rules! impl_for_img_buffer {
     };
 }
 
-impl_for_img_buffer!(GrayImage, PixelType::U8);
-impl_for_img_buffer!(GrayAlphaImage, PixelType::U8x2);
-impl_for_img_buffer!(RgbImage, PixelType::U8x3);
-impl_for_img_buffer!(RgbaImage, PixelType::U8x4);
-
-type Gray16Image = ImageBuffer<Luma<u16>, Vec<u16>>;
-impl_for_img_buffer!(Gray16Image, PixelType::U16);
-
-type GrayAlpha16Image = ImageBuffer<LumaA<u16>, Vec<u16>>;
-impl_for_img_buffer!(GrayAlpha16Image, PixelType::U16x2);
-
-type Rgb16Image = ImageBuffer<Rgb<u16>, Vec<u16>>;
-impl_for_img_buffer!(Rgb16Image, PixelType::U16x3);
-
-type Rgba16Image = ImageBuffer<Rgba<u16>, Vec<u16>>;
-impl_for_img_buffer!(Rgba16Image, PixelType::U16x4);
-
-impl_for_img_buffer!(Rgb32FImage, PixelType::F32x3);
-impl_for_img_buffer!(Rgba32FImage, PixelType::F32x4);
+impl_for_img_buffer!(Luma<u8>, PixelType::U8);
+impl_for_img_buffer!(LumaA<u8>, PixelType::U8x2);
+impl_for_img_buffer!(Rgb<u8>, PixelType::U8x3);
+impl_for_img_buffer!(Rgba<u8>, PixelType::U8x4);
+impl_for_img_buffer!(Luma<u16>, PixelType::U16);
+impl_for_img_buffer!(LumaA<u16>, PixelType::U16x2);
+impl_for_img_buffer!(Rgb<u16>, PixelType::U16x3);
+impl_for_img_buffer!(Rgba<u16>, PixelType::U16x4);
+impl_for_img_buffer!(Luma<f32>, PixelType::F32);
+impl_for_img_buffer!(LumaA<f32>, PixelType::F32x2);
+impl_for_img_buffer!(Rgb<f32>, PixelType::F32x3);
+impl_for_img_buffer!(Rgba<f32>, PixelType::F32x4);
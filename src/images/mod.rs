@@ -3,6 +3,8 @@ use core::fmt::Debug;
 
 pub use cropped_image::*;
 pub use image::*;
+#[cfg(feature = "image")]
+pub use image_crate::ResizeExt;
 pub use typed_cropped_image::*;
 pub use typed_image::*;
 pub(crate) use unsafe_image::UnsafeImageMut;
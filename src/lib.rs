@@ -16,6 +16,7 @@ cfg_if::cfg_if! {
 pub use alpha::errors::*;
 pub use array_chunks::*;
 pub use change_components_type::*;
+pub use color::color_space::ColorSpace;
 pub use color::mappers::*;
 pub use color::PixelComponentMapper;
 pub use convolution::*;
@@ -23,11 +24,24 @@ pub use cpu_extensions::CpuExtensions;
 pub use crop_box::*;
 pub use errors::*;
 pub use image_view::*;
-pub use mul_div::MulDiv;
+#[cfg(feature = "image")]
+pub use images::ResizeExt;
+pub use mul_div::{AlphaPosition, MulDiv};
+pub use orientation::{
+    flip_horizontal, flip_vertical, orient, rotate180, rotate270, rotate90, transpose, Orientation,
+};
 pub use pixels::PixelType;
-pub use resizer::{ResizeAlg, ResizeOptions, Resizer, SrcCropping};
+pub use pyramid::{ImagePyramid, PyramidLevel, PyramidOptions, PyramidRounding};
+pub use resize_plan::ResizePlan;
+pub use resizer::{
+    AlphaMode, AxisFilter, FillColor, Padding, ResizeAlg, ResizeOptions, Resizer, SrcCropping,
+};
+pub use sampler::Sampler;
+pub use streaming_resizer::StreamingResizer;
+pub use warp::AffineMatrix;
 
 use crate::alpha::AlphaMulDiv;
+use crate::color::color_space::ColorSpaceMapping;
 
 #[macro_use]
 mod utils;
@@ -47,14 +61,20 @@ pub mod images;
 mod mul_div;
 #[cfg(target_arch = "aarch64")]
 mod neon_utils;
+mod orientation;
 pub mod pixels;
+mod pyramid;
+mod resize_plan;
 mod resizer;
+mod sampler;
 #[cfg(target_arch = "x86_64")]
 mod simd_utils;
+mod streaming_resizer;
 #[cfg(feature = "for_testing")]
 pub mod testing;
 #[cfg(feature = "rayon")]
 pub(crate) mod threading;
+mod warp;
 #[cfg(target_arch = "wasm32")]
 mod wasm32_utils;
 
@@ -62,6 +82,6 @@ mod wasm32_utils;
 ///
 /// This trait must be used in your code instead of [InnerPixel](pixels::InnerPixel).
 #[allow(private_bounds)]
-pub trait PixelTrait: Convolution + AlphaMulDiv {}
+pub trait PixelTrait: Convolution + AlphaMulDiv + ColorSpaceMapping {}
 
-impl<P: Convolution + AlphaMulDiv> PixelTrait for P {}
+impl<P: Convolution + AlphaMulDiv + ColorSpaceMapping> PixelTrait for P {}
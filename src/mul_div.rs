@@ -1,10 +1,15 @@
 use crate::cpu_extensions::CpuExtensions;
 use crate::image_view::{try_pixel_type, ImageViewMut, IntoImageView, IntoImageViewMut};
-use crate::pixels::{F32x2, F32x4, U16x2, U16x4, U8x2, U8x4};
+use crate::pixels::{F16x2, F16x4, F32x2, F32x4, F64x2, F64x4, U16x2, U16x4, U8x2, U8x4};
 use crate::{ImageError, ImageView, MulDivImagesError, PixelTrait, PixelType};
 
 /// Methods of this structure used to multiply or divide color-channels (RGB or Luma)
-/// by alpha-channel. Supported pixel types: U8x2, U8x4, U16x2, U16x4, F32x2 and F32x4.
+/// by alpha-channel. Supported pixel types: U8x2, U8x4, U16x2, U16x4, F32x2, F32x4,
+/// F16x2, F16x4, F64x2 and F64x4.
+///
+/// The alpha-channel is the last component of pixels by default.
+/// Use [MulDiv::set_alpha_position] to process U8x4 and U16x4 images
+/// with the alpha-channel at the first position (e.g. ARGB).
 ///
 /// By default, the instance of `MulDiv` created with the best CPU-extension provided by your CPU.
 /// You can change this by using method [MulDiv::set_cpu_extensions].
@@ -27,6 +32,26 @@ use crate::{ImageError, ImageView, MulDivImagesError, PixelTrait, PixelType};
 #[derive(Default, Debug, Clone)]
 pub struct MulDiv {
     cpu_extensions: CpuExtensions,
+    alpha_position: AlphaPosition,
+}
+
+/// Position of the alpha channel among components of a pixel.
+///
+/// For example, pixels in RGBA and BGRA orders have the alpha channel
+/// at the last position, pixels in ARGB and ABGR orders - at the first one.
+/// The order of color channels doesn't matter to multiply or divide them
+/// by alpha.
+///
+/// The first position is supported only for pixels with four components
+/// (U8x4 and U16x4).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AlphaPosition {
+    /// The alpha channel is the last component of a pixel (RGBA, BGRA).
+    #[default]
+    Last,
+    /// The alpha channel is the first component of a pixel (ARGB, ABGR).
+    First,
 }
 
 impl MulDiv {
@@ -45,6 +70,15 @@ impl MulDiv {
         self.cpu_extensions = extensions;
     }
 
+    pub fn alpha_position(&self) -> AlphaPosition {
+        self.alpha_position
+    }
+
+    /// Sets the position of the alpha channel among components of pixels.
+    pub fn set_alpha_position(&mut self, alpha_position: AlphaPosition) {
+        self.alpha_position = alpha_position;
+    }
+
     /// Multiplies color-channels (RGB or Luma) of source image by alpha-channel and store
     /// result into destination image.
     pub fn multiply_alpha(
@@ -66,6 +100,10 @@ impl MulDiv {
             PixelType::U16x4 => self.multiply::<U16x4>(src_image, dst_image),
             PixelType::F32x2 => self.multiply::<F32x2>(src_image, dst_image),
             PixelType::F32x4 => self.multiply::<F32x4>(src_image, dst_image),
+            PixelType::F16x2 => self.multiply::<F16x2>(src_image, dst_image),
+            PixelType::F16x4 => self.multiply::<F16x4>(src_image, dst_image),
+            PixelType::F64x2 => self.multiply::<F64x2>(src_image, dst_image),
+            PixelType::F64x4 => self.multiply::<F64x4>(src_image, dst_image),
             _ => Err(MulDivImagesError::ImageError(
                 ImageError::UnsupportedPixelType,
             )),
@@ -102,8 +140,9 @@ impl MulDiv {
         if src_view.width() != dst_view.width() || src_view.height() != dst_view.height() {
             return Err(MulDivImagesError::SizeIsDifferent);
         }
+        self.check_alpha_position::<P>()?;
         if src_view.width() > 0 && src_view.height() > 0 {
-            P::multiply_alpha(src_view, dst_view, self.cpu_extensions)?;
+            P::multiply_alpha(src_view, dst_view, self.cpu_extensions, self.alpha_position)?;
         }
         Ok(())
     }
@@ -123,6 +162,10 @@ impl MulDiv {
             PixelType::U16x4 => self.multiply_inplace::<U16x4>(image),
             PixelType::F32x2 => self.multiply_inplace::<F32x2>(image),
             PixelType::F32x4 => self.multiply_inplace::<F32x4>(image),
+            PixelType::F16x2 => self.multiply_inplace::<F16x2>(image),
+            PixelType::F16x4 => self.multiply_inplace::<F16x4>(image),
+            PixelType::F64x2 => self.multiply_inplace::<F64x2>(image),
+            PixelType::F64x4 => self.multiply_inplace::<F64x4>(image),
             _ => Err(ImageError::UnsupportedPixelType),
         }
 
@@ -148,8 +191,9 @@ impl MulDiv {
         &self,
         img_view: &mut impl ImageViewMut<Pixel = P>,
     ) -> Result<(), ImageError> {
+        self.check_alpha_position::<P>()?;
         if img_view.width() > 0 && img_view.height() > 0 {
-            P::multiply_alpha_inplace(img_view, self.cpu_extensions)
+            P::multiply_alpha_inplace(img_view, self.cpu_extensions, self.alpha_position)
         } else {
             Ok(())
         }
@@ -176,6 +220,10 @@ impl MulDiv {
             PixelType::U16x4 => self.divide::<U16x4>(src_image, dst_image),
             PixelType::F32x2 => self.divide::<F32x2>(src_image, dst_image),
             PixelType::F32x4 => self.divide::<F32x4>(src_image, dst_image),
+            PixelType::F16x2 => self.divide::<F16x2>(src_image, dst_image),
+            PixelType::F16x4 => self.divide::<F16x4>(src_image, dst_image),
+            PixelType::F64x2 => self.divide::<F64x2>(src_image, dst_image),
+            PixelType::F64x4 => self.divide::<F64x4>(src_image, dst_image),
             _ => Err(MulDivImagesError::ImageError(
                 ImageError::UnsupportedPixelType,
             )),
@@ -212,8 +260,9 @@ impl MulDiv {
         if src_view.width() != dst_view.width() || src_view.height() != dst_view.height() {
             return Err(MulDivImagesError::SizeIsDifferent);
         }
+        self.check_alpha_position::<P>()?;
         if src_view.width() > 0 && src_view.height() > 0 {
-            P::divide_alpha(src_view, dst_view, self.cpu_extensions)?;
+            P::divide_alpha(src_view, dst_view, self.cpu_extensions, self.alpha_position)?;
         }
         Ok(())
     }
@@ -233,6 +282,10 @@ impl MulDiv {
             PixelType::U16x4 => self.divide_inplace::<U16x4>(image),
             PixelType::F32x2 => self.divide_inplace::<F32x2>(image),
             PixelType::F32x4 => self.divide_inplace::<F32x4>(image),
+            PixelType::F16x2 => self.divide_inplace::<F16x2>(image),
+            PixelType::F16x4 => self.divide_inplace::<F16x4>(image),
+            PixelType::F64x2 => self.divide_inplace::<F64x2>(image),
+            PixelType::F64x4 => self.divide_inplace::<F64x4>(image),
             _ => Err(ImageError::UnsupportedPixelType),
         }
 
@@ -258,29 +311,62 @@ impl MulDiv {
         &self,
         img_view: &mut impl ImageViewMut<Pixel = P>,
     ) -> Result<(), ImageError> {
+        self.check_alpha_position::<P>()?;
         if img_view.width() > 0 && img_view.height() > 0 {
-            P::divide_alpha_inplace(img_view, self.cpu_extensions)
+            P::divide_alpha_inplace(img_view, self.cpu_extensions, self.alpha_position)
         } else {
             Ok(())
         }
     }
 
     pub fn is_supported(&self, pixel_type: PixelType) -> bool {
-        #[cfg(not(feature = "only_u8x4"))]
-        {
-            matches!(
-                pixel_type,
-                PixelType::U8x2
-                    | PixelType::U8x4
-                    | PixelType::U16x2
-                    | PixelType::U16x4
-                    | PixelType::F32x2
-                    | PixelType::F32x4
-            )
-        }
-        #[cfg(feature = "only_u8x4")]
-        {
-            matches!(pixel_type, PixelType::U8x4)
+        is_supported_pixel_type(pixel_type)
+            && is_supported_alpha_position(pixel_type, self.alpha_position)
+    }
+
+    fn check_alpha_position<P: PixelTrait>(&self) -> Result<(), ImageError> {
+        if is_supported_alpha_position(P::pixel_type(), self.alpha_position) {
+            Ok(())
+        } else {
+            Err(ImageError::UnsupportedAlphaPosition)
         }
     }
 }
+
+/// Returns `true` if images with the given pixel type have an alpha channel
+/// that can be used to multiply or divide other channels.
+pub(crate) fn is_supported_pixel_type(pixel_type: PixelType) -> bool {
+    #[cfg(not(feature = "only_u8x4"))]
+    {
+        matches!(
+            pixel_type,
+            PixelType::U8x2
+                | PixelType::U8x4
+                | PixelType::U16x2
+                | PixelType::U16x4
+                | PixelType::F32x2
+                | PixelType::F32x4
+                | PixelType::F16x2
+                | PixelType::F16x4
+                | PixelType::F64x2
+                | PixelType::F64x4
+        )
+    }
+    #[cfg(feature = "only_u8x4")]
+    {
+        // U16x4 is used to resize U8x4 images in linear color space.
+        matches!(pixel_type, PixelType::U8x4 | PixelType::U16x4)
+    }
+}
+
+/// Returns `true` if the alpha channel of images with the given pixel type
+/// may be at the given position.
+pub(crate) fn is_supported_alpha_position(
+    pixel_type: PixelType,
+    alpha_position: AlphaPosition,
+) -> bool {
+    match alpha_position {
+        AlphaPosition::Last => true,
+        AlphaPosition::First => matches!(pixel_type, PixelType::U8x4 | PixelType::U16x4),
+    }
+}
@@ -0,0 +1,345 @@
+use crate::compat::*;
+use crate::pixels::{self, InnerPixel};
+use crate::{
+    try_pixel_type, CropBox, ImageError, ImageView, ImageViewMut, IntoImageView, IntoImageViewMut,
+    OrientationError, PixelType,
+};
+
+/// Orientation of an image, like the `Orientation` tag of EXIF.
+///
+/// It describes the lossless transformation that must be applied
+/// to the stored image to display it correctly.
+/// Rotations are clockwise.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// The image is displayed as is (EXIF value `1`).
+    #[default]
+    Normal,
+    /// Mirror the image horizontally (EXIF value `2`).
+    FlipHorizontal,
+    /// Rotate the image by 180 degrees (EXIF value `3`).
+    Rotate180,
+    /// Mirror the image vertically (EXIF value `4`).
+    FlipVertical,
+    /// Mirror the image across the main diagonal (EXIF value `5`).
+    Transpose,
+    /// Rotate the image by 90 degrees (EXIF value `6`).
+    Rotate90,
+    /// Mirror the image across the anti-diagonal (EXIF value `7`).
+    Transverse,
+    /// Rotate the image by 270 degrees (EXIF value `8`).
+    Rotate270,
+}
+
+impl Orientation {
+    /// Returns orientation corresponding to the value of the `Orientation`
+    /// tag of EXIF or `None` if the value is not in range `1..=8`.
+    pub fn from_exif(value: u16) -> Option<Self> {
+        let orientation = match value {
+            1 => Self::Normal,
+            2 => Self::FlipHorizontal,
+            3 => Self::Rotate180,
+            4 => Self::FlipVertical,
+            5 => Self::Transpose,
+            6 => Self::Rotate90,
+            7 => Self::Transverse,
+            8 => Self::Rotate270,
+            _ => return None,
+        };
+        Some(orientation)
+    }
+
+    /// Returns the value of the `Orientation` tag of EXIF.
+    pub fn to_exif(self) -> u16 {
+        match self {
+            Self::Normal => 1,
+            Self::FlipHorizontal => 2,
+            Self::Rotate180 => 3,
+            Self::FlipVertical => 4,
+            Self::Transpose => 5,
+            Self::Rotate90 => 6,
+            Self::Transverse => 7,
+            Self::Rotate270 => 8,
+        }
+    }
+
+    /// Returns `true` if the width and height of the image
+    /// are swapped by this orientation.
+    pub fn swaps_dimensions(self) -> bool {
+        matches!(
+            self,
+            Self::Transpose | Self::Rotate90 | Self::Transverse | Self::Rotate270
+        )
+    }
+
+    /// Returns the size of the image with the given size
+    /// after applying the orientation.
+    pub fn oriented_size(self, width: u32, height: u32) -> (u32, u32) {
+        if self.swaps_dimensions() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Returns the way to find a source pixel for a destination pixel.
+    fn mapping(self) -> PixelMapping {
+        let (transpose, flip_x, flip_y) = match self {
+            Self::Normal => (false, false, false),
+            Self::FlipHorizontal => (false, true, false),
+            Self::Rotate180 => (false, true, true),
+            Self::FlipVertical => (false, false, true),
+            Self::Transpose => (true, false, false),
+            Self::Rotate90 => (true, false, true),
+            Self::Transverse => (true, true, true),
+            Self::Rotate270 => (true, true, false),
+        };
+        PixelMapping {
+            transpose,
+            flip_x,
+            flip_y,
+        }
+    }
+
+    /// Maps a crop box in coordinates of the oriented image
+    /// into coordinates of the source image with the given size.
+    pub(crate) fn src_crop_box(
+        self,
+        crop_box: CropBox,
+        src_width: u32,
+        src_height: u32,
+    ) -> CropBox {
+        let mapping = self.mapping();
+        let CropBox {
+            mut left,
+            mut top,
+            mut width,
+            mut height,
+        } = crop_box;
+        if mapping.transpose {
+            (left, top) = (top, left);
+            (width, height) = (height, width);
+        }
+        if mapping.flip_x {
+            left = src_width as f64 - left - width;
+        }
+        if mapping.flip_y {
+            top = src_height as f64 - top - height;
+        }
+        CropBox {
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+}
+
+/// The destination pixel `(x, y)` is equal to the source pixel `(a, b)`,
+/// where `(a, b)` is `(x, y)` or `(y, x)` if `transpose` is `true`,
+/// and then `a` and `b` are mirrored if `flip_x` and `flip_y` are `true`.
+struct PixelMapping {
+    transpose: bool,
+    flip_x: bool,
+    flip_y: bool,
+}
+
+/// Size of square tiles used to transpose images.
+const TILE_SIZE: usize = 32;
+
+/// Applies the orientation to the source image and stores the result
+/// into the destination image.
+///
+/// The size of the destination image must be equal to the size
+/// of the oriented source image.
+pub(crate) fn orient_typed<P: InnerPixel>(
+    src_view: &impl ImageView<Pixel = P>,
+    dst_view: &mut impl ImageViewMut<Pixel = P>,
+    orientation: Orientation,
+) {
+    let mapping = orientation.mapping();
+    let src_rows: Vec<&[P]> = src_view.iter_rows(0).collect();
+    let mut dst_rows: Vec<&mut [P]> = dst_view.iter_rows_mut(0).collect();
+    let src_height = src_rows.len();
+    let src_row = |y: usize| {
+        if mapping.flip_y {
+            src_rows[src_height - 1 - y]
+        } else {
+            src_rows[y]
+        }
+    };
+
+    if !mapping.transpose {
+        for (y, dst_row) in dst_rows.iter_mut().enumerate() {
+            let src_row = src_row(y);
+            if mapping.flip_x {
+                for (d, &s) in dst_row.iter_mut().zip(src_row.iter().rev()) {
+                    *d = s;
+                }
+            } else {
+                dst_row.copy_from_slice(src_row);
+            }
+        }
+        return;
+    }
+
+    // Rows of the destination image are columns of the source image,
+    // so the image is processed by tiles to use CPU cache effectively.
+    let src_width = src_view.width() as usize;
+    for (tile_y, dst_rows) in dst_rows.chunks_mut(TILE_SIZE).enumerate() {
+        let first_col = tile_y * TILE_SIZE;
+        for first_row in (0..src_height).step_by(TILE_SIZE) {
+            let last_row = (first_row + TILE_SIZE).min(src_height);
+            for (col, dst_row) in (first_col..).zip(dst_rows.iter_mut()) {
+                let src_x = if mapping.flip_x {
+                    src_width - 1 - col
+                } else {
+                    col
+                };
+                for x in first_row..last_row {
+                    dst_row[x] = src_row(x)[src_x];
+                }
+            }
+        }
+    }
+}
+
+/// Applies the orientation to the source image and stores the result
+/// into the destination image.
+///
+/// The size of the destination image must be equal to the size
+/// of the oriented source image (see [Orientation::oriented_size]),
+/// otherwise [OrientationError::InvalidDstSize] is returned.
+pub fn orient(
+    src_image: &impl IntoImageView,
+    dst_image: &mut impl IntoImageViewMut,
+    orientation: Orientation,
+) -> Result<(), OrientationError> {
+    let src_pixel_type = try_pixel_type(src_image)?;
+    let dst_pixel_type = try_pixel_type(dst_image)?;
+    if src_pixel_type != dst_pixel_type {
+        return Err(OrientationError::PixelTypesAreDifferent);
+    }
+    let oriented_size = orientation.oriented_size(src_image.width(), src_image.height());
+    if oriented_size != (dst_image.width(), dst_image.height()) {
+        return Err(OrientationError::InvalidDstSize);
+    }
+
+    use PixelType as PT;
+
+    macro_rules! match_img {
+        (
+            $(($p: path, $pt: path),)*
+        ) => (
+            match src_pixel_type {
+                $(
+                    $p => {
+                        match (
+                            src_image.image_view::<$pt>(),
+                            dst_image.image_view_mut::<$pt>(),
+                        ) {
+                            (Some(src), Some(mut dst)) => {
+                                orient_typed(&src, &mut dst, orientation);
+                                Ok(())
+                            }
+                            _ => Err(ImageError::InvalidBufferAlignment.into()),
+                        }
+                    }
+                )*
+                _ => Err(OrientationError::PixelTypesAreDifferent),
+            }
+        )
+    }
+
+    #[cfg(not(feature = "only_u8x4"))]
+    #[allow(unreachable_patterns)]
+    let result = match_img!(
+        (PT::U8, pixels::U8),
+        (PT::U8x2, pixels::U8x2),
+        (PT::U8x3, pixels::U8x3),
+        (PT::U8x4, pixels::U8x4),
+        (PT::U16, pixels::U16),
+        (PT::U16x2, pixels::U16x2),
+        (PT::U16x3, pixels::U16x3),
+        (PT::U16x4, pixels::U16x4),
+        (PT::I32, pixels::I32),
+        (PT::F32, pixels::F32),
+        (PT::F32x2, pixels::F32x2),
+        (PT::F32x3, pixels::F32x3),
+        (PT::F32x4, pixels::F32x4),
+        (PT::F16, pixels::F16),
+        (PT::F16x2, pixels::F16x2),
+        (PT::F16x3, pixels::F16x3),
+        (PT::F16x4, pixels::F16x4),
+        (PT::F64, pixels::F64),
+        (PT::F64x2, pixels::F64x2),
+        (PT::F64x3, pixels::F64x3),
+        (PT::F64x4, pixels::F64x4),
+        (PT::I32x2, pixels::I32x2),
+        (PT::I32x3, pixels::I32x3),
+        (PT::I32x4, pixels::I32x4),
+        (PT::U32, pixels::U32),
+        (PT::U32x2, pixels::U32x2),
+        (PT::U32x3, pixels::U32x3),
+        (PT::U32x4, pixels::U32x4),
+    );
+
+    #[cfg(feature = "only_u8x4")]
+    let result = match_img!((PT::U8x4, pixels::U8x4),);
+
+    result
+}
+
+/// Mirrors the source image horizontally into the destination image
+/// with the same size.
+pub fn flip_horizontal(
+    src_image: &impl IntoImageView,
+    dst_image: &mut impl IntoImageViewMut,
+) -> Result<(), OrientationError> {
+    orient(src_image, dst_image, Orientation::FlipHorizontal)
+}
+
+/// Mirrors the source image vertically into the destination image
+/// with the same size.
+pub fn flip_vertical(
+    src_image: &impl IntoImageView,
+    dst_image: &mut impl IntoImageViewMut,
+) -> Result<(), OrientationError> {
+    orient(src_image, dst_image, Orientation::FlipVertical)
+}
+
+/// Rotates the source image by 90 degrees clockwise into the destination
+/// image with swapped width and height.
+pub fn rotate90(
+    src_image: &impl IntoImageView,
+    dst_image: &mut impl IntoImageViewMut,
+) -> Result<(), OrientationError> {
+    orient(src_image, dst_image, Orientation::Rotate90)
+}
+
+/// Rotates the source image by 180 degrees into the destination image
+/// with the same size.
+pub fn rotate180(
+    src_image: &impl IntoImageView,
+    dst_image: &mut impl IntoImageViewMut,
+) -> Result<(), OrientationError> {
+    orient(src_image, dst_image, Orientation::Rotate180)
+}
+
+/// Rotates the source image by 270 degrees clockwise into the destination
+/// image with swapped width and height.
+pub fn rotate270(
+    src_image: &impl IntoImageView,
+    dst_image: &mut impl IntoImageViewMut,
+) -> Result<(), OrientationError> {
+    orient(src_image, dst_image, Orientation::Rotate270)
+}
+
+/// Mirrors the source image across its main diagonal into
+/// the destination image with swapped width and height.
+pub fn transpose(
+    src_image: &impl IntoImageView,
+    dst_image: &mut impl IntoImageViewMut,
+) -> Result<(), OrientationError> {
+    orient(src_image, dst_image, Orientation::Transpose)
+}
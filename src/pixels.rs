@@ -4,6 +4,8 @@ use core::marker::PhantomData;
 use core::mem::size_of;
 use core::slice;
 
+pub use half::f16;
+
 #[allow(unused_imports)]
 // It is used to implement floats in no_std
 use crate::compat::*;
@@ -24,6 +26,21 @@ pub enum PixelType {
     F32x2,
     F32x3,
     F32x4,
+    F16,
+    F16x2,
+    F16x3,
+    F16x4,
+    F64,
+    F64x2,
+    F64x3,
+    F64x4,
+    I32x2,
+    I32x3,
+    I32x4,
+    U32,
+    U32x2,
+    U32x3,
+    U32x4,
 }
 
 impl PixelType {
@@ -40,10 +57,29 @@ impl PixelType {
             Self::F32x2 => 8,
             Self::F32x3 => 12,
             Self::F32x4 => 16,
+            Self::F16 => 2,
+            Self::F16x2 => 4,
+            Self::F16x3 => 6,
+            Self::F16x4 => 8,
+            Self::F64 => 8,
+            Self::F64x2 => 16,
+            Self::F64x3 => 24,
+            Self::F64x4 => 32,
+            Self::I32x2 => 8,
+            Self::I32x3 => 12,
+            Self::I32x4 => 16,
+            Self::U32x2 => 8,
+            Self::U32x3 => 12,
+            Self::U32x4 => 16,
             _ => 4,
         }
     }
 
+    /// Returns `true` if components of the pixel have `u8` type.
+    pub(crate) fn components_is_u8(&self) -> bool {
+        matches!(self, Self::U8 | Self::U8x2 | Self::U8x3 | Self::U8x4)
+    }
+
     /// Returns `true` if the given buffer has the same alignment as the pixel.
     pub(crate) fn is_aligned(&self, buffer: &[u8]) -> bool {
         match self {
@@ -60,6 +96,21 @@ impl PixelType {
             Self::F32x2 => unsafe { buffer.align_to::<F32x2>().0.is_empty() },
             Self::F32x3 => unsafe { buffer.align_to::<F32x3>().0.is_empty() },
             Self::F32x4 => unsafe { buffer.align_to::<F32x4>().0.is_empty() },
+            Self::F16 => unsafe { buffer.align_to::<F16>().0.is_empty() },
+            Self::F16x2 => unsafe { buffer.align_to::<F16x2>().0.is_empty() },
+            Self::F16x3 => unsafe { buffer.align_to::<F16x3>().0.is_empty() },
+            Self::F16x4 => unsafe { buffer.align_to::<F16x4>().0.is_empty() },
+            Self::F64 => unsafe { buffer.align_to::<F64>().0.is_empty() },
+            Self::F64x2 => unsafe { buffer.align_to::<F64x2>().0.is_empty() },
+            Self::F64x3 => unsafe { buffer.align_to::<F64x3>().0.is_empty() },
+            Self::F64x4 => unsafe { buffer.align_to::<F64x4>().0.is_empty() },
+            Self::I32x2 => unsafe { buffer.align_to::<I32x2>().0.is_empty() },
+            Self::I32x3 => unsafe { buffer.align_to::<I32x3>().0.is_empty() },
+            Self::I32x4 => unsafe { buffer.align_to::<I32x4>().0.is_empty() },
+            Self::U32 => unsafe { buffer.align_to::<U32>().0.is_empty() },
+            Self::U32x2 => unsafe { buffer.align_to::<U32x2>().0.is_empty() },
+            Self::U32x3 => unsafe { buffer.align_to::<U32x3>().0.is_empty() },
+            Self::U32x4 => unsafe { buffer.align_to::<U32x4>().0.is_empty() },
         }
     }
 }
@@ -122,6 +173,18 @@ impl PixelComponent for f32 {
     type CountOfComponentValues = Values<0>;
 }
 
+impl PixelComponent for f16 {
+    type CountOfComponentValues = Values<0>;
+}
+
+impl PixelComponent for f64 {
+    type CountOfComponentValues = Values<0>;
+}
+
+impl PixelComponent for u32 {
+    type CountOfComponentValues = Values<0>;
+}
+
 // Prevent users from implementing the InnerPixel trait.
 mod private {
     pub trait Sealed {}
@@ -359,6 +422,126 @@ pixel_struct!(
     PixelType::F32x4,
     "Four `f32` components per pixel (e.g. RGBA32F)"
 );
+pixel_struct!(
+    F16,
+    f16,
+    f16,
+    1,
+    PixelType::F16,
+    "One `f16` component per pixel (e.g. L16F)"
+);
+pixel_struct!(
+    F16x2,
+    [f16; 2],
+    f16,
+    2,
+    PixelType::F16x2,
+    "Two `f16` components per pixel (e.g. LA16F)"
+);
+pixel_struct!(
+    F16x3,
+    [f16; 3],
+    f16,
+    3,
+    PixelType::F16x3,
+    "Three `f16` components per pixel (e.g. RGB16F)"
+);
+pixel_struct!(
+    F16x4,
+    [f16; 4],
+    f16,
+    4,
+    PixelType::F16x4,
+    "Four `f16` components per pixel (e.g. RGBA16F)"
+);
+pixel_struct!(
+    F64,
+    f64,
+    f64,
+    1,
+    PixelType::F64,
+    "One `f64` component per pixel (e.g. L64F)"
+);
+pixel_struct!(
+    F64x2,
+    [f64; 2],
+    f64,
+    2,
+    PixelType::F64x2,
+    "Two `f64` components per pixel (e.g. LA64F)"
+);
+pixel_struct!(
+    F64x3,
+    [f64; 3],
+    f64,
+    3,
+    PixelType::F64x3,
+    "Three `f64` components per pixel (e.g. RGB64F)"
+);
+pixel_struct!(
+    F64x4,
+    [f64; 4],
+    f64,
+    4,
+    PixelType::F64x4,
+    "Four `f64` components per pixel (e.g. RGBA64F)"
+);
+pixel_struct!(
+    I32x2,
+    [i32; 2],
+    i32,
+    2,
+    PixelType::I32x2,
+    "Two `i32` components per pixel"
+);
+pixel_struct!(
+    I32x3,
+    [i32; 3],
+    i32,
+    3,
+    PixelType::I32x3,
+    "Three `i32` components per pixel"
+);
+pixel_struct!(
+    I32x4,
+    [i32; 4],
+    i32,
+    4,
+    PixelType::I32x4,
+    "Four `i32` components per pixel"
+);
+pixel_struct!(
+    U32,
+    u32,
+    u32,
+    1,
+    PixelType::U32,
+    "One `u32` component per pixel"
+);
+pixel_struct!(
+    U32x2,
+    [u32; 2],
+    u32,
+    2,
+    PixelType::U32x2,
+    "Two `u32` components per pixel"
+);
+pixel_struct!(
+    U32x3,
+    [u32; 3],
+    u32,
+    3,
+    PixelType::U32x3,
+    "Three `u32` components per pixel"
+);
+pixel_struct!(
+    U32x4,
+    [u32; 4],
+    u32,
+    4,
+    PixelType::U32x4,
+    "Four `u32` components per pixel"
+);
 
 pub trait IntoPixelComponent<Out: PixelComponent>
 where
@@ -393,6 +576,18 @@ impl IntoPixelComponent<f32> for u8 {
     }
 }
 
+impl IntoPixelComponent<u32> for u8 {
+    fn into_component(self) -> u32 {
+        u32::from_le_bytes([self; 4])
+    }
+}
+
+impl IntoPixelComponent<f16> for u8 {
+    fn into_component(self) -> f16 {
+        f16::from_f32(self.into_component())
+    }
+}
+
 // u16
 
 impl IntoPixelComponent<u8> for u16 {
@@ -413,6 +608,19 @@ impl IntoPixelComponent<f32> for u16 {
     }
 }
 
+impl IntoPixelComponent<u32> for u16 {
+    fn into_component(self) -> u32 {
+        let [low, high] = self.to_le_bytes();
+        u32::from_le_bytes([low, high, low, high])
+    }
+}
+
+impl IntoPixelComponent<f16> for u16 {
+    fn into_component(self) -> f16 {
+        f16::from_f32(self.into_component())
+    }
+}
+
 // i32
 
 impl IntoPixelComponent<u8> for i32 {
@@ -437,6 +645,17 @@ impl IntoPixelComponent<f32> for i32 {
     }
 }
 
+impl IntoPixelComponent<f64> for i32 {
+    fn into_component(self) -> f64 {
+        // Negative values are mapped into [-1.0, 0.0)
+        if self < 0 {
+            (self as f64) / -(i32::MIN as f64)
+        } else {
+            (self as f64) / i32::MAX as f64
+        }
+    }
+}
+
 // f32
 
 impl IntoPixelComponent<u8> for f32 {
@@ -461,3 +680,92 @@ impl IntoPixelComponent<i32> for f32 {
         (self.clamp(-1., 1.) * max).round() as i32
     }
 }
+
+impl IntoPixelComponent<u32> for f32 {
+    fn into_component(self) -> u32 {
+        (self as f64).into_component()
+    }
+}
+
+impl IntoPixelComponent<f16> for f32 {
+    fn into_component(self) -> f16 {
+        f16::from_f32(self)
+    }
+}
+
+impl IntoPixelComponent<f64> for f32 {
+    fn into_component(self) -> f64 {
+        self as f64
+    }
+}
+
+// f16
+
+impl IntoPixelComponent<u8> for f16 {
+    fn into_component(self) -> u8 {
+        self.to_f32().into_component()
+    }
+}
+
+impl IntoPixelComponent<u16> for f16 {
+    fn into_component(self) -> u16 {
+        self.to_f32().into_component()
+    }
+}
+
+impl IntoPixelComponent<f32> for f16 {
+    fn into_component(self) -> f32 {
+        self.to_f32()
+    }
+}
+
+// f64
+
+impl IntoPixelComponent<i32> for f64 {
+    fn into_component(self) -> i32 {
+        let max = if self < 0. {
+            -(i32::MIN as f64)
+        } else {
+            i32::MAX as f64
+        };
+        (self.clamp(-1., 1.) * max).round() as i32
+    }
+}
+
+impl IntoPixelComponent<f32> for f64 {
+    fn into_component(self) -> f32 {
+        self as f32
+    }
+}
+
+impl IntoPixelComponent<u32> for f64 {
+    fn into_component(self) -> u32 {
+        (self.clamp(0., 1.) * u32::MAX as f64).round() as u32
+    }
+}
+
+// u32
+
+impl IntoPixelComponent<u8> for u32 {
+    fn into_component(self) -> u8 {
+        self.to_le_bytes()[3]
+    }
+}
+
+impl IntoPixelComponent<u16> for u32 {
+    fn into_component(self) -> u16 {
+        (self >> 16) as u16
+    }
+}
+
+impl IntoPixelComponent<f32> for u32 {
+    fn into_component(self) -> f32 {
+        (self as f64 / u32::MAX as f64) as f32
+    }
+}
+
+impl IntoPixelComponent<f64> for u32 {
+    fn into_component(self) -> f64 {
+        self as f64 / u32::MAX as f64
+    }
+}
@@ -0,0 +1,257 @@
+use crate::compat::*;
+use crate::images::ImageRef;
+use crate::{Orientation, PixelType, ResizeOptions, SrcCropping};
+
+/// Rounding of sizes of pyramid levels if a size of the previous
+/// level is not divisible by 2.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PyramidRounding {
+    /// Size of the next level is `floor(size / 2)`,
+    /// like OpenGL and Direct3D mipmaps.
+    #[default]
+    Floor,
+    /// Size of the next level is `ceil(size / 2)`.
+    Ceil,
+}
+
+impl PyramidRounding {
+    fn half(&self, size: u32) -> u32 {
+        match self {
+            Self::Floor => size / 2,
+            Self::Ceil => size.div_ceil(2),
+        }
+    }
+}
+
+/// Options for building an image pyramid.
+#[derive(Debug, Clone)]
+pub struct PyramidOptions {
+    /// Options used to resize every level of the pyramid.
+    /// The first level has the size of the source image after applying
+    /// the orientation and the crop box from these options.
+    /// Padding and fitting into the destination image are ignored.
+    ///
+    /// Default: `ResizeOptions::default()`
+    pub resize_options: ResizeOptions,
+    /// Default: `PyramidRounding::Floor`
+    pub rounding: PyramidRounding,
+    /// Minimal width of levels.
+    /// Widths of levels are never less than it (or the source width).
+    ///
+    /// Default: `1`
+    pub min_width: u32,
+    /// Minimal height of levels.
+    /// Heights of levels are never less than it (or the source height).
+    ///
+    /// Default: `1`
+    pub min_height: u32,
+    /// Resize every level from the source image instead of the previous level.
+    /// It is slower but avoids accumulation of errors of resizing.
+    ///
+    /// Default: `false`
+    pub from_original: bool,
+}
+
+impl Default for PyramidOptions {
+    fn default() -> Self {
+        Self {
+            resize_options: ResizeOptions::default(),
+            rounding: PyramidRounding::Floor,
+            min_width: 1,
+            min_height: 1,
+            from_original: false,
+        }
+    }
+}
+
+impl PyramidOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set options used to resize every level of the pyramid.
+    pub fn resize_options(&self, resize_options: &ResizeOptions) -> Self {
+        let mut options = self.clone();
+        options.resize_options = resize_options.clone();
+        options
+    }
+
+    /// Set rounding of sizes of levels.
+    pub fn rounding(&self, rounding: PyramidRounding) -> Self {
+        let mut options = self.clone();
+        options.rounding = rounding;
+        options
+    }
+
+    /// Set minimal size of levels.
+    pub fn min_size(&self, min_width: u32, min_height: u32) -> Self {
+        let mut options = self.clone();
+        options.min_width = min_width;
+        options.min_height = min_height;
+        options
+    }
+
+    /// Enable or disable resizing of every level from the source image.
+    pub fn from_original(&self, v: bool) -> Self {
+        let mut options = self.clone();
+        options.from_original = v;
+        options
+    }
+
+    /// Returns options used to resize a level from the source image
+    /// or from the previous level.
+    pub(crate) fn level_resize_options(&self, from_original: bool) -> ResizeOptions {
+        let mut options = self.resize_options.clone();
+        options.padding = None;
+        if from_original {
+            if let SrcCropping::FitIntoDestination(_) = options.cropping {
+                options.cropping = SrcCropping::None;
+            }
+        } else {
+            // Previous levels already have the alpha mode
+            // of the destination image and are oriented and cropped.
+            options.src_alpha_mode = options.dst_alpha_mode;
+            options.orientation = Orientation::Normal;
+            options.cropping = SrcCropping::None;
+        }
+        options
+    }
+
+    /// Returns the size of the first level of a pyramid
+    /// for the source image with the given size.
+    fn first_level_size(&self, src_width: u32, src_height: u32) -> (u32, u32) {
+        let options = &self.resize_options;
+        match options.cropping {
+            SrcCropping::Crop(crop_box) => (
+                crop_box.width.round().max(0.) as u32,
+                crop_box.height.round().max(0.) as u32,
+            ),
+            _ => options.orientation.oriented_size(src_width, src_height),
+        }
+    }
+
+    /// Returns sizes of all levels of a pyramid for the source image
+    /// with the given size.
+    fn level_sizes(&self, src_width: u32, src_height: u32) -> Vec<(u32, u32)> {
+        let (width, height) = self.first_level_size(src_width, src_height);
+        let min_width = self.min_width.max(1).min(width);
+        let min_height = self.min_height.max(1).min(height);
+        let mut sizes = vec![(width, height)];
+        loop {
+            let (width, height) = sizes[sizes.len() - 1];
+            let next_size = (
+                self.rounding.half(width).max(min_width),
+                self.rounding.half(height).max(min_height),
+            );
+            if next_size == (width, height) {
+                break;
+            }
+            sizes.push(next_size);
+        }
+        sizes
+    }
+}
+
+/// Level of an image pyramid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PyramidLevel {
+    pub width: u32,
+    pub height: u32,
+    /// Offset of pixels of the level in the buffer of the pyramid (in bytes).
+    pub offset: usize,
+}
+
+/// Image pyramid (mipmap chain) stored in a single contiguous buffer.
+///
+/// The first level is a copy of the source image, every next level
+/// is about two times smaller than the previous one.
+/// Levels are stored one after another without gaps.
+#[derive(Debug, Clone)]
+pub struct ImagePyramid {
+    pub(crate) pixel_type: PixelType,
+    pub(crate) levels: Vec<PyramidLevel>,
+    // Vector of `u64` is used to align the buffer to the alignment
+    // of pixels of any type.
+    buffer: Vec<u64>,
+    // Size of the buffer in bytes.
+    size: usize,
+}
+
+impl ImagePyramid {
+    pub(crate) fn empty(pixel_type: PixelType) -> Self {
+        Self {
+            pixel_type,
+            levels: Vec::new(),
+            buffer: Vec::new(),
+            size: 0,
+        }
+    }
+
+    /// Sets levels for the source image with the given size
+    /// and resizes the buffer to fit all of them.
+    /// The size of the first level depends on the orientation
+    /// and cropping options.
+    pub(crate) fn reset(
+        &mut self,
+        pixel_type: PixelType,
+        width: u32,
+        height: u32,
+        options: &PyramidOptions,
+    ) {
+        self.pixel_type = pixel_type;
+        self.levels.clear();
+        let mut offset = 0;
+        for (width, height) in options.level_sizes(width, height) {
+            self.levels.push(PyramidLevel {
+                width,
+                height,
+                offset,
+            });
+            offset += width as usize * height as usize * pixel_type.size();
+        }
+        self.buffer.resize(offset.div_ceil(size_of::<u64>()), 0);
+        self.size = offset;
+    }
+
+    #[inline]
+    pub fn pixel_type(&self) -> PixelType {
+        self.pixel_type
+    }
+
+    /// Levels of the pyramid, from the biggest to the smallest one.
+    #[inline]
+    pub fn levels(&self) -> &[PyramidLevel] {
+        &self.levels
+    }
+
+    /// Returns the image of the level with the given index.
+    /// Returns `None` only if the index is out of range.
+    pub fn level(&self, index: usize) -> Option<ImageRef<'_>> {
+        let level = self.levels.get(index)?;
+        let size = level.width as usize * level.height as usize * self.pixel_type.size();
+        let buffer = &self.buffer()[level.offset..level.offset + size];
+        // Offsets of levels are multiples of the pixel size and the buffer
+        // is aligned, so the buffer of every level is valid.
+        Some(ImageRef::new(level.width, level.height, buffer, self.pixel_type).unwrap())
+    }
+
+    /// Buffer with pixels data of all levels.
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        let (_, buffer, _) = unsafe { self.buffer.align_to::<u8>() };
+        &buffer[..self.size]
+    }
+
+    #[inline]
+    pub(crate) fn buffer_mut(&mut self) -> &mut [u8] {
+        let (_, buffer, _) = unsafe { self.buffer.align_to_mut::<u8>() };
+        &mut buffer[..self.size]
+    }
+
+    /// Returns a copy of the buffer with pixels data of all levels.
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.buffer().into()
+    }
+}
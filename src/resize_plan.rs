@@ -0,0 +1,1116 @@
+use crate::color::color_space::linear_pixel_type;
+use crate::compat::*;
+use crate::convolution::{self, Coefficients, EdgeMode, MAX_BLOCK_SIZE};
+use crate::mul_div::{is_supported_alpha_position, is_supported_pixel_type};
+use crate::{
+    AlphaMode, AlphaPosition, AxisFilter, ColorSpace, CropBox, FillColor, ImageError, ImageViewMut,
+    Orientation, Padding, PixelTrait, PixelType, ResizeAlg, ResizeError, ResizeOptions,
+};
+
+/// Precomputed parameters of resizing images with given dimensions
+/// and pixel type.
+///
+/// The plan holds the convolution coefficients for both axes, the order
+/// of convolution passes and sizes of temporary images.
+/// It can be used to resize many images with the same dimensions by the
+/// method [Resizer::resize_with_plan](crate::Resizer::resize_with_plan)
+/// without calculating all these parameters again for every image.
+///
+/// # Examples
+///
+/// ```
+/// use fast_image_resize::images::Image;
+/// use fast_image_resize::{PixelType, ResizeOptions, ResizePlan, Resizer};
+///
+/// let options = ResizeOptions::new().crop(10., 10., 620., 460.);
+/// let plan = ResizePlan::new(PixelType::U8x4, 640, 480, 320, 240, &options).unwrap();
+///
+/// let mut resizer = Resizer::new();
+/// let mut dst_image = Image::new(320, 240, PixelType::U8x4);
+/// for _ in 0..3 {
+///     let src_image = Image::new(640, 480, PixelType::U8x4);
+///     resizer
+///         .resize_with_plan(&plan, &src_image, &mut dst_image)
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResizePlan {
+    pixel_type: PixelType,
+    src_width: u32,
+    src_height: u32,
+    dst_width: u32,
+    dst_height: u32,
+    pub(crate) alpha_position: AlphaPosition,
+    pub(crate) padding: Option<PaddingPlan>,
+    pub(crate) orientation: Option<OrientationStep>,
+    pub(crate) steps: ResizeSteps,
+}
+
+impl ResizePlan {
+    /// Creates a plan to resize images with pixels of the given type from
+    /// size `src_width`x`src_height` to size `dst_width`x`dst_height`.
+    pub fn new<'o>(
+        pixel_type: PixelType,
+        src_width: u32,
+        src_height: u32,
+        dst_width: u32,
+        dst_height: u32,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<Self, ResizeError> {
+        let default_options = ResizeOptions::default();
+        let options = options.into().unwrap_or(&default_options);
+        check_alpha_position(pixel_type, options)?;
+        let alpha_position = options.alpha_position;
+
+        let orientation = options.orientation;
+        if orientation == Orientation::Normal {
+            let (padding, steps) = padding_and_steps(
+                pixel_type, src_width, src_height, dst_width, dst_height, options,
+            )?;
+            return Ok(Self {
+                pixel_type,
+                src_width,
+                src_height,
+                dst_width,
+                dst_height,
+                alpha_position,
+                padding,
+                orientation: None,
+                steps,
+            });
+        }
+
+        // Cropping and padding are related to the oriented source image.
+        let (oriented_width, oriented_height) = orientation.oriented_size(src_width, src_height);
+        let crop_box = options.get_crop_box(oriented_width, oriented_height, dst_width, dst_height);
+        let padding = match options.padding {
+            Some(padding_options) => {
+                if padding_options.fill_color.pixel_type() != pixel_type {
+                    return Err(ResizeError::PixelTypesAreDifferent);
+                }
+                Some(PaddingPlan::new(
+                    padding_options,
+                    crop_box,
+                    dst_width,
+                    dst_height,
+                ))
+            }
+            None => None,
+        };
+        let (inner_width, inner_height) = padding
+            .map(|p| (p.width, p.height))
+            .unwrap_or((dst_width, dst_height));
+
+        // The orientation is applied to the smaller image: the source image
+        // is oriented before resizing, or the resized image is oriented
+        // while it is copied into the destination image.
+        // Positions of pixels selected by the nearest-neighbor algorithm
+        // are not symmetric, so the source image is always oriented
+        // before resizing with it.
+        let uses_nearest = matches!(
+            options.algorithm,
+            ResizeAlg::Nearest | ResizeAlg::SuperSampling(..)
+        );
+        let src_size = src_width as u64 * src_height as u64;
+        let (orientation, steps) =
+            if uses_nearest || src_size <= inner_width as u64 * inner_height as u64 {
+                let steps = ResizeSteps::new(
+                    pixel_type,
+                    oriented_width,
+                    oriented_height,
+                    inner_width,
+                    inner_height,
+                    &options.crop(crop_box.left, crop_box.top, crop_box.width, crop_box.height),
+                )?;
+                (OrientationStep::BeforeResizing(orientation), steps)
+            } else {
+                let src_crop_box = orientation.src_crop_box(crop_box, src_width, src_height);
+                let mut inner_options = options.crop(
+                    src_crop_box.left,
+                    src_crop_box.top,
+                    src_crop_box.width,
+                    src_crop_box.height,
+                );
+                if orientation.swaps_dimensions() {
+                    if let ResizeAlg::PerAxisConvolution {
+                        horizontal,
+                        vertical,
+                    } = &mut inner_options.algorithm
+                    {
+                        core::mem::swap(horizontal, vertical);
+                    }
+                }
+                let (tmp_width, tmp_height) = orientation.oriented_size(inner_width, inner_height);
+                let steps = ResizeSteps::new(
+                    pixel_type,
+                    src_width,
+                    src_height,
+                    tmp_width,
+                    tmp_height,
+                    &inner_options,
+                )?;
+                let orientation = OrientationStep::AfterResizing {
+                    orientation,
+                    tmp_width,
+                    tmp_height,
+                };
+                (orientation, steps)
+            };
+        Ok(Self {
+            pixel_type,
+            src_width,
+            src_height,
+            dst_width,
+            dst_height,
+            alpha_position,
+            padding,
+            orientation: Some(orientation),
+            steps,
+        })
+    }
+
+    #[inline]
+    pub fn pixel_type(&self) -> PixelType {
+        self.pixel_type
+    }
+
+    #[inline]
+    pub fn src_width(&self) -> u32 {
+        self.src_width
+    }
+
+    #[inline]
+    pub fn src_height(&self) -> u32 {
+        self.src_height
+    }
+
+    #[inline]
+    pub fn dst_width(&self) -> u32 {
+        self.dst_width
+    }
+
+    #[inline]
+    pub fn dst_height(&self) -> u32 {
+        self.dst_height
+    }
+
+    /// Returns `true` if images with the given pixel type and
+    /// dimensions may be resized with this plan.
+    pub(crate) fn is_suitable(
+        &self,
+        pixel_type: PixelType,
+        src_size: (u32, u32),
+        dst_size: (u32, u32),
+    ) -> bool {
+        self.pixel_type == pixel_type
+            && (self.src_width, self.src_height) == src_size
+            && (self.dst_width, self.dst_height) == dst_size
+    }
+}
+
+/// Returns an error if the alpha channel of images with the given pixel type
+/// can't be at the position specified in options.
+/// Pixel types without an alpha channel ignore this option.
+pub(crate) fn check_alpha_position(
+    pixel_type: PixelType,
+    options: &ResizeOptions,
+) -> Result<(), ResizeError> {
+    if is_supported_pixel_type(pixel_type)
+        && !is_supported_alpha_position(pixel_type, options.alpha_position)
+    {
+        return Err(ImageError::UnsupportedAlphaPosition.into());
+    }
+    Ok(())
+}
+
+/// Returns the plan of padding and steps of resizing
+/// the source image into the destination image.
+fn padding_and_steps(
+    pixel_type: PixelType,
+    src_width: u32,
+    src_height: u32,
+    dst_width: u32,
+    dst_height: u32,
+    options: &ResizeOptions,
+) -> Result<(Option<PaddingPlan>, ResizeSteps), ResizeError> {
+    let Some(padding_options) = options.padding else {
+        let steps = ResizeSteps::new(
+            pixel_type, src_width, src_height, dst_width, dst_height, options,
+        )?;
+        return Ok((None, steps));
+    };
+    if padding_options.fill_color.pixel_type() != pixel_type {
+        return Err(ResizeError::PixelTypesAreDifferent);
+    }
+    let crop_box = options.get_crop_box(src_width, src_height, dst_width, dst_height);
+    let padding_plan = PaddingPlan::new(padding_options, crop_box, dst_width, dst_height);
+    // The source image is resized into the inner part of
+    // the destination image.
+    let inner_options = options.crop(crop_box.left, crop_box.top, crop_box.width, crop_box.height);
+    let steps = ResizeSteps::new(
+        pixel_type,
+        src_width,
+        src_height,
+        padding_plan.width,
+        padding_plan.height,
+        &inner_options,
+    )?;
+    Ok((Some(padding_plan), steps))
+}
+
+/// Step of applying the orientation of the source image.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum OrientationStep {
+    /// The source image is oriented into a temporary image
+    /// that is resized into the destination image.
+    BeforeResizing(Orientation),
+    /// The source image is resized into a temporary image
+    /// that is oriented into the destination image.
+    AfterResizing {
+        orientation: Orientation,
+        tmp_width: u32,
+        tmp_height: u32,
+    },
+}
+
+/// Position of the resized image inside the destination image
+/// and the color of the area around it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PaddingPlan {
+    pub fill_color: FillColor,
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PaddingPlan {
+    pub fn new(padding: Padding, crop_box: CropBox, dst_width: u32, dst_height: u32) -> Self {
+        let mut width = 0;
+        let mut height = 0;
+        if crop_box.width > 0. && crop_box.height > 0. && dst_width > 0 && dst_height > 0 {
+            let scale =
+                (dst_width as f64 / crop_box.width).min(dst_height as f64 / crop_box.height);
+            width = ((crop_box.width * scale).round() as u32).clamp(1, dst_width);
+            height = ((crop_box.height * scale).round() as u32).clamp(1, dst_height);
+        }
+        let (center_x, center_y) = padding.centering;
+        let left = ((dst_width - width) as f64 * center_x.clamp(0., 1.)).round() as u32;
+        let top = ((dst_height - height) as f64 * center_y.clamp(0., 1.)).round() as u32;
+        Self {
+            fill_color: padding.fill_color,
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+
+    /// Fills the area of the destination image around the resized image.
+    pub fn fill_around<P: PixelTrait>(&self, dst_view: &mut impl ImageViewMut<Pixel = P>) {
+        let fill_color: P = self.fill_color.pixel().unwrap_or_default();
+        let left = self.left as usize;
+        let right = left + self.width as usize;
+        let inner_rows = self.top..self.top + self.height;
+        for (y, row) in (0u32..).zip(dst_view.iter_rows_mut(0)) {
+            if inner_rows.contains(&y) {
+                row[..left].fill(fill_color);
+                row[right..].fill(fill_color);
+            } else {
+                row.fill(fill_color);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ResizeSteps {
+    /// Nothing to do because some of the sizes is equal to zero.
+    Nothing,
+    /// Cropped source image has the same size as the destination image.
+    Copy(CropBox, AlphaConversion),
+    Nearest(NearestPlan, AlphaConversion),
+    Convolution(ConvolutionPlan),
+    SuperSampling {
+        nearest: NearestPlan,
+        tmp_width: u32,
+        tmp_height: u32,
+        convolution: ConvolutionPlan,
+    },
+    /// Reduction of the source image by integer factors into a temporary image
+    /// and the convolution of the temporary image into the destination image.
+    Reducing {
+        reduction: ConvolutionPlan,
+        tmp_width: u32,
+        tmp_height: u32,
+        convolution: ConvolutionPlan,
+    },
+}
+
+impl ResizeSteps {
+    fn new(
+        pixel_type: PixelType,
+        src_width: u32,
+        src_height: u32,
+        dst_width: u32,
+        dst_height: u32,
+        options: &ResizeOptions,
+    ) -> Result<Self, ResizeError> {
+        let crop_box = options.get_crop_box(src_width, src_height, dst_width, dst_height);
+        if crop_box.width == 0. || crop_box.height == 0. || dst_width == 0 || dst_height == 0 {
+            // Do nothing if any size of the source or destination image is equal to zero.
+            return Ok(Self::Nothing);
+        }
+
+        crop_box.check_bounds(src_width, src_height)?;
+
+        // Pixels are not mixed by copying and the Nearest algorithm,
+        // so only the alpha mode has to be converted.
+        let alpha_conversion = AlphaConversion::new(pixel_type, options);
+
+        if is_copy_possible(crop_box, dst_width, dst_height) {
+            return Ok(Self::Copy(crop_box, alpha_conversion));
+        }
+
+        let conv_plan = |horiz_kernel: &AxisKernel, vert_kernel: &AxisKernel| {
+            ConvolutionPlan::new(
+                pixel_type,
+                (src_width, src_height),
+                crop_box,
+                (dst_width, dst_height),
+                horiz_kernel,
+                vert_kernel,
+                options,
+            )
+        };
+
+        let steps = match &options.algorithm {
+            ResizeAlg::Nearest => Self::Nearest(
+                NearestPlan::new(src_width, crop_box, dst_width, dst_height),
+                alpha_conversion,
+            ),
+            ResizeAlg::Convolution(_)
+            | ResizeAlg::Interpolation(_)
+            | ResizeAlg::PerAxisConvolution { .. }
+            | ResizeAlg::Area => {
+                let (horiz_kernel, vert_kernel) =
+                    axis_kernels(&options.algorithm, crop_box, dst_width, dst_height)
+                        .expect("algorithm uses convolution");
+                Self::Convolution(conv_plan(&horiz_kernel, &vert_kernel)?)
+            }
+            ResizeAlg::IntegerBox => {
+                if integer_box_factors(crop_box, dst_width, dst_height).is_none() {
+                    return Err(ResizeError::NotIntegerScaleFactor);
+                }
+                Self::Convolution(conv_plan(&AxisKernel::Area, &AxisKernel::Area)?)
+            }
+            ResizeAlg::SuperSampling(filter_type, multiplicity) => {
+                let filter = AxisKernel::Filter(AxisFilter::convolution(filter_type.clone()));
+                let width_scale = crop_box.width / dst_width as f64;
+                let height_scale = crop_box.height / dst_height as f64;
+                // It makes sense to resize the image in two steps only if the image
+                // size is greater than the required size by multiplicity times.
+                let factor = width_scale.min(height_scale) / *multiplicity as f64;
+                if factor > 1.2 {
+                    // The first step is resizing the source image by the fastest algorithm.
+                    // The temporary image will be about ``multiplicity`` times larger
+                    // than required.
+                    let tmp_width = (crop_box.width / factor).round() as u32;
+                    let tmp_height = (crop_box.height / factor).round() as u32;
+                    let nearest = NearestPlan::new(src_width, crop_box, tmp_width, tmp_height);
+                    // The second step is resizing the temporary image with a convolution.
+                    let tmp_crop_box = CropBox {
+                        left: 0.,
+                        top: 0.,
+                        width: tmp_width as _,
+                        height: tmp_height as _,
+                    };
+                    let convolution = ConvolutionPlan::new(
+                        pixel_type,
+                        (tmp_width, tmp_height),
+                        tmp_crop_box,
+                        (dst_width, dst_height),
+                        &filter,
+                        &filter,
+                        options,
+                    )?;
+                    Self::SuperSampling {
+                        nearest,
+                        tmp_width,
+                        tmp_height,
+                        convolution,
+                    }
+                } else {
+                    // There is no point in doing the resizing in two steps.
+                    // We immediately resize the original image with a convolution.
+                    Self::Convolution(conv_plan(&filter, &filter)?)
+                }
+            }
+            ResizeAlg::ReducingGap(filter_type, reducing_gap) => {
+                if !(reducing_gap.is_finite() && *reducing_gap >= 1.) {
+                    return Err(ResizeError::InvalidReducingGap);
+                }
+                let filter = AxisKernel::Filter(AxisFilter::convolution(filter_type.clone()));
+                check_kernels(&filter, &filter)?;
+                check_kernel_scale(options.kernel_scale)?;
+                let scale_x = crop_box.width / dst_width as f64;
+                let scale_y = crop_box.height / dst_height as f64;
+                let factor_x = ((scale_x / reducing_gap) as u32).max(1);
+                let factor_y = ((scale_y / reducing_gap) as u32).max(1);
+                let block_size = factor_x as u64 * factor_y as u64;
+                if (factor_x > 1 || factor_y > 1) && block_size <= MAX_BLOCK_SIZE as u64 {
+                    // The first step is reducing the source image by integer factors
+                    // like `Image.reduce()` in Pillow. Pixels around the crop box
+                    // that are used by the filter are reduced too.
+                    let filter_support = convolution::get_filter_support(filter_type);
+                    let support = (filter_support * options.kernel_scale - 0.5).max(0.);
+                    let (support_x, support_y) = (support * scale_x, support * scale_y);
+                    let left = (crop_box.left - support_x).floor().max(0.) as u32;
+                    let top = (crop_box.top - support_y).floor().max(0.) as u32;
+                    let right =
+                        ((crop_box.left + crop_box.width + support_x).ceil() as u32).min(src_width);
+                    let bottom = ((crop_box.top + crop_box.height + support_y).ceil() as u32)
+                        .min(src_height);
+                    let (width, height) = (right - left, bottom - top);
+                    let factors = (factor_x, factor_y);
+
+                    // The crop box in coordinates of the reduced image.
+                    let tmp_crop_box = CropBox {
+                        left: (crop_box.left - left as f64) / factor_x as f64,
+                        top: (crop_box.top - top as f64) / factor_y as f64,
+                        width: crop_box.width / factor_x as f64,
+                        height: crop_box.height / factor_y as f64,
+                    };
+                    if is_copy_possible(tmp_crop_box, dst_width, dst_height) {
+                        // The reduced crop box is the destination image.
+                        let region = (
+                            crop_box.left as u32,
+                            crop_box.top as u32,
+                            dst_width * factor_x,
+                            dst_height * factor_y,
+                        );
+                        return Ok(Self::Convolution(ConvolutionPlan::box_reduction(
+                            pixel_type, region, factors, options,
+                        )?));
+                    }
+                    // Colors of the temporary image stay multiplied by alpha
+                    // to avoid excess conversions between the steps.
+                    let reduction = ConvolutionPlan::box_reduction(
+                        pixel_type,
+                        (left, top, width, height),
+                        factors,
+                        &options.alpha_modes(options.src_alpha_mode, AlphaMode::Premultiplied),
+                    )?;
+                    // The second step is resizing the temporary image
+                    // with the required filter.
+                    let (tmp_width, tmp_height) =
+                        (width.div_ceil(factor_x), height.div_ceil(factor_y));
+                    let convolution = ConvolutionPlan::new(
+                        pixel_type,
+                        (tmp_width, tmp_height),
+                        tmp_crop_box,
+                        (dst_width, dst_height),
+                        &filter,
+                        &filter,
+                        &options.alpha_modes(AlphaMode::Premultiplied, options.dst_alpha_mode),
+                    )?;
+                    Self::Reducing {
+                        reduction,
+                        tmp_width,
+                        tmp_height,
+                        convolution,
+                    }
+                } else {
+                    Self::Convolution(conv_plan(&filter, &filter)?)
+                }
+            }
+        };
+        Ok(steps)
+    }
+}
+
+/// Returns `true` if the size of the destination image is equal to
+/// the size of the cropped source image and the crop box
+/// hasn't a fractional part.
+pub(crate) fn is_copy_possible(crop_box: CropBox, dst_width: u32, dst_height: u32) -> bool {
+    crop_box.left == crop_box.left.round()
+        && crop_box.top == crop_box.top.round()
+        && crop_box.width == crop_box.width.round()
+        && crop_box.height == crop_box.height.round()
+        && dst_width == crop_box.width as u32
+        && dst_height == crop_box.height as u32
+}
+
+/// Precomputed positions of source pixels for the `Nearest` algorithm.
+#[derive(Debug, Clone)]
+pub(crate) struct NearestPlan {
+    pub x_in_tab: Vec<usize>,
+    pub y_in_start: f64,
+    pub y_scale: f64,
+    pub dst_height: u32,
+}
+
+impl NearestPlan {
+    pub(crate) fn new(src_width: u32, crop_box: CropBox, dst_width: u32, dst_height: u32) -> Self {
+        if dst_width == 0 || dst_height == 0 || crop_box.width <= 0. || crop_box.height <= 0. {
+            return Self {
+                x_in_tab: Vec::new(),
+                y_in_start: 0.,
+                y_scale: 1.,
+                dst_height: 0,
+            };
+        }
+        let x_scale = crop_box.width / dst_width as f64;
+        let y_scale = crop_box.height / dst_height as f64;
+
+        // Pretabulate horizontal pixel positions
+        let x_in_start = crop_box.left + x_scale * 0.5;
+        let max_src_x = src_width as usize;
+        let x_in_tab: Vec<usize> = (0..dst_width)
+            .map(|x| ((x_in_start + x_scale * x as f64) as usize).min(max_src_x))
+            .collect();
+
+        Self {
+            x_in_tab,
+            y_in_start: crop_box.top + y_scale * 0.5,
+            y_scale,
+            dst_height,
+        }
+    }
+}
+
+/// Conversion of colors of pixels from the alpha mode of the source image
+/// into the alpha mode of the destination image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AlphaConversion {
+    None,
+    Multiply,
+    Divide,
+}
+
+impl AlphaConversion {
+    pub fn new(pixel_type: PixelType, options: &ResizeOptions) -> Self {
+        if !options.mul_div_alpha || !is_supported_pixel_type(pixel_type) {
+            return Self::None;
+        }
+        match (options.src_alpha_mode, options.dst_alpha_mode) {
+            (AlphaMode::Straight, AlphaMode::Premultiplied) => Self::Multiply,
+            (AlphaMode::Premultiplied, AlphaMode::Straight) => Self::Divide,
+            _ => Self::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ConvolutionPlan {
+    pub mul_div_alpha: bool,
+    pub src_alpha_mode: AlphaMode,
+    pub dst_alpha_mode: AlphaMode,
+    /// If the color space is not linear, the convolution is performed
+    /// over an image converted into linear color space.
+    pub color_space: ColorSpace,
+    /// Borders with wrapped pixels that are added around
+    /// the source image with the `Wrap` edge mode.
+    pub wrap_borders: Option<WrapBorders>,
+    pub passes: ConvolutionPasses,
+}
+
+/// Sizes of borders with wrapped pixels around the source image.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WrapBorders {
+    pub horizontal: u32,
+    pub vertical: u32,
+}
+
+impl WrapBorders {
+    fn new(
+        crop_box: CropBox,
+        (dst_width, dst_height): (u32, u32),
+        horiz_kernel: &AxisKernel,
+        vert_kernel: &AxisKernel,
+        kernel_scale: f64,
+    ) -> Self {
+        Self {
+            horizontal: horiz_kernel.radius(
+                crop_box.left,
+                crop_box.left + crop_box.width,
+                dst_width,
+                kernel_scale,
+            ),
+            vertical: vert_kernel.radius(
+                crop_box.top,
+                crop_box.top + crop_box.height,
+                dst_height,
+                kernel_scale,
+            ),
+        }
+    }
+}
+
+/// Kernel of convolution along one axis of an image.
+#[derive(Debug, Clone)]
+pub(crate) enum AxisKernel {
+    Filter(AxisFilter),
+    /// Weights are equal to parts of areas of source pixels
+    /// covered by a destination pixel.
+    Area,
+    /// Linear interpolation used by OpenCV for the `INTER_AREA`
+    /// interpolation if the image is upscaled by any axis.
+    AreaInterpolation,
+}
+
+impl AxisKernel {
+    fn is_valid(&self) -> bool {
+        match self {
+            Self::Filter(filter) => filter.filter_type.is_valid(),
+            _ => true,
+        }
+    }
+
+    /// Returns the radius of the kernel rounded up to the
+    /// number of source pixels with one extra pixel.
+    fn radius(&self, in0: f64, in1: f64, out_size: u32, kernel_scale: f64) -> u32 {
+        let radius = match self {
+            Self::Filter(filter) => {
+                let filter_support = convolution::get_filter_support(&filter.filter_type);
+                convolution::kernel_radius(
+                    in0,
+                    in1,
+                    out_size,
+                    filter_support,
+                    filter.adaptive_kernel_size,
+                    kernel_scale,
+                )
+            }
+            Self::Area | Self::AreaInterpolation => {
+                convolution::kernel_radius(in0, in1, out_size, 0.5, true, 1.)
+            }
+        };
+        radius.ceil() as u32 + 1
+    }
+
+    fn coefficients(
+        &self,
+        in_size: u32,
+        in0: f64,
+        in1: f64,
+        out_size: u32,
+        kernel_scale: f64,
+        edge_mode: EdgeMode,
+    ) -> Coefficients {
+        match self {
+            Self::Filter(filter) => {
+                let (filter_fn, filter_support) =
+                    convolution::get_filter_func(filter.filter_type.clone());
+                convolution::precompute_coefficients(
+                    in_size,
+                    in0,
+                    in1,
+                    out_size,
+                    filter_fn,
+                    filter_support,
+                    filter.adaptive_kernel_size,
+                    kernel_scale,
+                    edge_mode,
+                )
+            }
+            Self::Area => convolution::precompute_area_coefficients(in_size, in0, in1, out_size),
+            Self::AreaInterpolation => {
+                convolution::precompute_area_interpolation_coefficients(in_size, in0, in1, out_size)
+            }
+        }
+    }
+}
+
+/// Returns kernels of the horizontal and vertical passes of convolution
+/// or `None` if the algorithm doesn't use convolution.
+pub(crate) fn axis_kernels(
+    algorithm: &ResizeAlg,
+    crop_box: CropBox,
+    dst_width: u32,
+    dst_height: u32,
+) -> Option<(AxisKernel, AxisKernel)> {
+    let filter = |filter: AxisFilter| {
+        Some((
+            AxisKernel::Filter(filter.clone()),
+            AxisKernel::Filter(filter),
+        ))
+    };
+    match algorithm {
+        ResizeAlg::Nearest => None,
+        ResizeAlg::Convolution(filter_type)
+        | ResizeAlg::SuperSampling(filter_type, _)
+        | ResizeAlg::ReducingGap(filter_type, _) => {
+            filter(AxisFilter::convolution(filter_type.clone()))
+        }
+        ResizeAlg::Interpolation(filter_type) => {
+            filter(AxisFilter::interpolation(filter_type.clone()))
+        }
+        ResizeAlg::PerAxisConvolution {
+            horizontal,
+            vertical,
+        } => Some((
+            AxisKernel::Filter(horizontal.clone()),
+            AxisKernel::Filter(vertical.clone()),
+        )),
+        ResizeAlg::IntegerBox => Some((AxisKernel::Area, AxisKernel::Area)),
+        ResizeAlg::Area => {
+            // Like OpenCV, areas of pixels are used only if
+            // the image is downscaled by both axes.
+            let kernel =
+                if crop_box.width >= dst_width as f64 && crop_box.height >= dst_height as f64 {
+                    AxisKernel::Area
+                } else {
+                    AxisKernel::AreaInterpolation
+                };
+            Some((kernel.clone(), kernel))
+        }
+    }
+}
+
+/// Returns factors of downscaling by axes if the cropped source image
+/// consists of whole blocks of source pixels for every destination pixel.
+pub(crate) fn integer_box_factors(
+    crop_box: CropBox,
+    dst_width: u32,
+    dst_height: u32,
+) -> Option<(u32, u32)> {
+    let is_integer = |v: f64| v == v.round();
+    if dst_width == 0
+        || dst_height == 0
+        || !is_integer(crop_box.left)
+        || !is_integer(crop_box.top)
+        || !is_integer(crop_box.width)
+        || !is_integer(crop_box.height)
+    {
+        return None;
+    }
+    let (width, height) = (crop_box.width as u32, crop_box.height as u32);
+    if width % dst_width != 0 || height % dst_height != 0 {
+        return None;
+    }
+    let factors = (width / dst_width, height / dst_height);
+    let block_size = factors.0 as u64 * factors.1 as u64;
+    (block_size > 0 && block_size <= MAX_BLOCK_SIZE as u64).then_some(factors)
+}
+
+/// Returns factors of the integer box filter if it gives the same
+/// result as the convolution with the given kernels.
+fn integer_box_of_kernels(
+    crop_box: CropBox,
+    (dst_width, dst_height): (u32, u32),
+    horiz_kernel: &AxisKernel,
+    vert_kernel: &AxisKernel,
+) -> Option<(u32, u32)> {
+    match (horiz_kernel, vert_kernel) {
+        (AxisKernel::Area, AxisKernel::Area) => {
+            integer_box_factors(crop_box, dst_width, dst_height)
+        }
+        _ => None,
+    }
+}
+
+/// Passes of convolution that have to be done to resize an image.
+#[derive(Debug, Clone)]
+pub(crate) enum ConvolutionPasses {
+    None,
+    Horizontal {
+        offset: u32,
+        coeffs: Coefficients,
+    },
+    Vertical {
+        offset: u32,
+        coeffs: Coefficients,
+    },
+    /// The horizontal pass into a temporary image
+    /// and the vertical pass into the destination image.
+    HorizontalFirst {
+        offset: u32,
+        horiz_coeffs: Coefficients,
+        vert_coeffs: Coefficients,
+        temp_width: u32,
+        temp_height: u32,
+    },
+    /// The vertical pass into a temporary image
+    /// and the horizontal pass into the destination image.
+    VerticalFirst {
+        offset: u32,
+        vert_coeffs: Coefficients,
+        horiz_coeffs: Coefficients,
+        temp_width: u32,
+        temp_height: u32,
+    },
+    /// Averaging of blocks of `factor_x`x`factor_y` source pixels
+    /// in the region of `width`x`height` pixels starting from
+    /// the pixel (`left`, `top`). Blocks at the right and bottom edges
+    /// are partial if sizes of the region are not multiples of factors.
+    IntegerBox {
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+        factor_x: u32,
+        factor_y: u32,
+    },
+}
+
+impl ConvolutionPlan {
+    fn new(
+        pixel_type: PixelType,
+        src_size: (u32, u32),
+        crop_box: CropBox,
+        dst_size: (u32, u32),
+        horiz_kernel: &AxisKernel,
+        vert_kernel: &AxisKernel,
+        options: &ResizeOptions,
+    ) -> Result<Self, ResizeError> {
+        check_kernels(horiz_kernel, vert_kernel)?;
+        check_kernel_scale(options.kernel_scale)?;
+        let color_space = options.color_space;
+        let pixel_type = match color_space {
+            ColorSpace::Linear => pixel_type,
+            _ => linear_pixel_type(pixel_type).ok_or(ImageError::UnsupportedPixelType)?,
+        };
+        let mul_div_alpha = options.mul_div_alpha && is_supported_pixel_type(pixel_type);
+
+        let mut src_size = src_size;
+        let mut crop_box = crop_box;
+        let mut edge_mode = options.edge_mode;
+        let mut wrap_borders = None;
+        let is_integer_box =
+            integer_box_of_kernels(crop_box, dst_size, horiz_kernel, vert_kernel).is_some();
+        // Blocks of pixels averaged by the integer box filter
+        // never cross edges of the image.
+        if edge_mode == EdgeMode::Wrap && !is_integer_box {
+            let borders = WrapBorders::new(
+                crop_box,
+                dst_size,
+                horiz_kernel,
+                vert_kernel,
+                options.kernel_scale,
+            );
+            // Convolution is performed over the source image with borders,
+            // so the kernel never crosses edges of the image.
+            src_size.0 += borders.horizontal * 2;
+            src_size.1 += borders.vertical * 2;
+            crop_box.left += borders.horizontal as f64;
+            crop_box.top += borders.vertical as f64;
+            edge_mode = EdgeMode::Renormalize;
+            wrap_borders = Some(borders);
+        }
+
+        let passes = ConvolutionPasses::new(
+            pixel_type,
+            src_size,
+            crop_box,
+            dst_size,
+            horiz_kernel,
+            vert_kernel,
+            options.kernel_scale,
+            edge_mode,
+        );
+        Ok(Self {
+            mul_div_alpha,
+            src_alpha_mode: options.src_alpha_mode,
+            dst_alpha_mode: options.dst_alpha_mode,
+            color_space,
+            wrap_borders,
+            passes,
+        })
+    }
+
+    /// Creates a plan of reducing the region of `width`x`height` source pixels,
+    /// starting from the pixel (`left`, `top`), by integer factors.
+    fn box_reduction(
+        pixel_type: PixelType,
+        (left, top, width, height): (u32, u32, u32, u32),
+        (factor_x, factor_y): (u32, u32),
+        options: &ResizeOptions,
+    ) -> Result<Self, ResizeError> {
+        let color_space = options.color_space;
+        let pixel_type = match color_space {
+            ColorSpace::Linear => pixel_type,
+            _ => linear_pixel_type(pixel_type).ok_or(ImageError::UnsupportedPixelType)?,
+        };
+        Ok(Self {
+            mul_div_alpha: options.mul_div_alpha && is_supported_pixel_type(pixel_type),
+            src_alpha_mode: options.src_alpha_mode,
+            dst_alpha_mode: options.dst_alpha_mode,
+            color_space,
+            // Blocks of pixels never cross edges of the image.
+            wrap_borders: None,
+            passes: ConvolutionPasses::IntegerBox {
+                left,
+                top,
+                width,
+                height,
+                factor_x,
+                factor_y,
+            },
+        })
+    }
+}
+
+impl ConvolutionPasses {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        pixel_type: PixelType,
+        (src_width, src_height): (u32, u32),
+        crop_box: CropBox,
+        (dst_width, dst_height): (u32, u32),
+        horiz_kernel: &AxisKernel,
+        vert_kernel: &AxisKernel,
+        kernel_scale: f64,
+        edge_mode: EdgeMode,
+    ) -> Self {
+        if dst_width == 0 || dst_height == 0 || crop_box.width <= 0. || crop_box.height <= 0. {
+            return Self::None;
+        }
+
+        if let Some((factor_x, factor_y)) =
+            integer_box_of_kernels(crop_box, (dst_width, dst_height), horiz_kernel, vert_kernel)
+        {
+            return Self::IntegerBox {
+                left: crop_box.left as u32,
+                top: crop_box.top as u32,
+                width: dst_width * factor_x,
+                height: dst_height * factor_y,
+                factor_x,
+                factor_y,
+            };
+        }
+
+        let (horiz_coeffs, vert_coeffs) = precompute_passes_coefficients(
+            (src_width, src_height),
+            crop_box,
+            (dst_width, dst_height),
+            horiz_kernel,
+            vert_kernel,
+            kernel_scale,
+            edge_mode,
+        );
+
+        match (horiz_coeffs, vert_coeffs) {
+            (Some(mut horiz_coeffs), Some(vert_coeffs)) if pixel_type.components_is_u8() => {
+                // For u8-based images, it is faster to do the vertical pass first
+                // instead of the horizontal.
+                let x_first = horiz_coeffs.bounds[0].start;
+                // Last used col in the source image
+                let last_x_bound = horiz_coeffs.bounds.last().unwrap();
+                let x_last = last_x_bound.start + last_x_bound.size;
+
+                // Shift bounds for the horizontal pass
+                horiz_coeffs
+                    .bounds
+                    .iter_mut()
+                    .for_each(|b| b.start -= x_first);
+
+                Self::VerticalFirst {
+                    offset: x_first,
+                    vert_coeffs,
+                    horiz_coeffs,
+                    temp_width: x_last - x_first,
+                    temp_height: dst_height,
+                }
+            }
+            (Some(horiz_coeffs), Some(mut vert_coeffs)) => {
+                let y_first = vert_coeffs.bounds[0].start;
+                // Last used row in the source image
+                let last_y_bound = vert_coeffs.bounds.last().unwrap();
+                let y_last = last_y_bound.start + last_y_bound.size;
+
+                // Shift bounds for the vertical pass
+                vert_coeffs
+                    .bounds
+                    .iter_mut()
+                    .for_each(|b| b.start -= y_first);
+
+                Self::HorizontalFirst {
+                    offset: y_first,
+                    horiz_coeffs,
+                    vert_coeffs,
+                    temp_width: dst_width,
+                    temp_height: y_last - y_first,
+                }
+            }
+            (Some(coeffs), None) => Self::Horizontal {
+                // crop_box.top is exactly an integer if the vertical pass is not required
+                offset: crop_box.top as u32,
+                coeffs,
+            },
+            (None, Some(coeffs)) => Self::Vertical {
+                // crop_box.left is exactly an integer if the horizontal pass is not required
+                offset: crop_box.left as u32,
+                coeffs,
+            },
+            (None, None) => Self::None,
+        }
+    }
+}
+
+/// Returns an error if parameters of any filter are invalid.
+pub(crate) fn check_kernels(
+    horiz_kernel: &AxisKernel,
+    vert_kernel: &AxisKernel,
+) -> Result<(), ResizeError> {
+    if horiz_kernel.is_valid() && vert_kernel.is_valid() {
+        Ok(())
+    } else {
+        Err(ResizeError::InvalidFilterParameters)
+    }
+}
+
+/// Returns an error if the scale of the kernel size is not
+/// a finite number greater than zero.
+pub(crate) fn check_kernel_scale(kernel_scale: f64) -> Result<(), ResizeError> {
+    if kernel_scale.is_finite() && kernel_scale > 0. {
+        Ok(())
+    } else {
+        Err(ResizeError::InvalidKernelScale)
+    }
+}
+
+/// Returns coefficients of the horizontal and vertical passes of convolution.
+/// The coefficients are `None` if the corresponding pass is not required
+/// because the cropped source image has the same size as the destination
+/// image by this axis.
+pub(crate) fn precompute_passes_coefficients(
+    (src_width, src_height): (u32, u32),
+    crop_box: CropBox,
+    (dst_width, dst_height): (u32, u32),
+    horiz_kernel: &AxisKernel,
+    vert_kernel: &AxisKernel,
+    kernel_scale: f64,
+    edge_mode: EdgeMode,
+) -> (Option<Coefficients>, Option<Coefficients>) {
+    let need_horizontal =
+        dst_width as f64 != crop_box.width || crop_box.left != crop_box.left.round();
+    let horiz_coeffs = need_horizontal.then(|| {
+        test_log!("compute horizontal convolution coefficients");
+        horiz_kernel.coefficients(
+            src_width,
+            crop_box.left,
+            crop_box.left + crop_box.width,
+            dst_width,
+            kernel_scale,
+            edge_mode,
+        )
+    });
+
+    let need_vertical =
+        dst_height as f64 != crop_box.height || crop_box.top != crop_box.top.round();
+    let vert_coeffs = need_vertical.then(|| {
+        test_log!("compute vertical convolution coefficients");
+        vert_kernel.coefficients(
+            src_height,
+            crop_box.top,
+            crop_box.top + crop_box.height,
+            dst_height,
+            kernel_scale,
+            edge_mode,
+        )
+    });
+
+    (horiz_coeffs, vert_coeffs)
+}
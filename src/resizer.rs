@@ -1,14 +1,25 @@
+use crate::color::color_space::ColorSpaceConverter;
 use crate::compat::*;
-use crate::convolution::{self, FilterType};
+use crate::convolution::{box_reduce, Coefficients, Convolution, EdgeMode, FilterType};
 use crate::crop_box::CroppedSrcImageView;
 use crate::image_view::{try_pixel_type, ImageView, ImageViewMut, IntoImageView, IntoImageViewMut};
-use crate::images::TypedImage;
+use crate::images::{Image, ImageRef, TypedCroppedImage, TypedCroppedImageMut, TypedImage};
+use crate::mul_div::is_supported_pixel_type;
+use crate::orientation::orient_typed;
 use crate::pixels::{self, InnerPixel};
+use crate::resize_plan::{
+    check_alpha_position, check_kernel_scale, AlphaConversion, ConvolutionPasses, ConvolutionPlan,
+    NearestPlan, OrientationStep, ResizeSteps, WrapBorders,
+};
+use crate::warp::{
+    check_matrix, minification_scales, separable_crop_box, warp_nearest, warp_with_sampler,
+};
 use crate::{
-    CpuExtensions, CropBox, DifferentDimensionsError, MulDiv, PixelTrait, PixelType, ResizeError,
+    AffineMatrix, AlphaPosition, ColorSpace, CpuExtensions, CropBox, ImageError, ImagePyramid,
+    MulDiv, Orientation, PixelTrait, PixelType, PyramidOptions, ResizeError, ResizePlan, Sampler,
 };
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
 pub enum ResizeAlg {
     Nearest,
@@ -16,9 +27,54 @@ pub enum ResizeAlg {
     /// It is like `Convolution` but with a fixed kernel size.
     ///
     /// This algorithm can be useful if you want to get a result
-    /// similar to `OpenCV` (except `INTER_AREA` interpolation).
+    /// similar to `OpenCV` (use `Area` instead of `INTER_AREA` interpolation).
     Interpolation(FilterType),
     SuperSampling(FilterType, u8),
+    /// It is like `Convolution` but with separate filters
+    /// for the horizontal and vertical passes.
+    PerAxisConvolution {
+        horizontal: AxisFilter,
+        vertical: AxisFilter,
+    },
+    /// It is like `Convolution` but the source image is reduced first
+    /// by integer factors with the exact area-average (box) filter,
+    /// like the `reducing_gap` argument of `Image.resize()` in Pillow does.
+    ///
+    /// The second value is a reducing gap (must be finite and not less than `1.0`).
+    /// The reduced image is at least `reducing_gap` times larger than
+    /// the destination image. The smaller the gap, the faster resizing, but
+    /// the result is less close to the result of `Convolution`.
+    ///
+    /// Like `Image.reduce()` in Pillow, only the part of the source image
+    /// used by the filter is reduced, and blocks of pixels at its right
+    /// and bottom edges are smaller if its sizes are not multiples
+    /// of the factors.
+    ReducingGap(FilterType, f64),
+    /// Resampling using pixel area relation, like `INTER_AREA`
+    /// interpolation in `OpenCV`.
+    ///
+    /// Every destination pixel is the average of source pixels covered
+    /// by its area, with partially covered pixels weighted by
+    /// the covered fraction. If the image is upscaled by any axis,
+    /// the bilinear interpolation of `OpenCV` for `INTER_AREA` is used.
+    ///
+    /// [ResizeOptions::kernel_scale] is ignored by this algorithm.
+    /// Weights never go beyond edges of the image, so only
+    /// [EdgeMode::Wrap] of edge modes changes the result.
+    ///
+    /// If sizes of the cropped source image are multiples of sizes
+    /// of the destination image, the faster `IntegerBox` algorithm
+    /// is used because it gives the same result.
+    Area,
+    /// Every destination pixel is the average of the block of source pixels
+    /// covered by it. Sizes of the cropped source image must be multiples
+    /// of sizes of the destination image, e.g. for downscaling
+    /// by exactly 2x, 3x or 4x.
+    ///
+    /// It is much faster than `Convolution` with the same result as `Area`.
+    /// [ResizeOptions::kernel_scale] and [ResizeOptions::edge_mode]
+    /// are ignored by this algorithm.
+    IntegerBox,
 }
 
 impl Default for ResizeAlg {
@@ -27,6 +83,34 @@ impl Default for ResizeAlg {
     }
 }
 
+/// Filter of convolution along one axis of an image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxisFilter {
+    pub filter_type: FilterType,
+    /// If `true`, the kernel size is increased in proportion to the scale
+    /// of downscaling, like with `ResizeAlg::Convolution`.
+    /// Otherwise, the kernel size is fixed, like with `ResizeAlg::Interpolation`.
+    pub adaptive_kernel_size: bool,
+}
+
+impl AxisFilter {
+    /// Filter with the adaptive kernel size.
+    pub fn convolution(filter_type: FilterType) -> Self {
+        Self {
+            filter_type,
+            adaptive_kernel_size: true,
+        }
+    }
+
+    /// Filter with the fixed kernel size.
+    pub fn interpolation(filter_type: FilterType) -> Self {
+        Self {
+            filter_type,
+            adaptive_kernel_size: false,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 #[non_exhaustive]
 pub enum SrcCropping {
@@ -36,8 +120,71 @@ pub enum SrcCropping {
     FitIntoDestination((f64, f64)),
 }
 
+/// Size of the biggest pixel type in bytes.
+const MAX_PIXEL_SIZE: usize = 32;
+
+/// Color used to fill an area of the destination image.
+///
+/// It stores a pixel value of any type together with its pixel type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FillColor {
+    pixel_type: PixelType,
+    bytes: [u8; MAX_PIXEL_SIZE],
+}
+
+impl FillColor {
+    pub fn new<P: PixelTrait>(pixel: P) -> Self {
+        let mut bytes = [0; MAX_PIXEL_SIZE];
+        // SAFETY: Pixel is a plain structure with components
+        // and its size is not greater than MAX_PIXEL_SIZE.
+        let pixel_bytes =
+            unsafe { core::slice::from_raw_parts(&pixel as *const P as *const u8, P::size()) };
+        bytes[..P::size()].copy_from_slice(pixel_bytes);
+        Self {
+            pixel_type: P::pixel_type(),
+            bytes,
+        }
+    }
+
+    #[inline]
+    pub fn pixel_type(&self) -> PixelType {
+        self.pixel_type
+    }
+
+    /// Returns the pixel value if it has the type `P`.
+    pub fn pixel<P: PixelTrait>(&self) -> Option<P> {
+        if self.pixel_type != P::pixel_type() {
+            return None;
+        }
+        // SAFETY: The bytes were created from a pixel of the same type.
+        Some(unsafe { core::ptr::read_unaligned(self.bytes.as_ptr() as *const P) })
+    }
+}
+
+/// Parameters of fitting a source image into a destination image
+/// with padding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Padding {
+    /// Color of the area of the destination image around the resized image.
+    pub fill_color: FillColor,
+    /// Position of the resized image inside the destination image.
+    /// (0.5, 0.5) is the center, (0.0, 0.0) is the top left corner.
+    pub centering: (f64, f64),
+}
+
+/// Mode of storing colors of pixels with an alpha channel.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AlphaMode {
+    /// Color components are not multiplied by alpha.
+    #[default]
+    Straight,
+    /// Color components are already multiplied by alpha.
+    Premultiplied,
+}
+
 /// Options for configuring a resize process.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct ResizeOptions {
     /// Default: `ResizeAlg::Convolution(FilterType::Lanczos3)`
     pub algorithm: ResizeAlg,
@@ -47,6 +194,50 @@ pub struct ResizeOptions {
     ///
     /// Default: `true`.
     pub mul_div_alpha: bool,
+    /// Alpha mode of the source image.
+    /// It is ignored if `mul_div_alpha` is `false`.
+    ///
+    /// Default: `AlphaMode::Straight`.
+    pub src_alpha_mode: AlphaMode,
+    /// Alpha mode of the destination image.
+    /// It is ignored if `mul_div_alpha` is `false`.
+    ///
+    /// Default: `AlphaMode::Straight`.
+    pub dst_alpha_mode: AlphaMode,
+    /// Position of the alpha channel among components of pixels.
+    /// Images with pixels in ARGB or ABGR order must be resized with
+    /// `AlphaPosition::First`, it is supported only by U8x4 and U16x4 pixels.
+    /// Pixel types without an alpha channel ignore this option.
+    ///
+    /// Default: `AlphaPosition::Last`.
+    pub alpha_position: AlphaPosition,
+    /// Color space of source and destination images.
+    ///
+    /// Default: `ColorSpace::Linear`.
+    pub color_space: ColorSpace,
+    /// Fit the (cropped) source image into the destination image without
+    /// distortions and fill the remaining area with a color.
+    ///
+    /// Default: `None`.
+    pub padding: Option<Padding>,
+    /// Mode of handling pixels outside the source image if the kernel
+    /// of a convolution filter crosses the image edge.
+    ///
+    /// Default: `EdgeMode::Renormalize`.
+    pub edge_mode: EdgeMode,
+    /// Scale of the kernel size of a convolution filter.
+    /// Values greater than `1.0` blur the result, values less than `1.0`
+    /// sharpen it. The value must be a finite number greater than zero.
+    ///
+    /// Default: `1.0`.
+    pub kernel_scale: f64,
+    /// Orientation of the source image, e.g. from the EXIF metadata.
+    /// The source image is resized as if the orientation had been applied
+    /// to it before resizing, so the size of the destination image and
+    /// cropping options are related to the oriented source image.
+    ///
+    /// Default: `Orientation::Normal`.
+    pub orientation: Orientation,
 }
 
 impl Default for ResizeOptions {
@@ -55,6 +246,14 @@ impl Default for ResizeOptions {
             algorithm: ResizeAlg::Convolution(FilterType::Lanczos3),
             cropping: SrcCropping::None,
             mul_div_alpha: true,
+            src_alpha_mode: AlphaMode::Straight,
+            dst_alpha_mode: AlphaMode::Straight,
+            alpha_position: AlphaPosition::Last,
+            color_space: ColorSpace::Linear,
+            padding: None,
+            edge_mode: EdgeMode::Renormalize,
+            kernel_scale: 1.0,
+            orientation: Orientation::Normal,
         }
     }
 }
@@ -66,14 +265,14 @@ impl ResizeOptions {
 
     /// Set resize algorythm.
     pub fn resize_alg(&self, resize_alg: ResizeAlg) -> Self {
-        let mut options = *self;
+        let mut options = self.clone();
         options.algorithm = resize_alg;
         options
     }
 
     /// Set crop box for source image.
     pub fn crop(&self, left: f64, top: f64, width: f64, height: f64) -> Self {
-        let mut options = *self;
+        let mut options = self.clone();
         options.cropping = SrcCropping::Crop(CropBox {
             left,
             top,
@@ -96,42 +295,133 @@ impl ResizeOptions {
     /// crop off the left side, and if cropping the height, take
     /// none from the top, and therefore all off the bottom).
     pub fn fit_into_destination(&self, centering: Option<(f64, f64)>) -> Self {
-        let mut options = *self;
+        let mut options = self.clone();
         options.cropping = SrcCropping::FitIntoDestination(centering.unwrap_or((0.5, 0.5)));
         options
     }
 
+    /// Fit a source image entirely inside a destination image without distortions
+    /// and fill the remaining area of the destination image with `fill_color`.
+    ///
+    /// `centering` is used to control the position of the resized image.
+    /// Use (0.5, 0.5) to place it into the center of the destination image,
+    /// (0.0, 0.0) to place it into the top left corner, (1.0, 1.0) to place it
+    /// into the bottom right corner, etc.
+    ///
+    /// The pixel type of `fill_color` must be the same as the pixel type of
+    /// images, otherwise [ResizeError::PixelTypesAreDifferent] is returned.
+    pub fn fit_into_destination_with_padding<P: PixelTrait>(
+        &self,
+        fill_color: P,
+        centering: Option<(f64, f64)>,
+    ) -> Self {
+        let mut options = self.clone();
+        options.padding = Some(Padding {
+            fill_color: FillColor::new(fill_color),
+            centering: centering.unwrap_or((0.5, 0.5)),
+        });
+        options
+    }
+
     /// Enable or disable consideration of the alpha channel when resizing.
     pub fn use_alpha(&self, v: bool) -> Self {
-        let mut options = *self;
+        let mut options = self.clone();
         options.mul_div_alpha = v;
         options
     }
 
-    fn get_crop_box<P: PixelTrait>(
+    /// Set mode of handling pixels outside the source image.
+    pub fn edge_mode(&self, edge_mode: EdgeMode) -> Self {
+        let mut options = self.clone();
+        options.edge_mode = edge_mode;
+        options
+    }
+
+    /// Set scale of the kernel size of a convolution filter.
+    ///
+    /// Values greater than `1.0` widen the kernel and blur the result,
+    /// it may be useful to avoid moiré while downscaling.
+    /// Values less than `1.0` narrow the kernel and sharpen the result.
+    pub fn kernel_scale(&self, kernel_scale: f64) -> Self {
+        let mut options = self.clone();
+        options.kernel_scale = kernel_scale;
+        options
+    }
+
+    /// Set alpha modes of source and destination images.
+    ///
+    /// Resizer multiplies colors of the source image by alpha only if it has
+    /// the straight alpha mode, and divides colors of the destination image
+    /// by alpha only if it has the straight alpha mode.
+    pub fn alpha_modes(&self, src_alpha_mode: AlphaMode, dst_alpha_mode: AlphaMode) -> Self {
+        let mut options = self.clone();
+        options.src_alpha_mode = src_alpha_mode;
+        options.dst_alpha_mode = dst_alpha_mode;
+        options
+    }
+
+    /// Set position of the alpha channel among components of pixels.
+    pub fn alpha_position(&self, alpha_position: AlphaPosition) -> Self {
+        let mut options = self.clone();
+        options.alpha_position = alpha_position;
+        options
+    }
+
+    /// Set orientation of the source image.
+    ///
+    /// Flips and rotations are applied to the smaller of the source
+    /// and the resized images, so it is faster than orienting
+    /// the source image before resizing.
+    pub fn orientation(&self, orientation: Orientation) -> Self {
+        let mut options = self.clone();
+        options.orientation = orientation;
+        options
+    }
+
+    /// Set color space of source and destination images.
+    ///
+    /// Images with non-linear color space are converted into linear
+    /// color space before resizing and back after it.
+    pub fn color_space(&self, color_space: ColorSpace) -> Self {
+        let mut options = self.clone();
+        options.color_space = color_space;
+        options
+    }
+
+    pub(crate) fn get_crop_box(
         &self,
-        src_view: &impl ImageView<Pixel = P>,
-        dst_view: &impl ImageView<Pixel = P>,
+        src_width: u32,
+        src_height: u32,
+        dst_width: u32,
+        dst_height: u32,
     ) -> CropBox {
         match self.cropping {
             SrcCropping::None => CropBox {
                 left: 0.,
                 top: 0.,
-                width: src_view.width() as _,
-                height: src_view.height() as _,
+                width: src_width as _,
+                height: src_height as _,
             },
             SrcCropping::Crop(crop_box) => crop_box,
             SrcCropping::FitIntoDestination(centering) => CropBox::fit_src_into_dst_size(
-                src_view.width(),
-                src_view.height(),
-                dst_view.width(),
-                dst_view.height(),
+                src_width,
+                src_height,
+                dst_width,
+                dst_height,
                 Some(centering),
             ),
         }
     }
 }
 
+/// Source of parameters used to resize images.
+#[derive(Clone, Copy)]
+enum ResizeParams<'a> {
+    Options(&'a ResizeOptions),
+    Plan(&'a ResizePlan),
+    Warp(&'a AffineMatrix, &'a ResizeOptions),
+}
+
 /// Methods of this structure used to resize images.
 #[derive(Default, Debug, Clone)]
 pub struct Resizer {
@@ -140,6 +430,10 @@ pub struct Resizer {
     alpha_buffer: Vec<u8>,
     convolution_buffer: Vec<u8>,
     super_sampling_buffer: Vec<u8>,
+    color_buffer: Vec<u8>,
+    wrap_buffer: Vec<u8>,
+    orientation_buffer: Vec<u8>,
+    color_converter: Option<ColorSpaceConverter>,
 }
 
 impl Resizer {
@@ -159,6 +453,31 @@ impl Resizer {
         src_image: &impl IntoImageView,
         dst_image: &mut impl IntoImageViewMut,
         options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<(), ResizeError> {
+        let default_options = ResizeOptions::default();
+        let options = options.into().unwrap_or(&default_options);
+        self.resize_images(src_image, dst_image, ResizeParams::Options(options))
+    }
+
+    /// Resize the source image to the size of the destination image and save
+    /// the result to the latter's pixel buffer using a precomputed plan.
+    ///
+    /// Returns [ResizeError::ImagesDoNotMatchPlan] if the pixel type or
+    /// dimensions of images are not equal to those the plan was created for.
+    pub fn resize_with_plan(
+        &mut self,
+        plan: &ResizePlan,
+        src_image: &impl IntoImageView,
+        dst_image: &mut impl IntoImageViewMut,
+    ) -> Result<(), ResizeError> {
+        self.resize_images(src_image, dst_image, ResizeParams::Plan(plan))
+    }
+
+    fn resize_images(
+        &mut self,
+        src_image: &impl IntoImageView,
+        dst_image: &mut impl IntoImageViewMut,
+        params: ResizeParams,
     ) -> Result<(), ResizeError> {
         let src_pixel_type = try_pixel_type(src_image)?;
         let dst_pixel_type = try_pixel_type(dst_image)?;
@@ -181,8 +500,20 @@ impl Resizer {
                                 $src_image.image_view::<$pt>(),
                                 $dst_image.image_view_mut::<$pt>(),
                             ) {
-                                (Some(src), Some(mut dst)) => self.resize_typed(&src, &mut dst, options),
-                                _ => Err(ResizeError::PixelTypesAreDifferent),
+                                (Some(src), Some(mut dst)) => match params {
+                                    ResizeParams::Options(options) => {
+                                        self.resize_typed(&src, &mut dst, options)
+                                    }
+                                    ResizeParams::Plan(plan) => {
+                                        self.resize_typed_with_plan(plan, &src, &mut dst)
+                                    }
+                                    ResizeParams::Warp(matrix, options) => {
+                                        self.warp_affine_typed(&src, &mut dst, matrix, options)
+                                    }
+                                },
+                                // Pixel types of images are equal, so views can't be
+                                // created because buffers of images are not aligned.
+                                _ => Err(ImageError::InvalidBufferAlignment.into()),
                             }
                         }
                     )*
@@ -209,6 +540,21 @@ impl Resizer {
             (PT::F32x2, pixels::F32x2),
             (PT::F32x3, pixels::F32x3),
             (PT::F32x4, pixels::F32x4),
+            (PT::F16, pixels::F16),
+            (PT::F16x2, pixels::F16x2),
+            (PT::F16x3, pixels::F16x3),
+            (PT::F16x4, pixels::F16x4),
+            (PT::F64, pixels::F64),
+            (PT::F64x2, pixels::F64x2),
+            (PT::F64x3, pixels::F64x3),
+            (PT::F64x4, pixels::F64x4),
+            (PT::I32x2, pixels::I32x2),
+            (PT::I32x3, pixels::I32x3),
+            (PT::I32x4, pixels::I32x4),
+            (PT::U32, pixels::U32),
+            (PT::U32x2, pixels::U32x2),
+            (PT::U32x3, pixels::U32x3),
+            (PT::U32x4, pixels::U32x4),
         );
 
         #[cfg(feature = "only_u8x4")]
@@ -224,53 +570,274 @@ impl Resizer {
         src_view: &impl ImageView<Pixel = P>,
         dst_view: &mut impl ImageViewMut<Pixel = P>,
         options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<(), ResizeError> {
+        let plan = ResizePlan::new(
+            P::pixel_type(),
+            src_view.width(),
+            src_view.height(),
+            dst_view.width(),
+            dst_view.height(),
+            options,
+        )?;
+        self.run_plan(&plan, src_view, dst_view);
+        Ok(())
+    }
+
+    /// Resize the source image to the size of the destination image
+    /// and save the result to the latter's pixel buffer using
+    /// a precomputed plan.
+    ///
+    /// Returns [ResizeError::ImagesDoNotMatchPlan] if the pixel type or
+    /// dimensions of images are not equal to those the plan was created for.
+    pub fn resize_typed_with_plan<P: PixelTrait>(
+        &mut self,
+        plan: &ResizePlan,
+        src_view: &impl ImageView<Pixel = P>,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+    ) -> Result<(), ResizeError> {
+        if !plan.is_suitable(
+            P::pixel_type(),
+            (src_view.width(), src_view.height()),
+            (dst_view.width(), dst_view.height()),
+        ) {
+            return Err(ResizeError::ImagesDoNotMatchPlan);
+        }
+        self.run_plan(plan, src_view, dst_view);
+        Ok(())
+    }
+
+    /// Transforms the source image with an affine transformation
+    /// and saves the result to the destination image.
+    ///
+    /// The matrix maps coordinates of points in the destination image into
+    /// coordinates of points in the source image (see [AffineMatrix]).
+    /// Coordinates use the same system as [CropBox]: the center of the pixel
+    /// `(x, y)` has coordinates `(x + 0.5, y + 0.5)`.
+    ///
+    /// Supported algorithms:
+    /// - `ResizeAlg::Nearest`;
+    /// - `ResizeAlg::Interpolation` - the kernel of the filter has a fixed size;
+    /// - `ResizeAlg::Convolution` - the kernel of the filter is widened along
+    ///   axes of the source image that are minified by the transformation,
+    ///   so the result has no aliasing.
+    ///
+    /// Other algorithms return [ResizeError::UnsupportedAlgorithm].
+    /// Cropping, padding and orientation options are ignored.
+    /// Pixels outside the source image are handled according
+    /// to [ResizeOptions::edge_mode];
+    /// destination pixels that don't cover any source pixels are set to zero.
+    ///
+    /// If the transformation only scales and translates the image and
+    /// the transformed region lies inside the source image, the image is
+    /// resized by passes of convolution along rows and columns like
+    /// [Resizer::resize] does, which is much faster than sampling of every
+    /// destination pixel.
+    pub fn warp_affine<'o>(
+        &mut self,
+        src_image: &impl IntoImageView,
+        dst_image: &mut impl IntoImageViewMut,
+        matrix: &AffineMatrix,
+        options: impl Into<Option<&'o ResizeOptions>>,
     ) -> Result<(), ResizeError> {
         let default_options = ResizeOptions::default();
         let options = options.into().unwrap_or(&default_options);
+        self.resize_images(src_image, dst_image, ResizeParams::Warp(matrix, options))
+    }
 
-        let crop_box = options.get_crop_box(src_view, dst_view);
-        if crop_box.width == 0.
-            || crop_box.height == 0.
-            || dst_view.width() == 0
-            || dst_view.height() == 0
-        {
-            // Do nothing if any size of the source or destination image is equal to zero.
-            return Ok(());
+    /// Transforms the source image with an affine transformation
+    /// and saves the result to the destination image.
+    ///
+    /// See [Resizer::warp_affine] for details.
+    pub fn warp_affine_typed<'o, P: PixelTrait>(
+        &mut self,
+        src_view: &impl ImageView<Pixel = P>,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+        matrix: &AffineMatrix,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<(), ResizeError> {
+        let default_options = ResizeOptions::default();
+        let options = options.into().unwrap_or(&default_options);
+        check_matrix(matrix)?;
+        check_kernel_scale(options.kernel_scale)?;
+        check_alpha_position(P::pixel_type(), options)?;
+        self.mul_div.set_alpha_position(options.alpha_position);
+
+        let (filter_type, (scale_x, scale_y)) = match &options.algorithm {
+            ResizeAlg::Nearest => {
+                warp_nearest(src_view, dst_view, matrix, options.edge_mode);
+                self.convert_alpha(dst_view, AlphaConversion::new(P::pixel_type(), options));
+                return Ok(());
+            }
+            ResizeAlg::Interpolation(filter_type) => (filter_type, (1., 1.)),
+            ResizeAlg::Convolution(filter_type) => (filter_type, minification_scales(matrix)),
+            _ => return Err(ResizeError::UnsupportedAlgorithm),
+        };
+        if !filter_type.is_valid() {
+            return Err(ResizeError::InvalidFilterParameters);
         }
 
-        let cropped_src_view = CroppedSrcImageView::crop(src_view, crop_box)?;
+        let src_size = (src_view.width(), src_view.height());
+        let dst_size = (dst_view.width(), dst_view.height());
+        if let Some(crop_box) = separable_crop_box(matrix, src_size, dst_size) {
+            // Kernels of filters are scaled in the same way while resizing,
+            // so the image is transformed by passes of convolution.
+            let options = ResizeOptions {
+                cropping: SrcCropping::Crop(crop_box),
+                padding: None,
+                orientation: Orientation::Normal,
+                ..options.clone()
+            };
+            return self.resize_typed(src_view, dst_view, &options);
+        }
+
+        let kernel_scale = options.kernel_scale;
+        let sampler = Sampler::new(src_view, filter_type.clone())?
+            .edge_mode(options.edge_mode)
+            .alpha_position(options.alpha_position)
+            .kernel_scale(scale_x * kernel_scale, scale_y * kernel_scale);
 
-        if copy_image(&cropped_src_view, dst_view).is_ok() {
-            // If `copy_image()` returns `Ok` it means that
-            // the size of the destination image is equal to
-            // the size of the cropped source image and
-            // the copy operation has success.
+        if options.color_space != ColorSpace::Linear {
+            self.warp_in_linear_space(&sampler, dst_view, matrix, options);
             return Ok(());
         }
 
-        match options.algorithm {
-            ResizeAlg::Nearest => resample_nearest(&cropped_src_view, dst_view),
-            ResizeAlg::Convolution(filter_type) => self.resample_convolution(
-                &cropped_src_view,
-                dst_view,
-                filter_type,
-                true,
-                options.mul_div_alpha,
-            ),
-            ResizeAlg::Interpolation(filter_type) => self.resample_convolution(
-                &cropped_src_view,
-                dst_view,
-                filter_type,
-                false,
-                options.mul_div_alpha,
-            ),
-            ResizeAlg::SuperSampling(filter_type, multiplicity) => self.resample_super_sampling(
-                &cropped_src_view,
-                dst_view,
-                filter_type,
-                multiplicity,
-                options.mul_div_alpha,
-            ),
+        // Colors of the source image with premultiplied alpha
+        // are interpolated as is.
+        let sampler = sampler
+            .use_alpha(options.mul_div_alpha && options.src_alpha_mode == AlphaMode::Straight);
+        warp_with_sampler(&sampler, dst_view, matrix);
+        self.convert_alpha(dst_view, AlphaConversion::new(P::pixel_type(), options));
+        Ok(())
+    }
+
+    /// Converts the source image into linear color space, transforms it
+    /// and converts the result back into the destination image.
+    fn warp_in_linear_space<P: PixelTrait>(
+        &mut self,
+        sampler: &Sampler<impl ImageView<Pixel = P>>,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+        matrix: &AffineMatrix,
+        options: &ResizeOptions,
+    ) {
+        let src_view = sampler.view();
+        let mut converter = match self.color_converter.take() {
+            Some(converter) if converter.color_space() == options.color_space => converter,
+            _ => ColorSpaceConverter::new(options.color_space),
+        };
+        converter.set_alpha_position(self.mul_div.alpha_position());
+        let mut src_buffer = core::mem::take(&mut self.alpha_buffer);
+        let mut dst_buffer = core::mem::take(&mut self.color_buffer);
+
+        let mut linear_src = get_temp_image_from_buffer::<P::Linear>(
+            &mut src_buffer,
+            src_view.width(),
+            src_view.height(),
+        );
+
+        // Colors with premultiplied alpha can't be mapped into linear
+        // color space, so they are divided by alpha first.
+        let mut is_mapped = false;
+        if options.mul_div_alpha && options.src_alpha_mode == AlphaMode::Premultiplied {
+            let mut straight_src = get_temp_image_from_buffer::<P>(
+                &mut dst_buffer,
+                src_view.width(),
+                src_view.height(),
+            );
+            if self
+                .mul_div
+                .divide_alpha_typed(src_view, &mut straight_src)
+                .is_ok()
+            {
+                P::into_linear(&mut converter, &straight_src, &mut linear_src);
+                is_mapped = true;
+            }
+        }
+        if !is_mapped {
+            P::into_linear(&mut converter, src_view, &mut linear_src);
+        }
+
+        let mut linear_dst = get_temp_image_from_buffer::<P::Linear>(
+            &mut dst_buffer,
+            dst_view.width(),
+            dst_view.height(),
+        );
+        let linear_sampler = sampler
+            .with_view(&linear_src)
+            .use_alpha(options.mul_div_alpha);
+        warp_with_sampler(&linear_sampler, &mut linear_dst, matrix);
+        P::from_linear(&mut converter, &linear_dst, dst_view);
+        if options.mul_div_alpha
+            && options.dst_alpha_mode == AlphaMode::Premultiplied
+            && is_supported_pixel_type(P::pixel_type())
+        {
+            self.mul_div.multiply_alpha_inplace_typed(dst_view).unwrap();
+        }
+
+        self.alpha_buffer = src_buffer;
+        self.color_buffer = dst_buffer;
+        self.color_converter = Some(converter);
+    }
+
+    /// Builds an image pyramid (mipmap chain) from the source image.
+    ///
+    /// The first level of the pyramid is a copy of the source image
+    /// oriented and cropped according to [PyramidOptions::resize_options].
+    /// Every next level is resized from the previous one (or from
+    /// the source image if [PyramidOptions::from_original] is `true`)
+    /// until the size 1x1 or the minimal size from options is reached.
+    pub fn build_pyramid<'o>(
+        &mut self,
+        src_image: &impl IntoImageView,
+        options: impl Into<Option<&'o PyramidOptions>>,
+    ) -> Result<ImagePyramid, ResizeError> {
+        let mut pyramid = ImagePyramid::empty(try_pixel_type(src_image)?);
+        self.build_pyramid_into(src_image, &mut pyramid, options)?;
+        Ok(pyramid)
+    }
+
+    /// Builds an image pyramid from the source image like
+    /// [Resizer::build_pyramid] does, but reuses the buffer
+    /// of the given pyramid.
+    pub fn build_pyramid_into<'o>(
+        &mut self,
+        src_image: &impl IntoImageView,
+        pyramid: &mut ImagePyramid,
+        options: impl Into<Option<&'o PyramidOptions>>,
+    ) -> Result<(), ResizeError> {
+        let default_options = PyramidOptions::default();
+        let options = options.into().unwrap_or(&default_options);
+        let pixel_type = try_pixel_type(src_image)?;
+        pyramid.reset(pixel_type, src_image.width(), src_image.height(), options);
+
+        let src_options = options.level_resize_options(true);
+        let prev_level_options = options.level_resize_options(false);
+        let levels = pyramid.levels.clone();
+        let buffer = pyramid.buffer_mut();
+        for (i, level) in levels.iter().enumerate() {
+            let (head, tail) = buffer.split_at_mut(level.offset);
+            let size = level.width as usize * level.height as usize * pixel_type.size();
+            if size == 0 {
+                continue;
+            }
+            // Offsets of levels are multiples of the pixel size,
+            // so all levels have the same alignment as the buffer.
+            // Sizes of buffers of levels are always enough.
+            let mut dst_image =
+                Image::from_slice_u8(level.width, level.height, &mut tail[..size], pixel_type)
+                    .map_err(|_| ImageError::InvalidBufferAlignment)?;
+            if i == 0 || options.from_original {
+                self.resize(src_image, &mut dst_image, &src_options)?;
+            } else {
+                let prev_level = levels[i - 1];
+                let prev_image = ImageRef::new(
+                    prev_level.width,
+                    prev_level.height,
+                    &head[prev_level.offset..],
+                    pixel_type,
+                )
+                .map_err(|_| ImageError::InvalidBufferAlignment)?;
+                self.resize(&prev_image, &mut dst_image, &prev_level_options)?;
+            }
         }
         Ok(())
     }
@@ -280,7 +847,10 @@ impl Resizer {
     pub fn size_of_internal_buffers(&self) -> usize {
         (self.alpha_buffer.capacity()
             + self.convolution_buffer.capacity()
-            + self.super_sampling_buffer.capacity())
+            + self.super_sampling_buffer.capacity()
+            + self.color_buffer.capacity()
+            + self.wrap_buffer.capacity()
+            + self.orientation_buffer.capacity())
             * size_of::<u8>()
     }
 
@@ -296,6 +866,15 @@ impl Resizer {
         if self.super_sampling_buffer.capacity() > 0 {
             self.super_sampling_buffer = Vec::new();
         }
+        if self.color_buffer.capacity() > 0 {
+            self.color_buffer = Vec::new();
+        }
+        if self.wrap_buffer.capacity() > 0 {
+            self.wrap_buffer = Vec::new();
+        }
+        if self.orientation_buffer.capacity() > 0 {
+            self.orientation_buffer = Vec::new();
+        }
     }
 
     #[inline(always)]
@@ -311,16 +890,190 @@ impl Resizer {
         self.mul_div.set_cpu_extensions(extensions);
     }
 
+    fn run_plan<P: PixelTrait>(
+        &mut self,
+        plan: &ResizePlan,
+        src_view: &impl ImageView<Pixel = P>,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+    ) {
+        self.mul_div.set_alpha_position(plan.alpha_position);
+        let orientation = match plan.steps {
+            ResizeSteps::Nothing => None,
+            _ => plan.orientation,
+        };
+        match orientation {
+            None => self.run_padded_steps(plan, src_view, dst_view),
+            Some(OrientationStep::BeforeResizing(orientation)) => {
+                let mut orientation_buffer = core::mem::take(&mut self.orientation_buffer);
+                let (width, height) =
+                    orientation.oriented_size(src_view.width(), src_view.height());
+                let mut oriented_src =
+                    get_temp_image_from_buffer(&mut orientation_buffer, width, height);
+                orient_typed(src_view, &mut oriented_src, orientation);
+                self.run_padded_steps(plan, &oriented_src, dst_view);
+                self.orientation_buffer = orientation_buffer;
+            }
+            Some(OrientationStep::AfterResizing {
+                orientation,
+                tmp_width,
+                tmp_height,
+            }) => {
+                let mut orientation_buffer = core::mem::take(&mut self.orientation_buffer);
+                let mut tmp_img =
+                    get_temp_image_from_buffer(&mut orientation_buffer, tmp_width, tmp_height);
+                self.run_steps(&plan.steps, src_view, &mut tmp_img);
+                match &plan.padding {
+                    Some(padding) => {
+                        padding.fill_around(dst_view);
+                        let mut inner_dst_view = TypedCroppedImageMut::from_ref(
+                            dst_view,
+                            padding.left,
+                            padding.top,
+                            padding.width,
+                            padding.height,
+                        )
+                        .unwrap();
+                        orient_typed(&tmp_img, &mut inner_dst_view, orientation);
+                    }
+                    None => orient_typed(&tmp_img, dst_view, orientation),
+                }
+                self.orientation_buffer = orientation_buffer;
+            }
+        }
+    }
+
+    fn run_padded_steps<P: PixelTrait>(
+        &mut self,
+        plan: &ResizePlan,
+        src_view: &impl ImageView<Pixel = P>,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+    ) {
+        match &plan.padding {
+            Some(padding) => {
+                padding.fill_around(dst_view);
+                // The source image is resized directly into
+                // the inner part of the destination image.
+                let mut inner_dst_view = TypedCroppedImageMut::from_ref(
+                    dst_view,
+                    padding.left,
+                    padding.top,
+                    padding.width,
+                    padding.height,
+                )
+                .unwrap();
+                self.run_steps(&plan.steps, src_view, &mut inner_dst_view);
+            }
+            None => self.run_steps(&plan.steps, src_view, dst_view),
+        }
+    }
+
+    fn run_steps<P: PixelTrait>(
+        &mut self,
+        steps: &ResizeSteps,
+        src_view: &impl ImageView<Pixel = P>,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+    ) {
+        match steps {
+            ResizeSteps::Nothing => {}
+            ResizeSteps::Copy(crop_box, alpha_conversion) => {
+                // SAFETY: The crop box has been checked while creating the plan.
+                let cropped_src_view =
+                    unsafe { CroppedSrcImageView::crop_unchecked(src_view, *crop_box) };
+                copy_image(&cropped_src_view, dst_view);
+                self.convert_alpha(dst_view, *alpha_conversion);
+            }
+            ResizeSteps::Nearest(nearest_plan, alpha_conversion) => {
+                resample_nearest(src_view, dst_view, nearest_plan);
+                self.convert_alpha(dst_view, *alpha_conversion);
+            }
+            ResizeSteps::Convolution(conv_plan) => {
+                self.resample_convolution(src_view, dst_view, conv_plan);
+            }
+            ResizeSteps::SuperSampling {
+                nearest,
+                tmp_width,
+                tmp_height,
+                convolution,
+            } => {
+                let mut super_sampling_buffer = core::mem::take(&mut self.super_sampling_buffer);
+                let mut tmp_img =
+                    get_temp_image_from_buffer(&mut super_sampling_buffer, *tmp_width, *tmp_height);
+                resample_nearest(src_view, &mut tmp_img, nearest);
+                self.resample_convolution(&tmp_img, dst_view, convolution);
+                self.super_sampling_buffer = super_sampling_buffer;
+            }
+            ResizeSteps::Reducing {
+                reduction,
+                tmp_width,
+                tmp_height,
+                convolution,
+            } => {
+                let mut super_sampling_buffer = core::mem::take(&mut self.super_sampling_buffer);
+                let mut tmp_img =
+                    get_temp_image_from_buffer(&mut super_sampling_buffer, *tmp_width, *tmp_height);
+                self.resample_convolution(src_view, &mut tmp_img, reduction);
+                self.resample_convolution(&tmp_img, dst_view, convolution);
+                self.super_sampling_buffer = super_sampling_buffer;
+            }
+        }
+    }
+
+    /// Converts the destination image from the alpha mode
+    /// of the source image into its own alpha mode.
+    fn convert_alpha<P: PixelTrait>(
+        &self,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+        alpha_conversion: AlphaConversion,
+    ) {
+        match alpha_conversion {
+            AlphaConversion::None => {}
+            AlphaConversion::Multiply => {
+                self.mul_div.multiply_alpha_inplace_typed(dst_view).unwrap();
+            }
+            AlphaConversion::Divide => {
+                self.mul_div.divide_alpha_inplace_typed(dst_view).unwrap();
+            }
+        }
+    }
+
     fn resample_convolution<P: PixelTrait>(
         &mut self,
-        cropped_src_view: &CroppedSrcImageView<impl ImageView<Pixel = P>>,
+        src_view: &impl ImageView<Pixel = P>,
         dst_view: &mut impl ImageViewMut<Pixel = P>,
-        filter_type: FilterType,
-        adaptive_kernel_size: bool,
-        use_alpha: bool,
+        conv_plan: &ConvolutionPlan,
     ) {
-        if use_alpha && self.mul_div.is_supported(P::pixel_type()) {
-            let src_view = cropped_src_view.image_view();
+        if let Some(borders) = conv_plan.wrap_borders {
+            let mut wrap_buffer = core::mem::take(&mut self.wrap_buffer);
+            let mut wrapped_src = get_temp_image_from_buffer(
+                &mut wrap_buffer,
+                src_view.width() + borders.horizontal * 2,
+                src_view.height() + borders.vertical * 2,
+            );
+            wrap_image(src_view, &mut wrapped_src, borders);
+            self.resample_convolution_of_view(&wrapped_src, dst_view, conv_plan);
+            self.wrap_buffer = wrap_buffer;
+        } else {
+            self.resample_convolution_of_view(src_view, dst_view, conv_plan);
+        }
+    }
+
+    fn resample_convolution_of_view<P: PixelTrait>(
+        &mut self,
+        src_view: &impl ImageView<Pixel = P>,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+        conv_plan: &ConvolutionPlan,
+    ) {
+        if conv_plan.color_space != ColorSpace::Linear {
+            self.resample_convolution_in_linear_space(src_view, dst_view, conv_plan);
+            return;
+        }
+
+        // Colors must be multiplied by alpha during the convolution.
+        // The source image with premultiplied alpha is used as is,
+        // and the result is divided by alpha only if the destination
+        // image has straight alpha.
+        let divide_dst = conv_plan.mul_div_alpha && conv_plan.dst_alpha_mode == AlphaMode::Straight;
+        if conv_plan.mul_div_alpha && conv_plan.src_alpha_mode == AlphaMode::Straight {
             let mut alpha_buffer = core::mem::take(&mut self.alpha_buffer);
 
             let mut premultiplied_src =
@@ -331,209 +1084,359 @@ impl Resizer {
                 .multiply_alpha_typed(src_view, &mut premultiplied_src)
                 .is_ok()
             {
-                // SAFETY: `premultiplied_src` has the same size as `src_view`
-                let cropped_premultiplied_src = unsafe {
-                    CroppedSrcImageView::crop_unchecked(
-                        &premultiplied_src,
-                        cropped_src_view.crop_box(),
-                    )
-                };
-                self.do_convolution(
-                    &cropped_premultiplied_src,
-                    dst_view,
-                    filter_type,
-                    adaptive_kernel_size,
-                );
-                self.mul_div.divide_alpha_inplace_typed(dst_view).unwrap();
+                self.do_convolution(&premultiplied_src, dst_view, &conv_plan.passes);
+                if divide_dst {
+                    self.mul_div.divide_alpha_inplace_typed(dst_view).unwrap();
+                }
                 self.alpha_buffer = alpha_buffer;
                 return;
             }
             self.alpha_buffer = alpha_buffer;
         }
 
-        self.do_convolution(
-            cropped_src_view,
-            dst_view,
-            filter_type,
-            adaptive_kernel_size,
-        );
+        self.do_convolution(src_view, dst_view, &conv_plan.passes);
+        if divide_dst && conv_plan.src_alpha_mode == AlphaMode::Premultiplied {
+            self.mul_div.divide_alpha_inplace_typed(dst_view).unwrap();
+        }
     }
 
-    fn do_convolution<P: PixelTrait>(
+    /// Resizes the source image in linear color space.
+    ///
+    /// Rows of the source image are converted into linear color space
+    /// by bands right before the first pass of convolution, and rows of
+    /// the result are converted back right after the last pass. So full-size
+    /// copies of the source and destination images in linear color space
+    /// are not required.
+    fn resample_convolution_in_linear_space<P: PixelTrait>(
         &mut self,
-        cropped_src_view: &CroppedSrcImageView<impl ImageView<Pixel = P>>,
+        src_view: &impl ImageView<Pixel = P>,
         dst_view: &mut impl ImageViewMut<Pixel = P>,
-        filter_type: FilterType,
-        adaptive_kernel_size: bool,
+        conv_plan: &ConvolutionPlan,
     ) {
-        let src_view = cropped_src_view.image_view();
-        let crop_box = cropped_src_view.crop_box();
-        let (dst_width, dst_height) = (dst_view.width(), dst_view.height());
-        if dst_width == 0 || dst_height == 0 || crop_box.width <= 0. || crop_box.height <= 0. {
-            return;
-        }
-
-        let (filter_fn, filter_support) = convolution::get_filter_func(filter_type);
+        let mut converter = match self.color_converter.take() {
+            Some(converter) if converter.color_space() == conv_plan.color_space => converter,
+            _ => ColorSpaceConverter::new(conv_plan.color_space),
+        };
+        converter.set_alpha_position(self.mul_div.alpha_position());
+        let mut straight_buffer = core::mem::take(&mut self.alpha_buffer);
+        let mut band_buffer = core::mem::take(&mut self.color_buffer);
+        let mut temp_buffer = core::mem::take(&mut self.convolution_buffer);
 
-        let need_horizontal =
-            dst_width as f64 != crop_box.width || crop_box.left != crop_box.left.round();
-        let horiz_coeffs = need_horizontal.then(|| {
-            test_log!("compute horizontal convolution coefficients");
-            convolution::precompute_coefficients(
-                src_view.width(),
-                crop_box.left,
-                crop_box.left + crop_box.width,
-                dst_width,
-                filter_fn,
-                filter_support,
-                adaptive_kernel_size,
-            )
-        });
-
-        let need_vertical =
-            dst_height as f64 != crop_box.height || crop_box.top != crop_box.top.round();
-        let vert_coeffs = need_vertical.then(|| {
-            test_log!("compute vertical convolution coefficients");
-            convolution::precompute_coefficients(
-                src_view.height(),
-                crop_box.top,
-                crop_box.top + crop_box.height,
-                dst_height,
-                filter_fn,
-                filter_support,
-                adaptive_kernel_size,
-            )
-        });
+        let cpu_extensions = self.cpu_extensions;
+        let mut conversion = LinearConversion {
+            converter: &mut converter,
+            mul_div: &self.mul_div,
+            mul_div_alpha: conv_plan.mul_div_alpha,
+            src_alpha_mode: conv_plan.src_alpha_mode,
+            dst_alpha_mode: conv_plan.dst_alpha_mode,
+            straight_buffer: &mut straight_buffer,
+        };
+        let dst_width = dst_view.width();
+        let dst_height = dst_view.height();
 
-        match (horiz_coeffs, vert_coeffs) {
-            (Some(mut horiz_coeffs), Some(mut vert_coeffs)) => {
-                if P::components_is_u8() {
-                    // For u8-based images, it is faster to do the vertical pass first
-                    // instead of the horizontal.
-                    let x_first = horiz_coeffs.bounds[0].start;
-                    // Last used col in the source image
-                    let last_x_bound = horiz_coeffs.bounds.last().unwrap();
-                    let x_last = last_x_bound.start + last_x_bound.size;
-                    let temp_width = x_last - x_first;
-                    let mut temp_image = get_temp_image_from_buffer(
-                        &mut self.convolution_buffer,
-                        temp_width,
-                        dst_height,
+        match &conv_plan.passes {
+            ConvolutionPasses::None => {}
+            ConvolutionPasses::Horizontal { offset, coeffs } => {
+                for (top, height) in linear_bands(dst_height) {
+                    let mut linear_src =
+                        get_temp_image_from_buffer(&mut band_buffer, src_view.width(), height);
+                    conversion.load_rows(src_view, (0, offset + top), &mut linear_src);
+                    let mut linear_dst =
+                        get_temp_image_from_buffer(&mut temp_buffer, dst_width, height);
+                    P::Linear::horiz_convolution(
+                        &linear_src,
+                        &mut linear_dst,
+                        0,
+                        coeffs,
+                        cpu_extensions,
                     );
-
-                    P::vert_convolution(
-                        src_view,
+                    conversion.store_rows(&mut linear_dst, dst_view, top);
+                }
+            }
+            ConvolutionPasses::Vertical { offset, coeffs } => {
+                let first_row = coeffs.bounds[0].start;
+                let last_bound = coeffs.bounds.last().unwrap();
+                let temp_height = last_bound.start + last_bound.size - first_row;
+                let mut temp_image =
+                    get_temp_image_from_buffer(&mut temp_buffer, dst_width, temp_height);
+                for (top, height) in linear_bands(temp_height) {
+                    let mut temp_rows =
+                        TypedCroppedImageMut::from_ref(&mut temp_image, 0, top, dst_width, height)
+                            .unwrap();
+                    conversion.load_rows(src_view, (*offset, first_row + top), &mut temp_rows);
+                }
+                let mut shifted_coeffs = coeffs.clone();
+                shifted_coeffs
+                    .bounds
+                    .iter_mut()
+                    .for_each(|b| b.start -= first_row);
+                vert_convolution_in_linear_bands(
+                    &mut conversion,
+                    &temp_image,
+                    dst_view,
+                    &shifted_coeffs,
+                    &mut band_buffer,
+                    cpu_extensions,
+                );
+            }
+            ConvolutionPasses::HorizontalFirst {
+                offset,
+                horiz_coeffs,
+                vert_coeffs,
+                temp_width,
+                temp_height,
+            } => {
+                let mut temp_image =
+                    get_temp_image_from_buffer(&mut temp_buffer, *temp_width, *temp_height);
+                for (top, height) in linear_bands(*temp_height) {
+                    let mut linear_src =
+                        get_temp_image_from_buffer(&mut band_buffer, src_view.width(), height);
+                    conversion.load_rows(src_view, (0, offset + top), &mut linear_src);
+                    let mut temp_rows = TypedCroppedImageMut::from_ref(
                         &mut temp_image,
-                        x_first,
-                        vert_coeffs,
-                        self.cpu_extensions,
-                    );
-
-                    // Shift bounds for the horizontal pass
-                    horiz_coeffs
-                        .bounds
-                        .iter_mut()
-                        .for_each(|b| b.start -= x_first);
-
-                    P::horiz_convolution(
-                        &temp_image,
-                        dst_view,
+                        0,
+                        top,
+                        *temp_width,
+                        height,
+                    )
+                    .unwrap();
+                    P::Linear::horiz_convolution(
+                        &linear_src,
+                        &mut temp_rows,
                         0,
                         horiz_coeffs,
-                        self.cpu_extensions,
-                    );
-                } else {
-                    let y_first = vert_coeffs.bounds[0].start;
-                    // Last used row in the source image
-                    let last_y_bound = vert_coeffs.bounds.last().unwrap();
-                    let y_last = last_y_bound.start + last_y_bound.size;
-                    let temp_height = y_last - y_first;
-                    let mut temp_image = get_temp_image_from_buffer(
-                        &mut self.convolution_buffer,
-                        dst_width,
-                        temp_height,
+                        cpu_extensions,
                     );
-                    P::horiz_convolution(
-                        src_view,
-                        &mut temp_image,
-                        y_first,
-                        horiz_coeffs,
-                        self.cpu_extensions,
+                }
+                vert_convolution_in_linear_bands(
+                    &mut conversion,
+                    &temp_image,
+                    dst_view,
+                    vert_coeffs,
+                    &mut band_buffer,
+                    cpu_extensions,
+                );
+            }
+            ConvolutionPasses::VerticalFirst { .. } => {
+                // Pixels in linear color space don't have `u8` components,
+                // so the horizontal pass is always done first.
+                unreachable!("the vertical pass is first only for pixels with u8 components");
+            }
+            ConvolutionPasses::IntegerBox {
+                left,
+                top: box_top,
+                width,
+                height: box_height,
+                factor_x,
+                factor_y,
+            } => {
+                for (top, height) in linear_bands(dst_height) {
+                    let src_top = top * factor_y;
+                    let src_height = (height * factor_y).min(box_height - src_top);
+                    let mut linear_src =
+                        get_temp_image_from_buffer(&mut band_buffer, *width, src_height);
+                    conversion.load_rows(src_view, (*left, box_top + src_top), &mut linear_src);
+                    let mut linear_dst =
+                        get_temp_image_from_buffer(&mut temp_buffer, dst_width, height);
+                    box_reduce(
+                        &linear_src,
+                        &mut linear_dst,
+                        (0, 0),
+                        (*width, src_height),
+                        (*factor_x, *factor_y),
+                        cpu_extensions,
                     );
-
-                    // Shift bounds for the vertical pass
-                    vert_coeffs
-                        .bounds
-                        .iter_mut()
-                        .for_each(|b| b.start -= y_first);
-                    P::vert_convolution(&temp_image, dst_view, 0, vert_coeffs, self.cpu_extensions);
+                    conversion.store_rows(&mut linear_dst, dst_view, top);
                 }
             }
-            (Some(horiz_coeffs), None) => {
+        }
+
+        self.alpha_buffer = straight_buffer;
+        self.color_buffer = band_buffer;
+        self.convolution_buffer = temp_buffer;
+        self.color_converter = Some(converter);
+    }
+
+    fn do_convolution<P: PixelTrait>(
+        &mut self,
+        src_view: &impl ImageView<Pixel = P>,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+        passes: &ConvolutionPasses,
+    ) {
+        match passes {
+            ConvolutionPasses::None => {}
+            ConvolutionPasses::Horizontal { offset, coeffs } => {
+                P::horiz_convolution(src_view, dst_view, *offset, coeffs, self.cpu_extensions);
+            }
+            ConvolutionPasses::Vertical { offset, coeffs } => {
+                P::vert_convolution(src_view, dst_view, *offset, coeffs, self.cpu_extensions);
+            }
+            ConvolutionPasses::HorizontalFirst {
+                offset,
+                horiz_coeffs,
+                vert_coeffs,
+                temp_width,
+                temp_height,
+            } => {
+                let mut temp_image = get_temp_image_from_buffer(
+                    &mut self.convolution_buffer,
+                    *temp_width,
+                    *temp_height,
+                );
                 P::horiz_convolution(
                     src_view,
-                    dst_view,
-                    crop_box.top as u32, // crop_box.top is exactly an integer if the vertical pass is not required
+                    &mut temp_image,
+                    *offset,
                     horiz_coeffs,
                     self.cpu_extensions,
                 );
+                P::vert_convolution(&temp_image, dst_view, 0, vert_coeffs, self.cpu_extensions);
             }
-            (None, Some(vert_coeffs)) => {
+            ConvolutionPasses::VerticalFirst {
+                offset,
+                vert_coeffs,
+                horiz_coeffs,
+                temp_width,
+                temp_height,
+            } => {
+                let mut temp_image = get_temp_image_from_buffer(
+                    &mut self.convolution_buffer,
+                    *temp_width,
+                    *temp_height,
+                );
                 P::vert_convolution(
                     src_view,
-                    dst_view,
-                    crop_box.left as u32, // crop_box.left is exactly an integer if the horizontal pass is not required
+                    &mut temp_image,
+                    *offset,
                     vert_coeffs,
                     self.cpu_extensions,
                 );
+                P::horiz_convolution(&temp_image, dst_view, 0, horiz_coeffs, self.cpu_extensions);
+            }
+            ConvolutionPasses::IntegerBox {
+                left,
+                top,
+                width,
+                height,
+                factor_x,
+                factor_y,
+            } => {
+                box_reduce(
+                    src_view,
+                    dst_view,
+                    (*left, *top),
+                    (*width, *height),
+                    (*factor_x, *factor_y),
+                    self.cpu_extensions,
+                );
             }
-            _ => {}
         }
     }
+}
+
+/// Count of rows that are converted into linear color space
+/// or back at once.
+const LINEAR_BAND_HEIGHT: u32 = 32;
+
+/// Returns the top row and height of every band of rows of an image.
+fn linear_bands(height: u32) -> impl Iterator<Item = (u32, u32)> {
+    (0..height)
+        .step_by(LINEAR_BAND_HEIGHT as usize)
+        .map(move |top| (top, LINEAR_BAND_HEIGHT.min(height - top)))
+}
+
+/// Conversion of rows of images into linear color space and back.
+struct LinearConversion<'a> {
+    converter: &'a mut ColorSpaceConverter,
+    mul_div: &'a MulDiv,
+    mul_div_alpha: bool,
+    src_alpha_mode: AlphaMode,
+    dst_alpha_mode: AlphaMode,
+    /// Buffer for source rows with straight alpha.
+    straight_buffer: &'a mut Vec<u8>,
+}
 
-    fn resample_super_sampling<P: PixelTrait>(
+impl LinearConversion<'_> {
+    /// Converts rows of the source image starting from the pixel
+    /// `(left, top)` into linear color space and stores them into
+    /// `dst_view`. Colors are multiplied by alpha if it is required.
+    fn load_rows<P: PixelTrait>(
         &mut self,
-        cropped_src_view: &CroppedSrcImageView<impl ImageView<Pixel = P>>,
-        dst_view: &mut impl ImageViewMut<Pixel = P>,
-        filter_type: FilterType,
-        multiplicity: u8,
-        use_alpha: bool,
+        src_view: &impl ImageView<Pixel = P>,
+        (left, top): (u32, u32),
+        dst_view: &mut impl ImageViewMut<Pixel = P::Linear>,
     ) {
-        let crop_box = cropped_src_view.crop_box();
-        let dst_width = dst_view.width();
-        let dst_height = dst_view.height();
-        if dst_width == 0 || dst_height == 0 || crop_box.width <= 0. || crop_box.height <= 0. {
-            return;
+        let (width, height) = (dst_view.width(), dst_view.height());
+        let src_rows = TypedCroppedImage::from_ref(src_view, left, top, width, height).unwrap();
+
+        // Colors with premultiplied alpha can't be mapped into linear
+        // color space, so they are divided by alpha first.
+        let mut is_mapped = false;
+        if self.mul_div_alpha && self.src_alpha_mode == AlphaMode::Premultiplied {
+            let mut straight_rows =
+                get_temp_image_from_buffer::<P>(self.straight_buffer, width, height);
+            if self
+                .mul_div
+                .divide_alpha_typed(&src_rows, &mut straight_rows)
+                .is_ok()
+            {
+                P::into_linear(self.converter, &straight_rows, dst_view);
+                is_mapped = true;
+            }
+        }
+        if !is_mapped {
+            P::into_linear(self.converter, &src_rows, dst_view);
         }
 
-        let width_scale = crop_box.width / dst_width as f64;
-        let height_scale = crop_box.height / dst_height as f64;
-        // It makes sense to resize the image in two steps only if the image
-        // size is greater than the required size by multiplicity times.
-        let factor = width_scale.min(height_scale) / multiplicity as f64;
-        if factor > 1.2 {
-            // The first step is resizing the source image by the fastest algorithm.
-            // The temporary image will be about ``multiplicity`` times larger
-            // than required.
-            let tmp_width = (crop_box.width / factor).round() as u32;
-            let tmp_height = (crop_box.height / factor).round() as u32;
-
-            let mut super_sampling_buffer = core::mem::take(&mut self.super_sampling_buffer);
-            let mut tmp_img =
-                get_temp_image_from_buffer(&mut super_sampling_buffer, tmp_width, tmp_height);
-            resample_nearest(cropped_src_view, &mut tmp_img);
-            // The second step is resizing the temporary image with a convolution.
-            let cropped_tmp_img = CroppedSrcImageView::new(&tmp_img);
-            self.resample_convolution(&cropped_tmp_img, dst_view, filter_type, true, use_alpha);
-            self.super_sampling_buffer = super_sampling_buffer;
-        } else {
-            // There is no point in doing the resizing in two steps.
-            // We immediately resize the original image with a convolution.
-            self.resample_convolution(cropped_src_view, dst_view, filter_type, true, use_alpha);
+        // Alpha channel is not mapped, so multiplication and division
+        // by alpha are performed in-place over rows in linear color space.
+        if self.mul_div_alpha {
+            self.mul_div.multiply_alpha_inplace_typed(dst_view).unwrap();
+        }
+    }
+
+    /// Converts rows from linear color space into rows of the destination
+    /// image starting from the row `top`.
+    fn store_rows<P: PixelTrait>(
+        &mut self,
+        src_view: &mut impl ImageViewMut<Pixel = P::Linear>,
+        dst_view: &mut impl ImageViewMut<Pixel = P>,
+        top: u32,
+    ) {
+        let (width, height) = (src_view.width(), src_view.height());
+        if self.mul_div_alpha {
+            self.mul_div.divide_alpha_inplace_typed(src_view).unwrap();
+        }
+        let mut dst_rows = TypedCroppedImageMut::from_ref(dst_view, 0, top, width, height).unwrap();
+        P::from_linear(self.converter, src_view, &mut dst_rows);
+        if self.mul_div_alpha && self.dst_alpha_mode == AlphaMode::Premultiplied {
+            self.mul_div
+                .multiply_alpha_inplace_typed(&mut dst_rows)
+                .unwrap();
         }
     }
 }
 
+/// Does the vertical pass of convolution by bands of rows of the result
+/// and converts every band into the destination image.
+fn vert_convolution_in_linear_bands<P: PixelTrait>(
+    conversion: &mut LinearConversion,
+    src_view: &impl ImageView<Pixel = P::Linear>,
+    dst_view: &mut impl ImageViewMut<Pixel = P>,
+    coeffs: &Coefficients,
+    band_buffer: &mut Vec<u8>,
+    cpu_extensions: CpuExtensions,
+) {
+    let max_weight = coeffs.max_weight();
+    let mut band_coeffs = Coefficients::default();
+    for (top, height) in linear_bands(dst_view.height()) {
+        coeffs.copy_part_into(top as usize, height as usize, max_weight, &mut band_coeffs);
+        let mut linear_dst = get_temp_image_from_buffer(band_buffer, dst_view.width(), height);
+        P::Linear::vert_convolution(src_view, &mut linear_dst, 0, &band_coeffs, cpu_extensions);
+        conversion.store_rows(&mut linear_dst, dst_view, top);
+    }
+}
+
 /// Creates an inner image container from part of the given buffer.
 /// Buffer may be expanded if its size is less than required for the image.
 fn get_temp_image_from_buffer<P: PixelTrait>(
@@ -552,29 +1455,16 @@ fn get_temp_image_from_buffer<P: PixelTrait>(
 }
 
 fn resample_nearest<P: InnerPixel>(
-    cropped_src_view: &CroppedSrcImageView<impl ImageView<Pixel = P>>,
+    src_view: &impl ImageView<Pixel = P>,
     dst_view: &mut impl ImageViewMut<Pixel = P>,
+    nearest_plan: &NearestPlan,
 ) {
-    let (dst_width, dst_height) = (dst_view.width(), dst_view.height());
-    let src_view = cropped_src_view.image_view();
-    let crop_box = cropped_src_view.crop_box();
-    if dst_width == 0 || dst_height == 0 || crop_box.width <= 0. || crop_box.height <= 0. {
-        return;
-    }
-    let x_scale = crop_box.width / dst_width as f64;
-    let y_scale = crop_box.height / dst_height as f64;
-
-    // Pretabulate horizontal pixel positions
-
-    let x_in_start = crop_box.left + x_scale * 0.5;
-    let max_src_x = src_view.width() as usize;
-    let x_in_tab: Vec<usize> = (0..dst_width)
-        .map(|x| ((x_in_start + x_scale * x as f64) as usize).min(max_src_x))
-        .collect();
-
-    let y_in_start = crop_box.top + y_scale * 0.5;
-
-    let src_rows = src_view.iter_rows_with_step(y_in_start, y_scale, dst_height);
+    let x_in_tab = &nearest_plan.x_in_tab;
+    let src_rows = src_view.iter_rows_with_step(
+        nearest_plan.y_in_start,
+        nearest_plan.y_scale,
+        nearest_plan.dst_height,
+    );
     let dst_rows = dst_view.iter_rows_mut(0);
 
     #[cfg(feature = "rayon")]
@@ -600,33 +1490,40 @@ fn resample_nearest<P: InnerPixel>(
     }
 }
 
-/// Copy pixels from src_view into dst_view.
-pub(crate) fn copy_image<S, P: PixelTrait>(
+/// Copy pixels from src_view into the center of dst_view and fill borders
+/// around them with pixels from the opposite sides of src_view.
+fn wrap_image<P: InnerPixel>(
+    src_view: &impl ImageView<Pixel = P>,
+    dst_view: &mut impl ImageViewMut<Pixel = P>,
+    borders: WrapBorders,
+) {
+    let src_width = src_view.width() as i64;
+    let src_height = src_view.height() as i64;
+    let border_x = borders.horizontal as i64;
+    let border_y = borders.vertical as i64;
+    for (y, dst_row) in (0i64..).zip(dst_view.iter_rows_mut(0)) {
+        let src_y = (y - border_y).rem_euclid(src_height) as u32;
+        let Some(src_row) = src_view.iter_rows(src_y).next() else {
+            break;
+        };
+        for (x, dst_pixel) in (0i64..).zip(dst_row.iter_mut()) {
+            let src_x = (x - border_x).rem_euclid(src_width) as usize;
+            *dst_pixel = src_row[src_x];
+        }
+    }
+}
+
+/// Copy pixels from the cropped part of src_view into dst_view.
+fn copy_image<S, P: PixelTrait>(
     cropped_src_view: &CroppedSrcImageView<S>,
     dst_view: &mut impl ImageViewMut<Pixel = P>,
-) -> Result<(), DifferentDimensionsError>
-where
+) where
     S: ImageView<Pixel = P>,
 {
-    let crop_box = cropped_src_view.crop_box();
-    if crop_box.left != crop_box.left.round()
-        || crop_box.top != crop_box.top.round()
-        || crop_box.width != crop_box.width.round()
-        || crop_box.height != crop_box.height.round()
-    {
-        // The crop box has a fractional part in some his part
-        return Err(DifferentDimensionsError);
-    }
-    if dst_view.width() != crop_box.width as u32 || dst_view.height() != crop_box.height as u32 {
-        return Err(DifferentDimensionsError);
-    }
-    if dst_view.width() > 0 && dst_view.height() > 0 {
-        dst_view
-            .iter_rows_mut(0)
-            .zip(iter_cropped_rows(cropped_src_view))
-            .for_each(|(d, s)| d.copy_from_slice(s));
-    }
-    Ok(())
+    dst_view
+        .iter_rows_mut(0)
+        .zip(iter_cropped_rows(cropped_src_view))
+        .for_each(|(d, s)| d.copy_from_slice(s));
 }
 
 fn iter_cropped_rows<'a, S: ImageView>(
@@ -0,0 +1,387 @@
+use crate::compat::*;
+use crate::convolution::{get_filter_func, EdgeMode, FilterType};
+use crate::mul_div::is_supported_pixel_type;
+use crate::pixels::{f16, InnerPixel};
+use crate::{AlphaPosition, ImageView, PixelTrait, ResizeError};
+
+/// Component of a pixel that can be interpolated by [Sampler].
+pub(crate) trait SampleComponent: Copy {
+    fn to_f64(self) -> f64;
+
+    /// Returns the nearest value of the component type.
+    fn from_f64(v: f64) -> Self;
+}
+
+impl SampleComponent for u8 {
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    #[inline(always)]
+    fn from_f64(v: f64) -> Self {
+        v.round().clamp(0., u8::MAX as f64) as u8
+    }
+}
+
+impl SampleComponent for u16 {
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    #[inline(always)]
+    fn from_f64(v: f64) -> Self {
+        v.round().clamp(0., u16::MAX as f64) as u16
+    }
+}
+
+impl SampleComponent for i32 {
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    #[inline(always)]
+    fn from_f64(v: f64) -> Self {
+        v.round().clamp(i32::MIN as f64, i32::MAX as f64) as i32
+    }
+}
+
+impl SampleComponent for u32 {
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    #[inline(always)]
+    fn from_f64(v: f64) -> Self {
+        v.round().clamp(0., u32::MAX as f64) as u32
+    }
+}
+
+impl SampleComponent for f32 {
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    #[inline(always)]
+    fn from_f64(v: f64) -> Self {
+        v as f32
+    }
+}
+
+impl SampleComponent for f64 {
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    #[inline(always)]
+    fn from_f64(v: f64) -> Self {
+        v
+    }
+}
+
+impl SampleComponent for f16 {
+    #[inline(always)]
+    fn to_f64(self) -> f64 {
+        f16::to_f64(self)
+    }
+
+    #[inline(always)]
+    fn from_f64(v: f64) -> Self {
+        f16::from_f64(v)
+    }
+}
+
+/// Interpolates pixels of an image at arbitrary points
+/// with help of a convolution filter.
+///
+/// Coordinates of points use the same system as [CropBox](crate::CropBox):
+/// the pixel `(x, y)` covers the area from `(x, y)` to `(x + 1, y + 1)`,
+/// so its center has coordinates `(x + 0.5, y + 0.5)`.
+///
+/// The kernel of the filter has a fixed size like in
+/// [ResizeAlg::Interpolation](crate::ResizeAlg::Interpolation).
+#[derive(Debug)]
+pub struct Sampler<'a, V: ImageView> {
+    view: &'a V,
+    filter_type: FilterType,
+    edge_mode: EdgeMode,
+    mul_div_alpha: bool,
+    alpha_position: AlphaPosition,
+    /// Scales of the kernel along axes of the image.
+    kernel_scale: (f64, f64),
+}
+
+impl<V: ImageView> Clone for Sampler<'_, V> {
+    fn clone(&self) -> Self {
+        Self {
+            view: self.view,
+            filter_type: self.filter_type.clone(),
+            edge_mode: self.edge_mode,
+            mul_div_alpha: self.mul_div_alpha,
+            alpha_position: self.alpha_position,
+            kernel_scale: self.kernel_scale,
+        }
+    }
+}
+
+impl<'a, V> Sampler<'a, V>
+where
+    V: ImageView<Pixel: PixelTrait>,
+{
+    /// Creates a sampler of the image with the given filter.
+    ///
+    /// Returns [ResizeError::InvalidFilterParameters] if parameters
+    /// of the filter are out of range.
+    pub fn new(view: &'a V, filter_type: FilterType) -> Result<Self, ResizeError> {
+        if !filter_type.is_valid() {
+            return Err(ResizeError::InvalidFilterParameters);
+        }
+        Ok(Self {
+            view,
+            filter_type,
+            edge_mode: EdgeMode::default(),
+            mul_div_alpha: true,
+            alpha_position: AlphaPosition::Last,
+            kernel_scale: (1., 1.),
+        })
+    }
+
+    /// Set mode of handling pixels outside the image.
+    ///
+    /// Default: `EdgeMode::Renormalize`.
+    pub fn edge_mode(&self, edge_mode: EdgeMode) -> Self {
+        let mut sampler = self.clone();
+        sampler.edge_mode = edge_mode;
+        sampler
+    }
+
+    /// Enable or disable consideration of the alpha channel.
+    /// Colors of pixels are weighted by their alpha if it is enabled,
+    /// so colors of transparent pixels don't affect the result.
+    ///
+    /// Default: `true`.
+    pub fn use_alpha(&self, v: bool) -> Self {
+        let mut sampler = self.clone();
+        sampler.mul_div_alpha = v;
+        sampler
+    }
+
+    /// Set position of the alpha channel among components of pixels.
+    ///
+    /// Default: `AlphaPosition::Last`.
+    pub fn alpha_position(&self, alpha_position: AlphaPosition) -> Self {
+        let mut sampler = self.clone();
+        sampler.alpha_position = alpha_position;
+        sampler
+    }
+
+    /// Set scales of the kernel size along the horizontal
+    /// and vertical axes of the image.
+    pub(crate) fn kernel_scale(&self, scale_x: f64, scale_y: f64) -> Self {
+        let mut sampler = self.clone();
+        sampler.kernel_scale = (scale_x, scale_y);
+        sampler
+    }
+
+    #[inline]
+    pub(crate) fn view(&self) -> &'a V {
+        self.view
+    }
+
+    /// Returns a sampler with the same parameters for another image.
+    pub(crate) fn with_view<'b, W>(&self, view: &'b W) -> Sampler<'b, W>
+    where
+        W: ImageView<Pixel: PixelTrait>,
+    {
+        Sampler {
+            view,
+            filter_type: self.filter_type.clone(),
+            edge_mode: self.edge_mode,
+            mul_div_alpha: self.mul_div_alpha,
+            alpha_position: self.alpha_position,
+            kernel_scale: self.kernel_scale,
+        }
+    }
+
+    /// Returns the interpolated pixel at the point `(x, y)`.
+    ///
+    /// The result is a pixel with zero components if no pixels
+    /// of the image are covered by the kernel of the filter.
+    pub fn sample(&self, x: f64, y: f64) -> V::Pixel {
+        self.sample_with_kernel(x, y, &self.kernel(), &mut Buffers::default())
+    }
+
+    /// Returns interpolated pixels at the given points.
+    pub fn sample_many(&self, points: &[(f64, f64)]) -> Vec<V::Pixel> {
+        let kernel = self.kernel();
+        let mut buffers = Buffers::default();
+        points
+            .iter()
+            .map(|&(x, y)| self.sample_with_kernel(x, y, &kernel, &mut buffers))
+            .collect()
+    }
+
+    /// Stores interpolated pixels at the given points into `dst_pixels`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst_pixels` and `points` have different lengths.
+    pub fn sample_into(&self, points: &[(f64, f64)], dst_pixels: &mut [V::Pixel]) {
+        assert_eq!(points.len(), dst_pixels.len());
+        let kernel = self.kernel();
+        let mut buffers = Buffers::default();
+        for (&(x, y), dst_pixel) in points.iter().zip(dst_pixels) {
+            *dst_pixel = self.sample_with_kernel(x, y, &kernel, &mut buffers);
+        }
+    }
+
+    /// Returns the filter function of the sampler with its support.
+    /// It is resolved once and shared between all samples of an image.
+    pub(crate) fn kernel(&self) -> Kernel<impl Fn(f64) -> f64 + Send + Sync> {
+        let (filter, support) = get_filter_func(self.filter_type.clone());
+        Kernel { filter, support }
+    }
+
+    pub(crate) fn sample_with_kernel(
+        &self,
+        x: f64,
+        y: f64,
+        kernel: &Kernel<impl Fn(f64) -> f64>,
+        buffers: &mut Buffers,
+    ) -> V::Pixel {
+        let mut pixel = V::Pixel::empty();
+        let (scale_x, scale_y) = self.kernel_scale;
+        let x_norm = self.weights(x, self.view.width(), kernel, scale_x, &mut buffers.x);
+        let y_norm = self.weights(y, self.view.height(), kernel, scale_y, &mut buffers.y);
+        let norm = x_norm * y_norm;
+        if buffers.x.is_empty() || buffers.y.is_empty() || norm == 0. {
+            return pixel;
+        }
+
+        let count = V::Pixel::count_of_components();
+        let has_alpha = self.mul_div_alpha && is_supported_pixel_type(V::Pixel::pixel_type());
+        let alpha_index = match self.alpha_position {
+            AlphaPosition::Last => count - 1,
+            AlphaPosition::First => 0,
+        };
+        let mut sums = [0f64; 4];
+        for &(src_y, wy) in buffers.y.iter() {
+            let Some(row) = self.view.iter_rows(src_y).next() else {
+                continue;
+            };
+            let components = V::Pixel::components(row);
+            for &(src_x, wx) in buffers.x.iter() {
+                let first = src_x as usize * count;
+                let src_pixel = &components[first..first + count];
+                let w = wx * wy;
+                if has_alpha {
+                    let alpha = src_pixel[alpha_index].to_f64();
+                    let color_w = w * alpha;
+                    for (i, (sum, c)) in sums.iter_mut().zip(src_pixel).enumerate() {
+                        if i == alpha_index {
+                            *sum += w * alpha;
+                        } else {
+                            *sum += color_w * c.to_f64();
+                        }
+                    }
+                } else {
+                    for (sum, c) in sums.iter_mut().zip(src_pixel) {
+                        *sum += w * c.to_f64();
+                    }
+                }
+            }
+        }
+
+        let dst_components = V::Pixel::components_mut(core::slice::from_mut(&mut pixel));
+        if has_alpha {
+            // Colors are weighted by alpha, so they are normalized
+            // by the sum of weighted alpha values.
+            let alpha_sum = sums[alpha_index];
+            for (i, (dst, sum)) in dst_components.iter_mut().zip(sums).enumerate() {
+                if i == alpha_index {
+                    *dst = SampleComponent::from_f64(alpha_sum / norm);
+                } else if alpha_sum != 0. {
+                    *dst = SampleComponent::from_f64(sum / alpha_sum);
+                }
+            }
+        } else {
+            for (dst, sum) in dst_components.iter_mut().zip(sums) {
+                *dst = SampleComponent::from_f64(sum / norm);
+            }
+        }
+        pixel
+    }
+
+    /// Stores indexes and weights of source pixels covered by
+    /// the kernel centered at `coord` into `weights`.
+    /// Returns the value used to normalize weights.
+    fn weights(
+        &self,
+        coord: f64,
+        size: u32,
+        kernel: &Kernel<impl Fn(f64) -> f64>,
+        scale: f64,
+        weights: &mut Vec<(u32, f64)>,
+    ) -> f64 {
+        weights.clear();
+        if size == 0 {
+            return 0.;
+        }
+        let center = coord - 0.5;
+        let radius = kernel.support * scale;
+        let recip_scale = 1. / scale;
+        let x_min = (coord - radius).floor() as i64;
+        let x_max = (coord + radius).ceil() as i64;
+        let mut norm = 0.;
+        for x in x_min..x_max {
+            let w = (kernel.filter)((x as f64 - center) * recip_scale);
+            if w == 0. {
+                continue;
+            }
+            match edge_index(self.edge_mode, x, size) {
+                Some(index) => {
+                    weights.push((index, w));
+                    norm += w;
+                }
+                // Pixels outside the image are equal to zero,
+                // but their weights are used for normalization.
+                None if self.edge_mode == EdgeMode::Constant => norm += w,
+                None => (),
+            }
+        }
+        norm
+    }
+}
+
+/// Returns the index of a source pixel that is used instead
+/// of the pixel `x` or `None` if the pixel must be ignored.
+pub(crate) fn edge_index(edge_mode: EdgeMode, x: i64, size: u32) -> Option<u32> {
+    match edge_mode {
+        EdgeMode::Wrap if size > 0 => Some(x.rem_euclid(size as i64) as u32),
+        edge_mode => edge_mode.source_index(x, size),
+    }
+}
+
+/// Returns the index of a source pixel that covers the point `coord`
+/// or `None` if the pixel must be ignored.
+pub(crate) fn nearest_index(edge_mode: EdgeMode, coord: f64, size: u32) -> Option<u32> {
+    edge_index(edge_mode, coord.floor() as i64, size)
+}
+
+/// Filter function with its support.
+pub(crate) struct Kernel<F> {
+    filter: F,
+    support: f64,
+}
+
+/// Buffers for weights of source pixels reused between samples.
+#[derive(Default)]
+pub(crate) struct Buffers {
+    x: Vec<(u32, f64)>,
+    y: Vec<(u32, f64)>,
+}
@@ -0,0 +1,811 @@
+use crate::color::color_space::{linear_pixel_type, ColorSpaceConverter};
+use crate::compat::*;
+use crate::convolution::{Coefficients, EdgeMode};
+use crate::images::{TypedImage, TypedImageRef};
+use crate::mul_div::is_supported_pixel_type;
+use crate::pixels::InnerPixel;
+use crate::resize_plan::{
+    axis_kernels, check_alpha_position, check_kernel_scale, check_kernels, integer_box_factors,
+    is_copy_possible, precompute_passes_coefficients, AlphaConversion, NearestPlan, PaddingPlan,
+};
+use crate::{
+    AlphaMode, ColorSpace, CpuExtensions, ImageError, ImageView, MulDiv, MulDivImagesError,
+    Orientation, PixelTrait, ResizeAlg, ResizeError, ResizeOptions,
+};
+
+/// Resizer that receives rows of the source image one by one and returns
+/// rows of the destination image as soon as all source rows required
+/// to calculate them have been received.
+///
+/// It doesn't need the whole source image in memory. Only a few rows
+/// of the source image are stored in an internal ring buffer. The number
+/// of these rows is equal to the size of the filter window by the vertical
+/// axis. The horizontal pass of convolution is applied to rows before storing
+/// them into the buffer, so the buffer contains rows with the width of
+/// the destination image.
+///
+/// Images with `u8` components are an exception. [Resizer](crate::Resizer)
+/// does the vertical pass first for them, and intermediate values are rounded
+/// to `u8` between passes. To get exactly the same result, the buffer stores
+/// the columns of source rows used by the horizontal pass, and this pass is
+/// applied to rows calculated by the vertical pass. So for downscaling of
+/// these images the buffer is larger and the horizontal pass is slower.
+///
+/// Supported algorithms are `Nearest`, `Convolution`, `Interpolation`,
+/// `PerAxisConvolution` and `Area`.
+/// The `Wrap` edge mode is not supported.
+/// Orientations other than `Orientation::Normal` are not supported too,
+/// because rows of the destination image can't be calculated in order.
+/// The `SuperSampling` and `ReducingGap` algorithms are replaced by
+/// `Convolution` with the same filter because the first step of these
+/// algorithms requires an additional temporary image.
+/// The `IntegerBox` algorithm is replaced by `Area`, so results of both
+/// algorithms may differ from results of [Resizer](crate::Resizer)
+/// by rounding errors if sizes of the cropped source image are multiples
+/// of sizes of the destination image.
+///
+/// If [ResizeOptions::color_space] is not linear, every received row
+/// is converted into linear color space and every calculated row
+/// is converted back, as [Resizer](crate::Resizer) does.
+///
+/// # Examples
+///
+/// ```
+/// use fast_image_resize::pixels::U8x4;
+/// use fast_image_resize::StreamingResizer;
+///
+/// let (src_width, src_height) = (640, 480);
+/// let mut resizer = StreamingResizer::<U8x4>::new(src_width, src_height, 320, 240, None).unwrap();
+///
+/// let src_row = vec![U8x4::new([10, 20, 30, 255]); src_width as usize];
+/// let mut dst_rows_count = 0;
+/// for _ in 0..src_height {
+///     for dst_row in resizer.push_row(&src_row).unwrap() {
+///         assert_eq!(dst_row.len(), 320);
+///         dst_rows_count += 1;
+///     }
+/// }
+/// assert_eq!(dst_rows_count, 240);
+/// assert!(resizer.is_finished());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingResizer<P: PixelTrait> {
+    rows_resizer: RowsResizerKind<P>,
+    padding: Option<RowsPadding<P>>,
+}
+
+/// Padding around rows calculated by the rows resizer.
+#[derive(Debug, Clone)]
+struct RowsPadding<P> {
+    plan: PaddingPlan,
+    fill_color: P,
+    dst_width: u32,
+    dst_height: u32,
+    buffer: Vec<P>,
+    emitted_rows: u32,
+}
+
+impl<P: PixelTrait> RowsPadding<P> {
+    /// Adds padding to rows calculated by the rows resizer.
+    /// `first_row` is the index of the first of these rows
+    /// inside the resized image.
+    fn pad_rows(&mut self, inner_pixels: &[P], first_row: u32) -> &[P] {
+        self.buffer.clear();
+        let inner_width = self.plan.width as usize;
+        if inner_pixels.is_empty() || inner_width == 0 {
+            return &self.buffer;
+        }
+        let dst_width = self.dst_width as usize;
+        let left = self.plan.left as usize;
+        let right = dst_width - left - inner_width;
+        let rows_count = (inner_pixels.len() / inner_width) as u32;
+
+        if first_row == 0 {
+            let top_size = self.plan.top as usize * dst_width;
+            self.buffer.resize(top_size, self.fill_color);
+        }
+        for row in inner_pixels.chunks_exact(inner_width) {
+            self.buffer
+                .extend(core::iter::repeat_n(self.fill_color, left));
+            self.buffer.extend_from_slice(row);
+            self.buffer
+                .extend(core::iter::repeat_n(self.fill_color, right));
+        }
+        if first_row + rows_count >= self.plan.height {
+            let bottom_rows = self.dst_height - self.plan.top - self.plan.height;
+            let new_len = self.buffer.len() + bottom_rows as usize * dst_width;
+            self.buffer.resize(new_len, self.fill_color);
+        }
+        self.emitted_rows += (self.buffer.len() / dst_width) as u32;
+        &self.buffer
+    }
+}
+
+#[derive(Debug, Clone)]
+enum RowsResizerKind<P: PixelTrait> {
+    Direct(RowsResizer<P>),
+    /// Rows are converted into linear color space before resizing
+    /// and converted back after it.
+    Linear {
+        rows_resizer: RowsResizer<P::Linear>,
+        converter: ColorSpaceConverter,
+        mul_div: MulDiv,
+        /// Received rows have premultiplied alpha, so they are divided
+        /// by alpha before conversion into linear color space.
+        divide_src_alpha: bool,
+        /// Calculated rows are multiplied by alpha after conversion
+        /// from linear color space.
+        multiply_dst_alpha: bool,
+        straight_row: Vec<P>,
+        linear_row: Vec<P::Linear>,
+        dst_buffer: Vec<P>,
+    },
+}
+
+macro_rules! rows_resizer {
+    ($self: ident, $r: ident => $e: expr) => {
+        match &$self.rows_resizer {
+            RowsResizerKind::Direct($r) => $e,
+            RowsResizerKind::Linear { rows_resizer: $r, .. } => $e,
+        }
+    };
+}
+
+impl<P: PixelTrait> StreamingResizer<P> {
+    /// Creates a resizer for a source image with size `src_width`x`src_height`
+    /// and a destination image with size `dst_width`x`dst_height`.
+    pub fn new<'o>(
+        src_width: u32,
+        src_height: u32,
+        dst_width: u32,
+        dst_height: u32,
+        options: impl Into<Option<&'o ResizeOptions>>,
+    ) -> Result<Self, ResizeError> {
+        let default_options = ResizeOptions::default();
+        let options = options.into().unwrap_or(&default_options);
+        if options.orientation != Orientation::Normal {
+            return Err(ResizeError::UnsupportedOrientation);
+        }
+        check_alpha_position(P::pixel_type(), options)?;
+
+        let crop_box = options.get_crop_box(src_width, src_height, dst_width, dst_height);
+        let mut padding = None;
+        let inner_options;
+        let (options, dst_width, dst_height) = match options.padding {
+            Some(padding_options) => {
+                let Some(fill_color) = padding_options.fill_color.pixel::<P>() else {
+                    return Err(ResizeError::PixelTypesAreDifferent);
+                };
+                let plan = PaddingPlan::new(padding_options, crop_box, dst_width, dst_height);
+                padding = Some(RowsPadding {
+                    plan,
+                    fill_color,
+                    dst_width,
+                    dst_height,
+                    buffer: Vec::new(),
+                    emitted_rows: 0,
+                });
+                // The source image is resized into the inner part of
+                // the destination image.
+                inner_options =
+                    options.crop(crop_box.left, crop_box.top, crop_box.width, crop_box.height);
+                (&inner_options, plan.width, plan.height)
+            }
+            None => (options, dst_width, dst_height),
+        };
+
+        // Color space is converted only if the image is resized
+        // with convolution, like Resizer does.
+        let convert_color_space = options.color_space != ColorSpace::Linear
+            && !matches!(options.algorithm, ResizeAlg::Nearest)
+            && crop_box.width > 0.
+            && crop_box.height > 0.
+            && dst_width > 0
+            && dst_height > 0
+            && !is_copy_possible(crop_box, dst_width, dst_height);
+
+        let rows_resizer = if convert_color_space {
+            if linear_pixel_type(P::pixel_type()).is_none() {
+                return Err(ImageError::UnsupportedPixelType.into());
+            }
+            // Rows in linear color space always have straight alpha.
+            let linear_options = options.alpha_modes(AlphaMode::Straight, AlphaMode::Straight);
+            let mul_div_alpha = options.mul_div_alpha && is_supported_pixel_type(P::pixel_type());
+            let mut converter = ColorSpaceConverter::new(options.color_space);
+            converter.set_alpha_position(options.alpha_position);
+            let mut mul_div = MulDiv::new();
+            mul_div.set_alpha_position(options.alpha_position);
+            RowsResizerKind::Linear {
+                rows_resizer: RowsResizer::new(
+                    src_width,
+                    src_height,
+                    dst_width,
+                    dst_height,
+                    &linear_options,
+                )?,
+                converter,
+                mul_div,
+                divide_src_alpha: mul_div_alpha
+                    && options.src_alpha_mode == AlphaMode::Premultiplied,
+                multiply_dst_alpha: mul_div_alpha
+                    && options.dst_alpha_mode == AlphaMode::Premultiplied,
+                straight_row: Vec::new(),
+                linear_row: Vec::new(),
+                dst_buffer: Vec::new(),
+            }
+        } else {
+            RowsResizerKind::Direct(RowsResizer::new(
+                src_width, src_height, dst_width, dst_height, options,
+            )?)
+        };
+        Ok(Self {
+            rows_resizer,
+            padding,
+        })
+    }
+
+    #[inline]
+    pub fn src_width(&self) -> u32 {
+        rows_resizer!(self, r => r.src_width)
+    }
+
+    #[inline]
+    pub fn src_height(&self) -> u32 {
+        rows_resizer!(self, r => r.src_height)
+    }
+
+    #[inline]
+    pub fn dst_width(&self) -> u32 {
+        match &self.padding {
+            Some(padding) => padding.dst_width,
+            None => rows_resizer!(self, r => r.dst_width),
+        }
+    }
+
+    #[inline]
+    pub fn dst_height(&self) -> u32 {
+        match &self.padding {
+            Some(padding) => padding.dst_height,
+            None => rows_resizer!(self, r => r.dst_height),
+        }
+    }
+
+    /// Returns the number of source rows that have been pushed.
+    #[inline]
+    pub fn pushed_rows(&self) -> u32 {
+        rows_resizer!(self, r => r.pushed_rows)
+    }
+
+    /// Returns the number of destination rows that have been returned.
+    #[inline]
+    pub fn emitted_rows(&self) -> u32 {
+        match &self.padding {
+            Some(padding) => padding.emitted_rows,
+            None => rows_resizer!(self, r => r.emitted_rows),
+        }
+    }
+
+    /// Returns `true` if all rows of the destination image have been returned.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        rows_resizer!(self, r => r.emitted_rows >= r.dst_height || r.pushed_rows >= r.src_height)
+    }
+
+    #[inline(always)]
+    pub fn cpu_extensions(&self) -> CpuExtensions {
+        rows_resizer!(self, r => r.cpu_extensions)
+    }
+
+    /// # Safety
+    /// This is unsafe because this method allows you to set a CPU extension
+    /// that is not supported by your CPU.
+    pub unsafe fn set_cpu_extensions(&mut self, extensions: CpuExtensions) {
+        match &mut self.rows_resizer {
+            RowsResizerKind::Direct(r) => r.set_cpu_extensions(extensions),
+            RowsResizerKind::Linear {
+                rows_resizer,
+                mul_div,
+                ..
+            } => {
+                rows_resizer.set_cpu_extensions(extensions);
+                mul_div.set_cpu_extensions(extensions);
+            }
+        }
+    }
+
+    /// Pushes the next row of the source image.
+    ///
+    /// Returns an iterator by the rows of the destination image that may be
+    /// calculated after receiving this row. The iterator may be empty.
+    pub fn push_row(
+        &mut self,
+        src_row: &[P],
+    ) -> Result<impl ExactSizeIterator<Item = &[P]>, ResizeError> {
+        let dst_width = (self.dst_width() as usize).max(1);
+        let inner_width = rows_resizer!(self, r => r.dst_width.max(1)) as usize;
+        let first_inner_row = rows_resizer!(self, r => r.emitted_rows);
+        let inner_pixels = match &mut self.rows_resizer {
+            RowsResizerKind::Direct(rows_resizer) => rows_resizer.push_row(src_row)?,
+            RowsResizerKind::Linear {
+                rows_resizer,
+                converter,
+                mul_div,
+                divide_src_alpha,
+                multiply_dst_alpha,
+                straight_row,
+                linear_row,
+                dst_buffer,
+            } => {
+                linear_row.resize(src_row.len(), Default::default());
+                if rows_resizer.check_row(src_row.len())? {
+                    let mut src_row = src_row;
+                    if *divide_src_alpha {
+                        straight_row.resize(src_row.len(), P::default());
+                        mul_div
+                            .divide_alpha_typed(&row_view(src_row), &mut row_view_mut(straight_row))
+                            .map_err(mul_div_error)?;
+                        src_row = straight_row;
+                    }
+                    P::into_linear(converter, &row_view(src_row), &mut row_view_mut(linear_row));
+                }
+                let linear_pixels = rows_resizer.push_row(linear_row)?;
+                dst_buffer.resize(linear_pixels.len(), P::default());
+                if !linear_pixels.is_empty() {
+                    let rows_count = (linear_pixels.len() / inner_width) as u32;
+                    let linear_view =
+                        TypedImageRef::new(inner_width as u32, rows_count, linear_pixels).unwrap();
+                    let mut dst_view =
+                        TypedImage::from_pixels_slice(inner_width as u32, rows_count, dst_buffer)
+                            .unwrap();
+                    P::from_linear(converter, &linear_view, &mut dst_view);
+                    if *multiply_dst_alpha {
+                        mul_div.multiply_alpha_inplace_typed(&mut dst_view)?;
+                    }
+                }
+                &dst_buffer[..]
+            }
+        };
+        let dst_pixels = match &mut self.padding {
+            Some(padding) => padding.pad_rows(inner_pixels, first_inner_row),
+            None => inner_pixels,
+        };
+        Ok(dst_pixels.chunks_exact(dst_width))
+    }
+}
+
+/// Resizer of rows without conversion of color space.
+#[derive(Debug, Clone)]
+struct RowsResizer<P: PixelTrait> {
+    cpu_extensions: CpuExtensions,
+    mul_div: MulDiv,
+    src_width: u32,
+    src_height: u32,
+    dst_width: u32,
+    dst_height: u32,
+    horiz_pass: HorizPass,
+    vert_pass: VertPass,
+    /// Multiply colors of received rows by alpha.
+    multiply_src_alpha: bool,
+    /// Conversion of alpha mode of calculated rows.
+    dst_alpha_conversion: AlphaConversion,
+    /// Range of source rows required to calculate the destination image.
+    used_src_rows: (u32, u32),
+    ring_buffer: Vec<P>,
+    ring_width: u32,
+    ring_rows: u32,
+    premultiplied_row: Vec<P>,
+    /// Coefficients of the vertical convolution for rows calculated
+    /// from the last received batch of source rows.
+    rows_coeffs: Coefficients,
+    vert_buffer: Vec<P>,
+    dst_buffer: Vec<P>,
+    pushed_rows: u32,
+    emitted_rows: u32,
+}
+
+#[derive(Debug, Clone)]
+enum HorizPass {
+    Copy {
+        left: usize,
+    },
+    Nearest {
+        x_in_tab: Vec<usize>,
+    },
+    Convolution(Coefficients),
+    /// Convolution that is applied after the vertical pass.
+    /// Only columns used by this pass are stored into the ring buffer.
+    ConvolutionAfterVertical {
+        x_first: usize,
+        coeffs: Coefficients,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum VertPass {
+    Copy {
+        top: u32,
+    },
+    Nearest {
+        next_y: f64,
+        step: f64,
+    },
+    Convolution {
+        coeffs: Coefficients,
+        /// Maximal value of all coefficients.
+        max_weight: f64,
+    },
+}
+
+impl<P: PixelTrait> RowsResizer<P> {
+    fn new(
+        src_width: u32,
+        src_height: u32,
+        dst_width: u32,
+        dst_height: u32,
+        options: &ResizeOptions,
+    ) -> Result<Self, ResizeError> {
+        let mut crop_box = options.get_crop_box(src_width, src_height, dst_width, dst_height);
+        if crop_box.width == 0. || crop_box.height == 0. || dst_width == 0 || dst_height == 0 {
+            // Nothing to do if any size of the source or destination image is equal to zero.
+            crop_box.width = 0.;
+            crop_box.height = 0.;
+        } else {
+            crop_box.check_bounds(src_width, src_height)?;
+        }
+
+        let (mut horiz_pass, vert_pass) =
+            match axis_kernels(&options.algorithm, crop_box, dst_width, dst_height) {
+                None => {
+                    let plan = NearestPlan::new(src_width, crop_box, dst_width, dst_height);
+                    (
+                        HorizPass::Nearest {
+                            x_in_tab: plan.x_in_tab,
+                        },
+                        VertPass::Nearest {
+                            next_y: plan.y_in_start,
+                            step: plan.y_scale,
+                        },
+                    )
+                }
+                Some((horiz_kernel, vert_kernel)) => {
+                    if options.edge_mode == EdgeMode::Wrap {
+                        // Wrapping requires the last rows of the source image
+                        // to calculate the first rows of the destination image.
+                        return Err(ResizeError::UnsupportedEdgeMode);
+                    }
+                    if options.algorithm == ResizeAlg::IntegerBox
+                        && integer_box_factors(crop_box, dst_width, dst_height).is_none()
+                    {
+                        return Err(ResizeError::NotIntegerScaleFactor);
+                    }
+                    check_kernels(&horiz_kernel, &vert_kernel)?;
+                    check_kernel_scale(options.kernel_scale)?;
+                    let (horiz_coeffs, vert_coeffs) = precompute_passes_coefficients(
+                        (src_width, src_height),
+                        crop_box,
+                        (dst_width, dst_height),
+                        &horiz_kernel,
+                        &vert_kernel,
+                        options.kernel_scale,
+                        options.edge_mode,
+                    );
+                    (
+                        match horiz_coeffs {
+                            Some(coeffs) => HorizPass::Convolution(coeffs),
+                            None => HorizPass::Copy {
+                                left: crop_box.left as usize,
+                            },
+                        },
+                        match vert_coeffs {
+                            Some(coeffs) => VertPass::Convolution {
+                                max_weight: coeffs.max_weight(),
+                                coeffs,
+                            },
+                            None => VertPass::Copy {
+                                top: crop_box.top as u32,
+                            },
+                        },
+                    )
+                }
+            };
+
+        let mut ring_width = dst_width;
+        if let (HorizPass::Convolution(coeffs), VertPass::Convolution { .. }) =
+            (&mut horiz_pass, &vert_pass)
+        {
+            if P::pixel_type().components_is_u8() {
+                // For u8-based images, Resizer does the vertical pass first.
+                // Use the same order to get the same result.
+                let mut coeffs = core::mem::take(coeffs);
+                let x_first = coeffs.bounds[0].start;
+                let last_x_bound = coeffs.bounds.last().unwrap();
+                ring_width = last_x_bound.start + last_x_bound.size - x_first;
+                coeffs.bounds.iter_mut().for_each(|b| b.start -= x_first);
+                horiz_pass = HorizPass::ConvolutionAfterVertical {
+                    x_first: x_first as usize,
+                    coeffs,
+                };
+            }
+        }
+
+        let is_convolution = matches!(horiz_pass, HorizPass::Convolution(_))
+            || matches!(vert_pass, VertPass::Convolution { .. });
+        let (multiply_src_alpha, dst_alpha_conversion) = if is_convolution {
+            let mul_div_alpha = options.mul_div_alpha && is_supported_pixel_type(P::pixel_type());
+            (
+                mul_div_alpha && options.src_alpha_mode == AlphaMode::Straight,
+                if mul_div_alpha && options.dst_alpha_mode == AlphaMode::Straight {
+                    AlphaConversion::Divide
+                } else {
+                    AlphaConversion::None
+                },
+            )
+        } else {
+            (false, AlphaConversion::new(P::pixel_type(), options))
+        };
+
+        let used_src_rows = if crop_box.width == 0. || crop_box.height == 0. {
+            (0, 0)
+        } else {
+            match &vert_pass {
+                VertPass::Convolution { coeffs, .. } => {
+                    match (coeffs.bounds.first(), coeffs.bounds.last()) {
+                        (Some(first), Some(last)) => (first.start, last.start + last.size),
+                        _ => (0, 0),
+                    }
+                }
+                _ => {
+                    let top = crop_box.top as u32;
+                    let bottom = (crop_box.top + crop_box.height).ceil() as u32;
+                    (top, bottom.min(src_height))
+                }
+            }
+        };
+        let ring_rows = match &vert_pass {
+            VertPass::Convolution { coeffs, .. } => coeffs.window_size as u32,
+            _ => 1,
+        };
+        let ring_rows = ring_rows.min(used_src_rows.1 - used_src_rows.0);
+
+        let mut mul_div = MulDiv::new();
+        mul_div.set_alpha_position(options.alpha_position);
+        Ok(Self {
+            cpu_extensions: Default::default(),
+            mul_div,
+            src_width,
+            src_height,
+            dst_width,
+            dst_height,
+            horiz_pass,
+            vert_pass,
+            multiply_src_alpha,
+            dst_alpha_conversion,
+            used_src_rows,
+            ring_buffer: vec![P::default(); ring_width as usize * ring_rows as usize],
+            ring_width,
+            ring_rows,
+            premultiplied_row: Vec::new(),
+            rows_coeffs: Coefficients::default(),
+            vert_buffer: Vec::new(),
+            dst_buffer: Vec::new(),
+            pushed_rows: 0,
+            emitted_rows: 0,
+        })
+    }
+
+    /// # Safety
+    /// This is unsafe because this method allows you to set a CPU extension
+    /// that is not supported by your CPU.
+    unsafe fn set_cpu_extensions(&mut self, extensions: CpuExtensions) {
+        self.cpu_extensions = extensions;
+        self.mul_div.set_cpu_extensions(extensions);
+    }
+
+    /// Checks the next row of the source image and returns `true`
+    /// if the row is required to calculate the destination image.
+    fn check_row(&self, src_row_len: usize) -> Result<bool, ResizeError> {
+        if src_row_len != self.src_width as usize {
+            return Err(ResizeError::InvalidRowLength);
+        }
+        if self.pushed_rows >= self.src_height {
+            return Err(ResizeError::TooManyRows);
+        }
+        let (first_row, end_row) = self.used_src_rows;
+        Ok(self.pushed_rows >= first_row && self.pushed_rows < end_row)
+    }
+
+    /// Pushes the next row of the source image and returns pixels of
+    /// the destination rows that have been calculated after that.
+    fn push_row(&mut self, src_row: &[P]) -> Result<&[P], ResizeError> {
+        let is_used = self.check_row(src_row.len())?;
+        let src_y = self.pushed_rows;
+        self.pushed_rows += 1;
+
+        let mut count = 0;
+        if is_used {
+            self.store_row(src_y, src_row)?;
+            count = self.calculate_dst_rows(src_y + 1)?;
+            self.emitted_rows += count;
+        }
+        Ok(&self.dst_buffer[..self.dst_width as usize * count as usize])
+    }
+
+    /// Applies the horizontal pass to the source row and stores the result
+    /// into the ring buffer.
+    fn store_row(&mut self, src_y: u32, src_row: &[P]) -> Result<(), ResizeError> {
+        let ring_width = self.ring_width as usize;
+        let slot = (src_y % self.ring_rows) as usize * ring_width;
+        let ring_row = &mut self.ring_buffer[slot..slot + ring_width];
+
+        let mut src_row = src_row;
+        if self.multiply_src_alpha {
+            self.premultiplied_row.resize(src_row.len(), P::default());
+            let src_view = row_view(src_row);
+            let mut dst_view = row_view_mut(&mut self.premultiplied_row);
+            self.mul_div
+                .multiply_alpha_typed(&src_view, &mut dst_view)
+                .map_err(mul_div_error)?;
+            src_row = &self.premultiplied_row;
+        }
+
+        match &self.horiz_pass {
+            HorizPass::Copy { left: x_first }
+            | HorizPass::ConvolutionAfterVertical { x_first, .. } => {
+                ring_row.copy_from_slice(&src_row[*x_first..*x_first + ring_width]);
+            }
+            HorizPass::Nearest { x_in_tab } => {
+                for (&x_in, dst_pixel) in x_in_tab.iter().zip(ring_row.iter_mut()) {
+                    *dst_pixel = src_row[x_in];
+                }
+            }
+            HorizPass::Convolution(coeffs) => {
+                let src_view = row_view(src_row);
+                let mut dst_view = row_view_mut(ring_row);
+                P::horiz_convolution(&src_view, &mut dst_view, 0, coeffs, self.cpu_extensions);
+            }
+        }
+        Ok(())
+    }
+
+    /// Calculates destination rows that require only source rows
+    /// before `end_src_row` and stores them into the destination buffer.
+    /// Returns the number of calculated rows.
+    fn calculate_dst_rows(&mut self, end_src_row: u32) -> Result<u32, ResizeError> {
+        let ring_width = self.ring_width as usize;
+        let first_dst_row = self.emitted_rows;
+        let ring_view = RingBufferView {
+            buffer: &self.ring_buffer,
+            width: self.ring_width,
+            height: end_src_row,
+            ring_rows: self.ring_rows,
+        };
+        let vert_buffer = match self.horiz_pass {
+            HorizPass::ConvolutionAfterVertical { .. } => &mut self.vert_buffer,
+            _ => &mut self.dst_buffer,
+        };
+
+        let count = match &mut self.vert_pass {
+            VertPass::Copy { top } => {
+                let count = (end_src_row - *top)
+                    .saturating_sub(first_dst_row)
+                    .min(self.dst_height - first_dst_row);
+                vert_buffer.resize(ring_width * count as usize, P::default());
+                let src_rows = ring_view.iter_rows(*top + first_dst_row);
+                for (dst_row, src_row) in vert_buffer.chunks_exact_mut(ring_width).zip(src_rows) {
+                    dst_row.copy_from_slice(src_row);
+                }
+                count
+            }
+            VertPass::Nearest { next_y, step } => {
+                let mut count = 0;
+                vert_buffer.clear();
+                while first_dst_row + count < self.dst_height && (*next_y as u32) < end_src_row {
+                    if let Some(src_row) = ring_view.iter_rows(*next_y as u32).next() {
+                        vert_buffer.extend_from_slice(src_row);
+                    }
+                    *next_y += *step;
+                    count += 1;
+                }
+                count
+            }
+            VertPass::Convolution { coeffs, max_weight } => {
+                let count = coeffs.bounds[first_dst_row as usize..]
+                    .iter()
+                    .take_while(|b| b.start + b.size <= end_src_row)
+                    .count();
+                if count > 0 {
+                    let first = first_dst_row as usize;
+                    let rows_coeffs = &mut self.rows_coeffs;
+                    coeffs.copy_part_into(first, count, *max_weight, rows_coeffs);
+                    vert_buffer.resize(ring_width * count, P::default());
+                    let mut dst_view =
+                        TypedImage::from_pixels_slice(self.ring_width, count as u32, vert_buffer)
+                            .unwrap();
+                    P::vert_convolution(
+                        &ring_view,
+                        &mut dst_view,
+                        0,
+                        rows_coeffs,
+                        self.cpu_extensions,
+                    );
+                }
+                count as u32
+            }
+        };
+
+        if let HorizPass::ConvolutionAfterVertical { coeffs, .. } = &self.horiz_pass {
+            if count > 0 {
+                let src_view =
+                    TypedImageRef::new(self.ring_width, count, &self.vert_buffer).unwrap();
+                self.dst_buffer
+                    .resize(self.dst_width as usize * count as usize, P::default());
+                let mut dst_view =
+                    TypedImage::from_pixels_slice(self.dst_width, count, &mut self.dst_buffer)
+                        .unwrap();
+                P::horiz_convolution(&src_view, &mut dst_view, 0, coeffs, self.cpu_extensions);
+            }
+        }
+
+        if self.dst_alpha_conversion != AlphaConversion::None && count > 0 {
+            let mut dst_view =
+                TypedImage::from_pixels_slice(self.dst_width, count, &mut self.dst_buffer).unwrap();
+            match self.dst_alpha_conversion {
+                AlphaConversion::Multiply => {
+                    self.mul_div.multiply_alpha_inplace_typed(&mut dst_view)
+                }
+                _ => self.mul_div.divide_alpha_inplace_typed(&mut dst_view),
+            }?;
+        }
+        Ok(count)
+    }
+}
+
+/// Converts an error of multiplication or division of a row by alpha
+/// into an error of resizing.
+fn mul_div_error(error: MulDivImagesError) -> ResizeError {
+    match error {
+        MulDivImagesError::ImageError(error) => ResizeError::ImageError(error),
+        MulDivImagesError::SizeIsDifferent => ResizeError::InvalidRowLength,
+        MulDivImagesError::PixelTypesAreDifferent => ResizeError::PixelTypesAreDifferent,
+    }
+}
+
+fn row_view<P: InnerPixel>(row: &[P]) -> TypedImageRef<'_, P> {
+    TypedImageRef::new(row.len() as u32, 1, row).unwrap()
+}
+
+fn row_view_mut<P: InnerPixel>(row: &mut [P]) -> TypedImage<'_, P> {
+    TypedImage::from_pixels_slice(row.len() as u32, 1, row).unwrap()
+}
+
+/// Image view over a ring buffer with rows of image.
+///
+/// Row with index `y` is stored in the slot `y % ring_rows` of the buffer.
+/// Only the last `ring_rows` rows before `height` are available.
+struct RingBufferView<'a, P> {
+    buffer: &'a [P],
+    width: u32,
+    height: u32,
+    ring_rows: u32,
+}
+
+unsafe impl<P: InnerPixel> ImageView for RingBufferView<'_, P> {
+    type Pixel = P;
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn iter_rows(&self, start_row: u32) -> impl Iterator<Item = &[Self::Pixel]> {
+        debug_assert!(start_row + self.ring_rows >= self.height);
+        let width = self.width as usize;
+        (start_row..self.height).map(move |y| {
+            let slot = (y % self.ring_rows) as usize * width;
+            &self.buffer[slot..slot + width]
+        })
+    }
+}
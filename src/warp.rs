@@ -0,0 +1,148 @@
+use crate::compat::*;
+use crate::convolution::EdgeMode;
+use crate::pixels::InnerPixel;
+use crate::sampler::{nearest_index, Buffers};
+use crate::{CropBox, ImageView, ImageViewMut, PixelTrait, ResizeError, Sampler};
+
+/// Matrix of an affine transformation that maps points of the destination
+/// image into points of the source image:
+///
+/// ```text
+/// src_x = m[0][0] * dst_x + m[0][1] * dst_y + m[0][2]
+/// src_y = m[1][0] * dst_x + m[1][1] * dst_y + m[1][2]
+/// ```
+pub type AffineMatrix = [[f64; 3]; 2];
+
+pub(crate) fn check_matrix(matrix: &AffineMatrix) -> Result<(), ResizeError> {
+    if matrix.iter().flatten().all(|v| v.is_finite()) {
+        Ok(())
+    } else {
+        Err(ResizeError::InvalidTransformMatrix)
+    }
+}
+
+/// Returns the point of the source image corresponding
+/// to the center of the destination pixel `(x, y)`.
+#[inline(always)]
+fn src_point(matrix: &AffineMatrix, x: u32, y: u32) -> (f64, f64) {
+    let x = x as f64 + 0.5;
+    let y = y as f64 + 0.5;
+    (
+        matrix[0][0] * x + matrix[0][1] * y + matrix[0][2],
+        matrix[1][0] * x + matrix[1][1] * y + matrix[1][2],
+    )
+}
+
+/// Returns scales of the kernel along axes of the source image that
+/// are required to avoid aliasing if the transformation minifies the image.
+///
+/// The scale along an axis is equal to the length of the step
+/// in the source image corresponding to the step of one pixel
+/// in the destination image.
+pub(crate) fn minification_scales(matrix: &AffineMatrix) -> (f64, f64) {
+    let [[a, b, _], [d, e, _]] = *matrix;
+    (a.hypot(b).max(1.), d.hypot(e).max(1.))
+}
+
+/// Returns the region of the source image mapped into the destination
+/// image if the transformation only scales and translates the image
+/// without flipping, and the region lies inside the source image.
+///
+/// Such a transformation is equal to resizing of the cropped source image,
+/// so it can be done by passes of convolution along rows and columns.
+pub(crate) fn separable_crop_box(
+    matrix: &AffineMatrix,
+    (src_width, src_height): (u32, u32),
+    (dst_width, dst_height): (u32, u32),
+) -> Option<CropBox> {
+    let [[a, b, c], [d, e, f]] = *matrix;
+    if b != 0. || d != 0. || a <= 0. || e <= 0. || dst_width == 0 || dst_height == 0 {
+        return None;
+    }
+    let (src_width, src_height) = (src_width as f64, src_height as f64);
+    let (width, height) = (a * dst_width as f64, e * dst_height as f64);
+    // Matrices of scaling by the ratio of sizes of images may map
+    // the destination image into a region that slightly exceeds
+    // the source image because of rounding errors.
+    const EPSILON: f64 = 1e-9;
+    let is_inside = c >= 0.
+        && f >= 0.
+        && c + width <= src_width + EPSILON * src_width
+        && f + height <= src_height + EPSILON * src_height;
+    is_inside.then(|| CropBox {
+        left: c,
+        top: f,
+        width: width.min(src_width - c),
+        height: height.min(src_height - f),
+    })
+}
+
+pub(crate) fn warp_nearest<P: InnerPixel>(
+    src_view: &impl ImageView<Pixel = P>,
+    dst_view: &mut impl ImageViewMut<Pixel = P>,
+    matrix: &AffineMatrix,
+    edge_mode: EdgeMode,
+) {
+    let src_width = src_view.width();
+    let src_height = src_view.height();
+    if src_width == 0 || src_height == 0 {
+        return;
+    }
+    let warp_row = |y: u32, dst_row: &mut [P]| {
+        for (x, dst_pixel) in (0..).zip(dst_row.iter_mut()) {
+            let (src_x, src_y) = src_point(matrix, x, y);
+            let src_x = nearest_index(edge_mode, src_x, src_width);
+            let src_y = nearest_index(edge_mode, src_y, src_height);
+            *dst_pixel = match (src_x, src_y) {
+                (Some(src_x), Some(src_y)) => src_view
+                    .iter_rows(src_y)
+                    .next()
+                    .map(|row| row[src_x as usize])
+                    .unwrap_or_default(),
+                _ => P::default(),
+            };
+        }
+    };
+    for_each_dst_row(dst_view, warp_row);
+}
+
+pub(crate) fn warp_with_sampler<V>(
+    sampler: &Sampler<V>,
+    dst_view: &mut impl ImageViewMut<Pixel = V::Pixel>,
+    matrix: &AffineMatrix,
+) where
+    V: ImageView<Pixel: PixelTrait>,
+{
+    let kernel = sampler.kernel();
+    let warp_row = |y: u32, dst_row: &mut [V::Pixel]| {
+        let mut buffers = Buffers::default();
+        for (x, dst_pixel) in (0..).zip(dst_row.iter_mut()) {
+            let (src_x, src_y) = src_point(matrix, x, y);
+            *dst_pixel = sampler.sample_with_kernel(src_x, src_y, &kernel, &mut buffers);
+        }
+    };
+    for_each_dst_row(dst_view, warp_row);
+}
+
+/// Calls `warp_row` for every row of the destination image
+/// with the index of the row.
+fn for_each_dst_row<P: InnerPixel>(
+    dst_view: &mut impl ImageViewMut<Pixel = P>,
+    warp_row: impl Fn(u32, &mut [P]) + Sync,
+) {
+    let mut dst_rows: Vec<(u32, &mut [P])> = (0..).zip(dst_view.iter_rows_mut(0)).collect();
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        dst_rows
+            .par_iter_mut()
+            .for_each(|(y, dst_row)| warp_row(*y, dst_row));
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        for (y, dst_row) in dst_rows.iter_mut() {
+            warp_row(*y, dst_row);
+        }
+    }
+}
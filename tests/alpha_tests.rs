@@ -527,3 +527,306 @@ mod f32_tests {
         }
     }
 }
+
+mod alpha_modes {
+    use super::*;
+    use fast_image_resize::pixels::U8x4;
+    use fast_image_resize::{AlphaMode, ResizeAlg, ResizeOptions, Resizer};
+
+    fn resize(src_image: &Image, options: &ResizeOptions) -> Image<'static> {
+        let mut dst_image = Image::new(255, 170, src_image.pixel_type());
+        Resizer::new()
+            .resize(src_image, &mut dst_image, options)
+            .unwrap();
+        dst_image
+    }
+
+    fn mul_div(src_image: &Image, oper: Oper) -> Image<'static> {
+        let mut dst_image = Image::new(
+            src_image.width(),
+            src_image.height(),
+            src_image.pixel_type(),
+        );
+        let mul_div = MulDiv::default();
+        match oper {
+            Oper::Mul => mul_div.multiply_alpha(src_image, &mut dst_image),
+            Oper::Div => mul_div.divide_alpha(src_image, &mut dst_image),
+        }
+        .unwrap();
+        dst_image
+    }
+
+    #[test]
+    fn premultiplied_src() {
+        let src_image = U8x4::load_small_src_image();
+        let premultiplied_src = mul_div(&src_image, Oper::Mul);
+        let expected = resize(&src_image, &ResizeOptions::new());
+        let options =
+            ResizeOptions::new().alpha_modes(AlphaMode::Premultiplied, AlphaMode::Straight);
+        let result = resize(&premultiplied_src, &options);
+        assert!(result.buffer() == expected.buffer());
+    }
+
+    #[test]
+    fn premultiplied_dst() {
+        let src_image = U8x4::load_small_src_image();
+        let premultiplied_src = mul_div(&src_image, Oper::Mul);
+        let options =
+            ResizeOptions::new().alpha_modes(AlphaMode::Premultiplied, AlphaMode::Premultiplied);
+        let expected = resize(&premultiplied_src, &options);
+        let options =
+            ResizeOptions::new().alpha_modes(AlphaMode::Straight, AlphaMode::Premultiplied);
+        let result = resize(&src_image, &options);
+        assert!(result.buffer() == expected.buffer());
+    }
+
+    #[test]
+    fn nearest_converts_alpha_mode() {
+        let src_image = U8x4::load_small_src_image();
+        let options = ResizeOptions::new().resize_alg(ResizeAlg::Nearest);
+        let nearest_dst = resize(&src_image, &options);
+        for (src_alpha_mode, dst_alpha_mode, oper) in [
+            (AlphaMode::Straight, AlphaMode::Premultiplied, Oper::Mul),
+            (AlphaMode::Premultiplied, AlphaMode::Straight, Oper::Div),
+        ] {
+            let expected = mul_div(&nearest_dst, oper);
+            let result = resize(
+                &src_image,
+                &options.alpha_modes(src_alpha_mode, dst_alpha_mode),
+            );
+            assert!(result.buffer() == expected.buffer());
+        }
+    }
+}
+
+mod alpha_position {
+    use super::*;
+    use fast_image_resize::pixels::U8x4;
+    use fast_image_resize::{
+        AlphaMode, AlphaPosition, ColorSpace, FilterType, ImageError, ResizeAlg, ResizeOptions,
+        Resizer,
+    };
+
+    /// Moves the alpha channel of pixels from the last position
+    /// to the first one (RGBA -> ARGB) or back.
+    fn move_alpha(image: &Image, to_first: bool) -> Image<'static> {
+        let pixel_size = image.pixel_type().size();
+        let component_size = pixel_size / 4;
+        let mut buffer = image.buffer().to_vec();
+        for pixel in buffer.chunks_exact_mut(pixel_size) {
+            if to_first {
+                pixel.rotate_right(component_size);
+            } else {
+                pixel.rotate_left(component_size);
+            }
+        }
+        Image::from_vec_u8(image.width(), image.height(), buffer, image.pixel_type()).unwrap()
+    }
+
+    fn new_image_like(image: &Image) -> Image<'static> {
+        Image::new(image.width(), image.height(), image.pixel_type())
+    }
+
+    fn mul_div_with_alpha_first<P: PixelTestingExt>() {
+        let src_image = P::load_small_src_image();
+        let argb_src_image = move_alpha(&src_image, true);
+        for cpu_extensions in P::cpu_extensions() {
+            if !cpu_extensions.is_supported() {
+                continue;
+            }
+            let cpu_ext_str = cpu_ext_into_str(cpu_extensions);
+            let mut mul_div = MulDiv::new();
+            unsafe {
+                mul_div.set_cpu_extensions(cpu_extensions);
+            }
+            let mut expected = new_image_like(&src_image);
+            mul_div.multiply_alpha(&src_image, &mut expected).unwrap();
+            let mut expected_inplace = expected.copy();
+            mul_div.divide_alpha_inplace(&mut expected_inplace).unwrap();
+
+            mul_div.set_alpha_position(AlphaPosition::First);
+            let mut result = new_image_like(&src_image);
+            mul_div
+                .multiply_alpha(&argb_src_image, &mut result)
+                .unwrap();
+            assert!(
+                move_alpha(&result, false).buffer() == expected.buffer(),
+                "multiply alpha with '{cpu_ext_str}' CPU extensions"
+            );
+            let mut result_inplace = argb_src_image.copy();
+            mul_div.multiply_alpha_inplace(&mut result_inplace).unwrap();
+            assert!(
+                result_inplace.buffer() == result.buffer(),
+                "multiply alpha inplace with '{cpu_ext_str}' CPU extensions"
+            );
+
+            let mut divided = new_image_like(&src_image);
+            mul_div.divide_alpha(&result, &mut divided).unwrap();
+            assert!(
+                move_alpha(&divided, false).buffer() == expected_inplace.buffer(),
+                "divide alpha with '{cpu_ext_str}' CPU extensions"
+            );
+            mul_div.divide_alpha_inplace(&mut result).unwrap();
+            assert!(
+                move_alpha(&result, false).buffer() == expected_inplace.buffer(),
+                "divide alpha inplace with '{cpu_ext_str}' CPU extensions"
+            );
+        }
+    }
+
+    /// Results of every CPU extension must be equal
+    /// to results of the native implementation.
+    fn alpha_first_like_native<P: PixelTestingExt>() {
+        let src_image = P::load_small_src_image();
+        // Width of the image is not a multiple of the number of pixels
+        // processed by SIMD instructions at once.
+        let (width, height) = (37, 11);
+        let size = (width * height) as usize * src_image.pixel_type().size();
+        let argb_src_image = Image::from_vec_u8(
+            width,
+            height,
+            move_alpha(&src_image, true).buffer()[..size].to_vec(),
+            src_image.pixel_type(),
+        )
+        .unwrap();
+
+        let results = |cpu_extensions| {
+            let mut mul_div = MulDiv::new();
+            unsafe {
+                mul_div.set_cpu_extensions(cpu_extensions);
+            }
+            mul_div.set_alpha_position(AlphaPosition::First);
+            let mut multiplied = new_image_like(&argb_src_image);
+            mul_div
+                .multiply_alpha(&argb_src_image, &mut multiplied)
+                .unwrap();
+            let mut multiplied_inplace = argb_src_image.copy();
+            mul_div
+                .multiply_alpha_inplace(&mut multiplied_inplace)
+                .unwrap();
+            let mut divided = new_image_like(&argb_src_image);
+            mul_div.divide_alpha(&argb_src_image, &mut divided).unwrap();
+            let mut divided_inplace = argb_src_image.copy();
+            mul_div.divide_alpha_inplace(&mut divided_inplace).unwrap();
+            [multiplied, multiplied_inplace, divided, divided_inplace]
+        };
+
+        let expected = results(CpuExtensions::None);
+        for cpu_extensions in P::cpu_extensions() {
+            if cpu_extensions == CpuExtensions::None || !cpu_extensions.is_supported() {
+                continue;
+            }
+            let cpu_ext_str = cpu_ext_into_str(cpu_extensions);
+            let operations = [
+                "multiply alpha",
+                "multiply alpha inplace",
+                "divide alpha",
+                "divide alpha inplace",
+            ];
+            for ((result, expected), operation) in results(cpu_extensions)
+                .iter()
+                .zip(&expected)
+                .zip(operations)
+            {
+                assert!(
+                    result.buffer() == expected.buffer(),
+                    "{operation} with '{cpu_ext_str}' CPU extensions"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mul_div_with_alpha_first_u8x4() {
+        mul_div_with_alpha_first::<U8x4>();
+        alpha_first_like_native::<U8x4>();
+    }
+
+    #[cfg(not(feature = "only_u8x4"))]
+    #[test]
+    fn mul_div_with_alpha_first_u16x4() {
+        mul_div_with_alpha_first::<fast_image_resize::pixels::U16x4>();
+        alpha_first_like_native::<fast_image_resize::pixels::U16x4>();
+    }
+
+    #[test]
+    fn resize_with_alpha_first() {
+        let src_image = U8x4::load_small_src_image();
+        let argb_src_image = move_alpha(&src_image, true);
+        let mut resizer = Resizer::new();
+        let lanczos3 = ResizeOptions::new();
+        let all_options = [
+            lanczos3.clone(),
+            lanczos3.color_space(ColorSpace::Srgb),
+            lanczos3.alpha_modes(AlphaMode::Premultiplied, AlphaMode::Straight),
+            ResizeOptions::new()
+                .resize_alg(ResizeAlg::Nearest)
+                .alpha_modes(AlphaMode::Straight, AlphaMode::Premultiplied),
+        ];
+        for options in all_options {
+            let mut expected = Image::new(255, 170, src_image.pixel_type());
+            resizer.resize(&src_image, &mut expected, &options).unwrap();
+            let mut result = Image::new(255, 170, src_image.pixel_type());
+            resizer
+                .resize(
+                    &argb_src_image,
+                    &mut result,
+                    &options.alpha_position(AlphaPosition::First),
+                )
+                .unwrap();
+            assert!(
+                move_alpha(&result, false).buffer() == expected.buffer(),
+                "{options:?}"
+            );
+        }
+
+        // Warping uses another way to interpolate pixels.
+        let matrix = [[2.5, 0.3, 0.], [-0.2, 2.5, 30.]];
+        let options = ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Bilinear));
+        let mut expected = Image::new(100, 100, src_image.pixel_type());
+        resizer
+            .warp_affine(&src_image, &mut expected, &matrix, &options)
+            .unwrap();
+        let mut result = Image::new(100, 100, src_image.pixel_type());
+        let options = options.alpha_position(AlphaPosition::First);
+        resizer
+            .warp_affine(&argb_src_image, &mut result, &matrix, &options)
+            .unwrap();
+        assert!(move_alpha(&result, false).buffer() == expected.buffer());
+    }
+
+    #[cfg(not(feature = "only_u8x4"))]
+    #[test]
+    fn unsupported_alpha_position() {
+        use fast_image_resize::{MulDivImagesError, PixelType, ResizeError};
+
+        let mut mul_div = MulDiv::new();
+        mul_div.set_alpha_position(AlphaPosition::First);
+        assert!(mul_div.is_supported(PixelType::U8x4));
+        assert!(!mul_div.is_supported(PixelType::U8x2));
+        let src_image = Image::new(4, 4, PixelType::U8x2);
+        let mut dst_image = Image::new(4, 4, PixelType::U8x2);
+        assert!(matches!(
+            mul_div.multiply_alpha(&src_image, &mut dst_image),
+            Err(MulDivImagesError::ImageError(
+                ImageError::UnsupportedAlphaPosition
+            ))
+        ));
+
+        let options = ResizeOptions::new().alpha_position(AlphaPosition::First);
+        let mut dst_image = Image::new(2, 2, PixelType::U8x2);
+        assert!(matches!(
+            Resizer::new().resize(&src_image, &mut dst_image, &options),
+            Err(ResizeError::ImageError(
+                ImageError::UnsupportedAlphaPosition
+            ))
+        ));
+
+        // Pixels without alpha channel ignore the position of alpha.
+        let src_image = Image::new(4, 4, PixelType::U8x3);
+        let mut dst_image = Image::new(2, 2, PixelType::U8x3);
+        assert!(Resizer::new()
+            .resize(&src_image, &mut dst_image, &options)
+            .is_ok());
+    }
+}
@@ -166,3 +166,144 @@ mod srgb_tests {
         ));
     }
 }
+
+mod resize_in_linear_space {
+    use super::*;
+    use fr::{AlphaMode, ColorSpace, FilterType, ImageView, ResizeAlg, ResizeOptions, Resizer};
+    use testing::PixelTestingExt;
+
+    fn resize_with_manual_mapping(
+        resizer: &mut Resizer,
+        src_image: &Image,
+        dst_size: (u32, u32),
+        options: &ResizeOptions,
+    ) -> Image<'static> {
+        let (dst_width, dst_height) = dst_size;
+        let mapper = fr::create_srgb_mapper();
+        let mut linear_src = Image::new(src_image.width(), src_image.height(), PixelType::U16x4);
+        mapper.forward_map(src_image, &mut linear_src).unwrap();
+        let mut linear_dst = Image::new(dst_width, dst_height, PixelType::U16x4);
+        resizer
+            .resize(&linear_src, &mut linear_dst, options)
+            .unwrap();
+        let mut expected = Image::new(dst_width, dst_height, PixelType::U8x4);
+        mapper.backward_map(&linear_dst, &mut expected).unwrap();
+        expected
+    }
+
+    #[test]
+    fn same_result_as_manual_mapping() {
+        let src_image = U8x4::load_small_src_image();
+        let (src_width, src_height) = (src_image.width(), src_image.height());
+        let lanczos3 =
+            || ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3));
+        let cases = [
+            ("both passes", (255, 170), lanczos3()),
+            ("horizontal pass", (255, src_height), lanczos3()),
+            ("vertical pass", (src_width, 170), lanczos3()),
+            ("upscale", (src_width + 100, src_height + 50), lanczos3()),
+            ("crop", (255, 170), lanczos3().crop(10.5, 20., 600., 400.5)),
+            (
+                "vertical pass with crop",
+                (300, 170),
+                lanczos3().crop(100., 20., 300., 400.),
+            ),
+            (
+                "integer box",
+                (src_width / 4, src_height / 3),
+                ResizeOptions::new().resize_alg(ResizeAlg::IntegerBox),
+            ),
+        ];
+        let mut resizer = Resizer::new();
+
+        for (name, (dst_width, dst_height), options) in cases {
+            let expected = resize_with_manual_mapping(
+                &mut resizer,
+                &src_image,
+                (dst_width, dst_height),
+                &options,
+            );
+            let mut result = Image::new(dst_width, dst_height, PixelType::U8x4);
+            resizer
+                .resize(
+                    &src_image,
+                    &mut result,
+                    &options.color_space(ColorSpace::Srgb),
+                )
+                .unwrap();
+            assert_eq!(result.buffer(), expected.buffer(), "{name}");
+        }
+    }
+
+    #[test]
+    fn premultiplied_alpha() {
+        let src_image = U8x4::load_small_src_image();
+        let (dst_width, dst_height) = (255, 170);
+        let options = ResizeOptions::new().color_space(ColorSpace::Srgb);
+        let mul_div = fr::MulDiv::default();
+        let mut resizer = Resizer::new();
+
+        let mut premultiplied_src =
+            Image::new(src_image.width(), src_image.height(), PixelType::U8x4);
+        mul_div
+            .multiply_alpha(&src_image, &mut premultiplied_src)
+            .unwrap();
+        let mut straight_src = Image::new(src_image.width(), src_image.height(), PixelType::U8x4);
+        mul_div
+            .divide_alpha(&premultiplied_src, &mut straight_src)
+            .unwrap();
+        let mut expected = Image::new(dst_width, dst_height, PixelType::U8x4);
+        resizer
+            .resize(&straight_src, &mut expected, &options)
+            .unwrap();
+        mul_div.multiply_alpha_inplace(&mut expected).unwrap();
+
+        let mut result = Image::new(dst_width, dst_height, PixelType::U8x4);
+        resizer
+            .resize(
+                &premultiplied_src,
+                &mut result,
+                &options.alpha_modes(AlphaMode::Premultiplied, AlphaMode::Premultiplied),
+            )
+            .unwrap();
+        assert_eq!(result.buffer(), expected.buffer());
+    }
+
+    #[test]
+    fn alpha_is_not_mapped() {
+        let buffer: Vec<u8> = (0u8..=255).flat_map(|v| [v, v, v, 128]).collect();
+        let src_image = Image::from_vec_u8(16, 16, buffer, PixelType::U8x4).unwrap();
+        let mut dst_image = Image::new(5, 5, PixelType::U8x4);
+        let options = ResizeOptions::new().color_space(ColorSpace::Gamma22);
+        Resizer::new()
+            .resize(&src_image, &mut dst_image, &options)
+            .unwrap();
+
+        let dst_view = dst_image.typed_image::<U8x4>().unwrap();
+        for row in dst_view.iter_rows(0) {
+            for pixel in row {
+                assert_eq!(pixel.0[3], 128);
+            }
+        }
+    }
+
+    #[cfg(not(feature = "only_u8x4"))]
+    #[test]
+    fn unsupported_pixel_type() {
+        let src_image = Image::new(16, 16, PixelType::I32);
+        let mut dst_image = Image::new(8, 8, PixelType::I32);
+        let mut resizer = Resizer::new();
+        let options = ResizeOptions::new().color_space(ColorSpace::Srgb);
+        let result = resizer.resize(&src_image, &mut dst_image, &options);
+        assert!(matches!(
+            result,
+            Err(fr::ResizeError::ImageError(
+                fr::ImageError::UnsupportedPixelType
+            ))
+        ));
+
+        // The Nearest algorithm doesn't require the conversion.
+        let options = options.resize_alg(ResizeAlg::Nearest);
+        assert!(resizer.resize(&src_image, &mut dst_image, &options).is_ok());
+    }
+}
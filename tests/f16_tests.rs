@@ -0,0 +1,158 @@
+mod testing;
+
+#[cfg(not(feature = "only_u8x4"))]
+mod f16_pixels {
+    use fast_image_resize::images::Image;
+    use fast_image_resize::pixels::*;
+    use fast_image_resize::{
+        change_type_of_pixel_components, ColorSpace, CpuExtensions, FilterType, MulDiv, PixelType,
+        ResizeAlg, ResizeOptions, Resizer,
+    };
+
+    use super::testing::PixelTestingExt;
+
+    fn convert(image: &Image, pixel_type: PixelType) -> Image<'static> {
+        let mut dst_image = Image::new(image.width(), image.height(), pixel_type);
+        change_type_of_pixel_components(image, &mut dst_image).unwrap();
+        dst_image
+    }
+
+    /// Returns the maximal difference between components of two images
+    /// with `f32` components.
+    fn max_difference(image1: &Image, image2: &Image) -> f32 {
+        let components1 = unsafe { image1.buffer().align_to::<f32>().1 };
+        let components2 = unsafe { image2.buffer().align_to::<f32>().1 };
+        assert_eq!(components1.len(), components2.len());
+        components1
+            .iter()
+            .zip(components2)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0., f32::max)
+    }
+
+    /// Multiplies colors by alpha, so small errors of colors of almost
+    /// transparent pixels, enlarged by division by alpha, are ignored.
+    fn premultiplied(mut image: Image<'static>) -> Image<'static> {
+        let mul_div = MulDiv::new();
+        if mul_div.is_supported(image.pixel_type()) {
+            mul_div.multiply_alpha_inplace(&mut image).unwrap();
+        }
+        image
+    }
+
+    fn resize(
+        src_image: &Image,
+        (width, height): (u32, u32),
+        options: &ResizeOptions,
+        cpu_extensions: CpuExtensions,
+    ) -> Image<'static> {
+        let mut dst_image = Image::new(width, height, src_image.pixel_type());
+        let mut resizer = Resizer::new();
+        unsafe {
+            resizer.set_cpu_extensions(cpu_extensions);
+        }
+        resizer.resize(src_image, &mut dst_image, options).unwrap();
+        dst_image
+    }
+
+    /// Resizes the image with `f16` components and compares the result
+    /// with the result of resizing the same image with `f32` components.
+    fn check_resize<P: PixelTestingExt>(f16_type: PixelType, f32_type: PixelType) {
+        let src_image = convert(&P::load_small_src_image(), f16_type);
+        let src_f32_image = convert(&src_image, f32_type);
+
+        let options_list = [
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3)),
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Bilinear)),
+            ResizeOptions::new().color_space(ColorSpace::Srgb),
+        ];
+        for options in options_list {
+            for size in [(213, 142), (1024, 768)] {
+                let expected =
+                    premultiplied(resize(&src_f32_image, size, &options, CpuExtensions::None));
+
+                let mut native_result: Option<Image> = None;
+                for cpu_extensions in P::cpu_extensions() {
+                    if !cpu_extensions.is_supported() {
+                        continue;
+                    }
+                    let result = resize(&src_image, size, &options, cpu_extensions);
+                    let result_f32 = premultiplied(convert(&result, f32_type));
+                    let difference = max_difference(&result_f32, &expected);
+                    assert!(
+                        difference < 0.01,
+                        "{f16_type:?}, {size:?}, {cpu_extensions:?}: difference {difference}"
+                    );
+
+                    // Only conversion of components depends on CPU extensions.
+                    // Images with a non-linear color space are resized as images
+                    // with `f32` components, so they are not checked.
+                    if options.color_space != ColorSpace::Linear {
+                        continue;
+                    }
+                    match &native_result {
+                        Some(native_result) => assert_eq!(
+                            result.buffer(),
+                            native_result.buffer(),
+                            "{f16_type:?}, {size:?}, {cpu_extensions:?}"
+                        ),
+                        None => native_result = Some(result),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resize_f16() {
+        check_resize::<U8>(PixelType::F16, PixelType::F32);
+        check_resize::<U8x2>(PixelType::F16x2, PixelType::F32x2);
+        check_resize::<U8x3>(PixelType::F16x3, PixelType::F32x3);
+        check_resize::<U8x4>(PixelType::F16x4, PixelType::F32x4);
+    }
+
+    #[test]
+    fn change_type_of_components() {
+        let src_image = U8x4::load_small_src_image();
+
+        // All u8 values are represented by f16 exactly enough
+        // to restore them after conversion.
+        let f16_image = convert(&src_image, PixelType::F16x4);
+        let u8_image = convert(&f16_image, PixelType::U8x4);
+        assert_eq!(u8_image.buffer(), src_image.buffer());
+
+        let f32_image = convert(&src_image, PixelType::F32x4);
+        let f16_image = convert(&f32_image, PixelType::F16x4);
+        let difference = max_difference(&convert(&f16_image, PixelType::F32x4), &f32_image);
+        assert!(difference < 0.001, "difference {difference}");
+
+        let u16_image = convert(&f16_image, PixelType::U16x4);
+        let f16_image2 = convert(&u16_image, PixelType::F16x4);
+        assert_eq!(f16_image2.buffer(), f16_image.buffer());
+
+        let mut i32_image = Image::new(8, 8, PixelType::I32);
+        let f16_image = Image::new(8, 8, PixelType::F16);
+        assert!(change_type_of_pixel_components(&f16_image, &mut i32_image).is_err());
+    }
+
+    #[test]
+    fn multiply_and_divide_alpha() {
+        let src_image = convert(&U8x4::load_small_src_image(), PixelType::F16x4);
+        let src_f32_image = convert(&src_image, PixelType::F32x4);
+        let mul_div = MulDiv::new();
+
+        let mut image = Image::new(src_image.width(), src_image.height(), PixelType::F16x4);
+        mul_div.multiply_alpha(&src_image, &mut image).unwrap();
+        let mut f32_image = Image::new(src_image.width(), src_image.height(), PixelType::F32x4);
+        mul_div
+            .multiply_alpha(&src_f32_image, &mut f32_image)
+            .unwrap();
+        let difference = max_difference(&convert(&image, PixelType::F32x4), &f32_image);
+        assert!(difference < 0.001, "difference {difference}");
+
+        mul_div.divide_alpha_inplace(&mut image).unwrap();
+        mul_div.divide_alpha_inplace(&mut f32_image).unwrap();
+        let difference = max_difference(&convert(&image, PixelType::F32x4), &f32_image);
+        assert!(difference < 0.01, "difference {difference}");
+    }
+}
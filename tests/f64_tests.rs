@@ -0,0 +1,165 @@
+mod testing;
+
+#[cfg(not(feature = "only_u8x4"))]
+mod f64_pixels {
+    use fast_image_resize::images::Image;
+    use fast_image_resize::pixels::*;
+    use fast_image_resize::{
+        change_type_of_pixel_components, ColorSpace, CpuExtensions, FilterType, MulDiv, PixelType,
+        ResizeAlg, ResizeOptions, Resizer,
+    };
+
+    use super::testing::PixelTestingExt;
+
+    fn convert(image: &Image, pixel_type: PixelType) -> Image<'static> {
+        let mut dst_image = Image::new(image.width(), image.height(), pixel_type);
+        change_type_of_pixel_components(image, &mut dst_image).unwrap();
+        dst_image
+    }
+
+    fn components<'a>(image: &'a Image) -> &'a [f64] {
+        unsafe { image.buffer().align_to::<f64>().1 }
+    }
+
+    /// Returns the maximal difference between components of two images
+    /// with `f64` components.
+    fn max_difference(image1: &Image, image2: &Image) -> f64 {
+        let components1 = components(image1);
+        let components2 = components(image2);
+        assert_eq!(components1.len(), components2.len());
+        components1
+            .iter()
+            .zip(components2)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0., f64::max)
+    }
+
+    /// Multiplies colors by alpha, so small errors of colors of almost
+    /// transparent pixels, enlarged by division by alpha, are ignored.
+    fn premultiplied(mut image: Image<'static>) -> Image<'static> {
+        let mul_div = MulDiv::new();
+        if mul_div.is_supported(image.pixel_type()) {
+            mul_div.multiply_alpha_inplace(&mut image).unwrap();
+        }
+        image
+    }
+
+    fn resize(
+        src_image: &Image,
+        (width, height): (u32, u32),
+        options: &ResizeOptions,
+        cpu_extensions: CpuExtensions,
+    ) -> Image<'static> {
+        let mut dst_image = Image::new(width, height, src_image.pixel_type());
+        let mut resizer = Resizer::new();
+        unsafe {
+            resizer.set_cpu_extensions(cpu_extensions);
+        }
+        resizer.resize(src_image, &mut dst_image, options).unwrap();
+        dst_image
+    }
+
+    /// Resizes the image with `f64` components and compares the result
+    /// with the result of resizing the same image with `f32` components.
+    fn check_resize<P: PixelTestingExt>(f64_type: PixelType, f32_type: PixelType) {
+        let src_f32_image = convert(&P::load_small_src_image(), f32_type);
+        let src_image = convert(&src_f32_image, f64_type);
+
+        let options_list = [
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3)),
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Bilinear)),
+            ResizeOptions::new().resize_alg(ResizeAlg::Nearest),
+        ];
+        for options in options_list {
+            for size in [(213, 142), (1024, 768)] {
+                let expected = premultiplied(convert(
+                    &resize(&src_f32_image, size, &options, CpuExtensions::None),
+                    f64_type,
+                ));
+
+                let mut native_result: Option<Image> = None;
+                for cpu_extensions in P::cpu_extensions() {
+                    if !cpu_extensions.is_supported() {
+                        continue;
+                    }
+                    let result = resize(&src_image, size, &options, cpu_extensions);
+                    let difference = max_difference(&premultiplied(result.copy()), &expected);
+                    assert!(
+                        difference < 0.0001,
+                        "{f64_type:?}, {size:?}, {cpu_extensions:?}: difference {difference}"
+                    );
+
+                    // Sums are calculated in the same order with any CPU extensions.
+                    match &native_result {
+                        Some(native_result) => assert_eq!(
+                            result.buffer(),
+                            native_result.buffer(),
+                            "{f64_type:?}, {size:?}, {cpu_extensions:?}"
+                        ),
+                        None => native_result = Some(result),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resize_f64() {
+        check_resize::<U8>(PixelType::F64, PixelType::F32);
+        check_resize::<U8x2>(PixelType::F64x2, PixelType::F32x2);
+        check_resize::<U8x3>(PixelType::F64x3, PixelType::F32x3);
+        check_resize::<U8x4>(PixelType::F64x4, PixelType::F32x4);
+    }
+
+    #[test]
+    fn resize_in_non_linear_color_space() {
+        let src_image = Image::new(8, 8, PixelType::F64x4);
+        let mut dst_image = Image::new(4, 4, PixelType::F64x4);
+        let options = ResizeOptions::new().color_space(ColorSpace::Srgb);
+        let result = Resizer::new().resize(&src_image, &mut dst_image, &options);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn change_type_of_components() {
+        let f32_image = convert(&U8x4::load_small_src_image(), PixelType::F32x4);
+        let f64_image = convert(&f32_image, PixelType::F64x4);
+        let f32_image2 = convert(&f64_image, PixelType::F32x4);
+        assert_eq!(f32_image2.buffer(), f32_image.buffer());
+
+        let mut i32_image = Image::new(3, 1, PixelType::I32);
+        let values = [i32::MIN, 0, i32::MAX];
+        for (dst, value) in i32_image.buffer_mut().chunks_exact_mut(4).zip(values) {
+            dst.copy_from_slice(&value.to_ne_bytes());
+        }
+        let f64_image = convert(&i32_image, PixelType::F64);
+        assert_eq!(components(&f64_image), [-1., 0., 1.]);
+        let i32_image2 = convert(&f64_image, PixelType::I32);
+        assert_eq!(i32_image2.buffer(), i32_image.buffer());
+
+        let mut u8_image = Image::new(8, 8, PixelType::U8x4);
+        let f64_image = Image::new(8, 8, PixelType::F64x4);
+        assert!(change_type_of_pixel_components(&f64_image, &mut u8_image).is_err());
+    }
+
+    #[test]
+    fn multiply_and_divide_alpha() {
+        let src_f32_image = convert(&U8x4::load_small_src_image(), PixelType::F32x4);
+        let src_image = convert(&src_f32_image, PixelType::F64x4);
+        let mul_div = MulDiv::new();
+
+        let mut image = Image::new(src_image.width(), src_image.height(), PixelType::F64x4);
+        mul_div.multiply_alpha(&src_image, &mut image).unwrap();
+        let mut f32_image = Image::new(src_image.width(), src_image.height(), PixelType::F32x4);
+        mul_div
+            .multiply_alpha(&src_f32_image, &mut f32_image)
+            .unwrap();
+        let difference = max_difference(&image, &convert(&f32_image, PixelType::F64x4));
+        assert!(difference < 0.000001, "difference {difference}");
+
+        mul_div.divide_alpha_inplace(&mut image).unwrap();
+        mul_div.divide_alpha_inplace(&mut f32_image).unwrap();
+        let difference = max_difference(&image, &convert(&f32_image, PixelType::F64x4));
+        assert!(difference < 0.00001, "difference {difference}");
+    }
+}
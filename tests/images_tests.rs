@@ -4,7 +4,7 @@ use fast_image_resize::images::{
     TypedImage, TypedImageRef,
 };
 use fast_image_resize::pixels::{U8x3, U8x4, U8};
-use fast_image_resize::{ImageView, IntoImageView, PixelType, ResizeOptions};
+use fast_image_resize::{ImageView, IntoImageView, PixelTrait, PixelType, ResizeOptions};
 
 mod testing;
 
@@ -247,6 +247,51 @@ fn cropped_image_mut() {
     assert_eq!(dst_pixels[22 * row_size..], black_block);
 }
 
+/// Image with a buffer that is not aligned for its pixel type.
+struct UnalignedImage {
+    buffer: Vec<u8>,
+}
+
+impl IntoImageView for UnalignedImage {
+    fn pixel_type(&self) -> Option<PixelType> {
+        Some(PixelType::U16)
+    }
+
+    fn width(&self) -> u32 {
+        4
+    }
+
+    fn height(&self) -> u32 {
+        4
+    }
+
+    fn image_view<P: PixelTrait>(&self) -> Option<impl ImageView<Pixel = P>> {
+        let offset = 1 - self.buffer.as_ptr() as usize % 2;
+        TypedImageRef::<P>::from_buffer(4, 4, &self.buffer[offset..]).ok()
+    }
+}
+
+#[cfg(not(feature = "only_u8x4"))]
+#[test]
+fn unaligned_image_buffer() {
+    let src_image = UnalignedImage {
+        buffer: vec![0; 4 * 4 * 2 + 1],
+    };
+    let mut dst_image = Image::new(4, 4, PixelType::U16);
+    assert!(matches!(
+        fr::Resizer::new().resize(&src_image, &mut dst_image, None),
+        Err(fr::ResizeError::ImageError(
+            fr::ImageError::InvalidBufferAlignment
+        ))
+    ));
+    assert_eq!(
+        fr::flip_vertical(&src_image, &mut dst_image),
+        Err(fr::OrientationError::ImageError(
+            fr::ImageError::InvalidBufferAlignment
+        ))
+    );
+}
+
 #[cfg(feature = "bytemuck")]
 #[test]
 fn use_bytemuck_to_create_typed_image_from_slice() {
@@ -277,7 +322,13 @@ fn use_bytemuck_to_create_typed_image_from_slice() {
 #[cfg(feature = "image")]
 mod support_of_image_crate {
     use super::*;
-    use image::{DynamicImage, RgbImage};
+    use fast_image_resize::ResizeExt;
+    use image::imageops::FilterType;
+    use image::metadata::Orientation as ImageOrientation;
+    use image::{
+        ColorType, DynamicImage, GenericImageView, ImageBuffer, Luma, RgbImage, Rgba, Rgba32FImage,
+        RgbaImage,
+    };
     use testing::PixelTestingExt;
 
     #[test]
@@ -306,4 +357,110 @@ mod support_of_image_crate {
         let res = resizer.resize(&src_image_buffer, &mut dst_image_buffer, None);
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn use_image_buffer_with_borrowed_container() {
+        let (width, height) = (64, 32);
+        let src_image_buffer = U8x4::load_small_image();
+        let mut buffer = vec![0u8; width as usize * height as usize * 4];
+        let mut dst_image_buffer =
+            ImageBuffer::<Rgba<u8>, &mut [u8]>::from_raw(width, height, buffer.as_mut_slice())
+                .unwrap();
+
+        let mut resizer = fr::Resizer::new();
+        resizer
+            .resize(&src_image_buffer, &mut dst_image_buffer, None)
+            .unwrap();
+
+        let mut expected = RgbaImage::new(width, height);
+        resizer
+            .resize(&src_image_buffer, &mut expected, None)
+            .unwrap();
+        assert_eq!(buffer, expected.into_raw());
+    }
+
+    #[test]
+    fn use_image_buffer_with_f32_pixels() {
+        let src_image = DynamicImage::from(U8x3::load_small_image());
+        let src_luma: ImageBuffer<Luma<f32>, Vec<f32>> = src_image.to_luma32f();
+        let mut dst_luma = ImageBuffer::<Luma<f32>, Vec<f32>>::new(64, 32);
+        let src_rgba = src_image.to_rgba32f();
+        let mut dst_rgba = Rgba32FImage::new(64, 32);
+
+        let mut resizer = fr::Resizer::new();
+        resizer.resize(&src_luma, &mut dst_luma, None).unwrap();
+        resizer.resize(&src_rgba, &mut dst_rgba, None).unwrap();
+        assert_eq!(dst_luma.pixel_type(), Some(PixelType::F32));
+        assert!(dst_luma.pixels().any(|p| p.0[0] > 0.));
+        assert!(dst_rgba.pixels().any(|p| p.0[0] > 0.));
+    }
+
+    #[test]
+    fn resize_with_image_orientation() {
+        let src_dynamic_image: DynamicImage = U8x3::load_small_image().into();
+        let mut resizer = fr::Resizer::new();
+        for exif_value in 1..=8 {
+            let image_orientation = ImageOrientation::from_exif(exif_value).unwrap();
+            let orientation = fr::Orientation::from(image_orientation);
+            assert_eq!(orientation.to_exif(), exif_value as u16);
+
+            let result = resizer
+                .resize_with_image_orientation(&src_dynamic_image, image_orientation, 64, 32, None)
+                .unwrap();
+            let (width, height) = orientation.oriented_size(64, 32);
+            assert_eq!((result.width(), result.height()), (width, height));
+
+            let mut expected = DynamicImage::new_rgb8(width, height);
+            let options = ResizeOptions::new().orientation(orientation);
+            resizer
+                .resize(&src_dynamic_image, &mut expected, &options)
+                .unwrap();
+            assert_eq!(result, expected, "{orientation:?}");
+        }
+    }
+
+    #[test]
+    fn resize_ext_supports_all_color_types() {
+        for color_type in [
+            ColorType::L8,
+            ColorType::La8,
+            ColorType::Rgb8,
+            ColorType::Rgba8,
+            ColorType::L16,
+            ColorType::La16,
+            ColorType::Rgb16,
+            ColorType::Rgba16,
+            ColorType::Rgb32F,
+            ColorType::Rgba32F,
+        ] {
+            let src_image = DynamicImage::new(16, 12, color_type);
+            let result = src_image.resize_to(8, 3, None).unwrap();
+            assert_eq!((result.width(), result.height()), (8, 3));
+            assert_eq!(result.color(), color_type);
+        }
+    }
+
+    #[test]
+    fn resize_ext_like_image_crate() {
+        let src_image: DynamicImage = U8x3::load_small_image().into();
+        let options = ResizeOptions::new().resize_alg(fr::ResizeAlg::Nearest);
+        for (width, height) in [(100, 100), (300, 50), (1000, 2000), (1, 1)] {
+            let result = src_image.resize_to_fit(width, height, &options).unwrap();
+            let expected = src_image.resize(width, height, FilterType::Nearest);
+            assert_eq!(result.dimensions(), expected.dimensions());
+
+            let result = src_image.resize_to_cover(width, height, &options).unwrap();
+            assert_eq!(result.dimensions(), (width, height));
+            let expected = src_image
+                .resize_to(width, height, &options.fit_into_destination(None))
+                .unwrap();
+            assert_eq!(result, expected);
+        }
+
+        // The size of the new image relates to the oriented source image.
+        let options = options.orientation(fr::Orientation::Rotate90);
+        let result = src_image.resize_to_fit(100, 100, &options).unwrap();
+        let expected = src_image.rotate90().resize(100, 100, FilterType::Nearest);
+        assert_eq!(result.dimensions(), expected.dimensions());
+    }
 }
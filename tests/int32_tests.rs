@@ -0,0 +1,219 @@
+mod testing;
+
+#[cfg(not(feature = "only_u8x4"))]
+mod int32_pixels {
+    use fast_image_resize::images::Image;
+    use fast_image_resize::pixels::*;
+    use fast_image_resize::{
+        change_type_of_pixel_components, CpuExtensions, FilterType, PixelType, ResizeAlg,
+        ResizeOptions, Resizer,
+    };
+
+    use super::testing::PixelTestingExt;
+
+    fn convert(image: &Image, pixel_type: PixelType) -> Image<'static> {
+        let mut dst_image = Image::new(image.width(), image.height(), pixel_type);
+        change_type_of_pixel_components(image, &mut dst_image).unwrap();
+        dst_image
+    }
+
+    /// Returns an image with one channel of the given image
+    /// with 32-bit components.
+    fn channel(image: &Image, index: usize, pixel_type: PixelType) -> Image<'static> {
+        let count = image.pixel_type().size() / 4;
+        let buffer = image
+            .buffer()
+            .chunks_exact(4)
+            .skip(index)
+            .step_by(count)
+            .flatten()
+            .copied()
+            .collect();
+        Image::from_vec_u8(image.width(), image.height(), buffer, pixel_type).unwrap()
+    }
+
+    fn resize(src_image: &Image, options: &ResizeOptions) -> Image<'static> {
+        let mut dst_image = Image::new(213, 142, src_image.pixel_type());
+        let mut resizer = Resizer::new();
+        resizer.resize(src_image, &mut dst_image, options).unwrap();
+        dst_image
+    }
+
+    /// Channels of multi-component pixels must be resized
+    /// exactly like images with one component.
+    fn check_channels<P: PixelTestingExt>(pixel_type: PixelType, one_channel_type: PixelType) {
+        let src_image = convert(&P::load_small_src_image(), pixel_type);
+        let count = pixel_type.size() / 4;
+
+        let options_list = [
+            ResizeOptions::new().resize_alg(ResizeAlg::Nearest),
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3)),
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Bilinear)),
+        ];
+        for options in options_list {
+            let result = resize(&src_image, &options);
+            for i in 0..count {
+                let expected = resize(&channel(&src_image, i, one_channel_type), &options);
+                assert_eq!(
+                    channel(&result, i, one_channel_type).buffer(),
+                    expected.buffer(),
+                    "{pixel_type:?}, channel {i}, {:?}",
+                    options.algorithm
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn resize_multi_component_pixels() {
+        check_channels::<U8x2>(PixelType::I32x2, PixelType::I32);
+        check_channels::<U8x3>(PixelType::I32x3, PixelType::I32);
+        check_channels::<U8x4>(PixelType::I32x4, PixelType::I32);
+        check_channels::<U8x2>(PixelType::U32x2, PixelType::U32);
+        check_channels::<U8x3>(PixelType::U32x3, PixelType::U32);
+        check_channels::<U8x4>(PixelType::U32x4, PixelType::U32);
+    }
+
+    fn i32_image(width: u32, height: u32, values: impl Iterator<Item = i32>) -> Image<'static> {
+        let buffer = values.flat_map(i32::to_ne_bytes).collect();
+        Image::from_vec_u8(width, height, buffer, PixelType::I32).unwrap()
+    }
+
+    /// Results of every CPU extension must be equal
+    /// to results of the native implementation.
+    fn check_like_native(src_image: &Image, options: &ResizeOptions, width: u32, height: u32) {
+        let mut expected = Image::new(width, height, PixelType::I32);
+        let mut resizer = Resizer::new();
+        unsafe {
+            resizer.set_cpu_extensions(CpuExtensions::None);
+        }
+        resizer.resize(src_image, &mut expected, options).unwrap();
+
+        for cpu_extensions in I32::cpu_extensions() {
+            if !cpu_extensions.is_supported() {
+                continue;
+            }
+            let mut result = Image::new(width, height, PixelType::I32);
+            unsafe {
+                resizer.set_cpu_extensions(cpu_extensions);
+            }
+            resizer.resize(src_image, &mut result, options).unwrap();
+            assert_eq!(
+                result.buffer(),
+                expected.buffer(),
+                "{width}x{height}, {cpu_extensions:?}, {:?}",
+                options.algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn resize_i32_with_cpu_extensions() {
+        // Image with values from the whole range of `i32`.
+        let image = U8::load_small_src_image();
+        let values = image
+            .buffer()
+            .iter()
+            .map(|&v| (i32::MIN as i64 + v as i64 * u32::MAX as i64 / 255) as i32);
+        let src_image = i32_image(image.width(), image.height(), values);
+        // Image with sharp edges between minimal and maximal values,
+        // so results of convolution have to be saturated.
+        let values = (0..67 * 41).map(|i| match (i % 67 / 3 + i / 67 / 3) % 2 {
+            0 => i32::MIN,
+            _ => i32::MAX,
+        });
+        let stripes_image = i32_image(67, 41, values);
+
+        let options_list = [
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3)),
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::CatmullRom)),
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Bilinear)),
+        ];
+        for src_image in [&src_image, &stripes_image] {
+            for options in &options_list {
+                for (width, height) in [(213, 142), (31, 17), (1021, 767)] {
+                    check_like_native(src_image, options, width, height);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resize_negative_and_extreme_i32_with_cpu_extensions() {
+        // Small negative values give sums with the fractional part
+        // equal to 0.5 that must be rounded away from zero.
+        let negative_image = i32_image(66, 38, (0..66 * 38).map(|i| -(i * 7919 % 1001)));
+        // Values near bounds of `i32` and sums of them must be clamped.
+        let extremes = [i32::MIN, i32::MIN + 1, i32::MAX - 1, i32::MAX, -1, 0];
+        let values = (0..66 * 38).map(|i| extremes[(i * 31 % 67) as usize % extremes.len()]);
+        let extreme_image = i32_image(66, 38, values);
+
+        let options_list = [
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Box)),
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Bilinear)),
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3)),
+        ];
+        for src_image in [&negative_image, &extreme_image] {
+            for options in &options_list {
+                for (width, height) in [(33, 19), (132, 76), (23, 41)] {
+                    check_like_native(src_image, options, width, height);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resize_u32_greater_than_i32_max() {
+        let value = i32::MAX as u32 + 12345;
+        let buffer = value.to_ne_bytes().repeat(64 * 64);
+        let src_image = Image::from_vec_u8(64, 64, buffer, PixelType::U32).unwrap();
+        for options in [
+            ResizeOptions::new().resize_alg(ResizeAlg::Nearest),
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3)),
+        ] {
+            let result = resize(&src_image, &options);
+            assert!(
+                result
+                    .buffer()
+                    .chunks_exact(4)
+                    .all(|c| c == value.to_ne_bytes()),
+                "{:?}",
+                options.algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn change_type_of_components() {
+        let src_image = U8x4::load_small_src_image();
+        for pixel_type in [PixelType::I32x4, PixelType::U32x4] {
+            let image = convert(&src_image, pixel_type);
+            let u8_image = convert(&image, PixelType::U8x4);
+            assert_eq!(u8_image.buffer(), src_image.buffer(), "{pixel_type:?}");
+        }
+
+        let u16_image = convert(&src_image, PixelType::U16x4);
+        let u32_image = convert(&u16_image, PixelType::U32x4);
+        assert_eq!(
+            convert(&u32_image, PixelType::U16x4).buffer(),
+            u16_image.buffer()
+        );
+        let f64_image = convert(&u32_image, PixelType::F64x4);
+        assert_eq!(
+            convert(&f64_image, PixelType::U32x4).buffer(),
+            u32_image.buffer()
+        );
+
+        let mut u32_image = Image::new(2, 1, PixelType::U32);
+        u32_image.buffer_mut()[4..].copy_from_slice(&u32::MAX.to_ne_bytes());
+        let f32_image = convert(&u32_image, PixelType::F32);
+        assert_eq!(
+            f32_image.buffer(),
+            [0f32, 1.].map(f32::to_ne_bytes).concat()
+        );
+
+        let mut i32_image = Image::new(8, 8, PixelType::I32x2);
+        let u32_image = Image::new(8, 8, PixelType::U32x2);
+        assert!(change_type_of_pixel_components(&u32_image, &mut i32_image).is_err());
+    }
+}
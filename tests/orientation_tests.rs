@@ -0,0 +1,251 @@
+use fast_image_resize::images::{Image, ImageRef};
+use fast_image_resize::pixels::*;
+use fast_image_resize::{
+    flip_horizontal, flip_vertical, orient, rotate180, rotate270, rotate90, transpose, AxisFilter,
+    FilterType, IntoImageView, Orientation, OrientationError, PixelTrait, PixelType, ResizeAlg,
+    ResizeError, ResizeOptions, Resizer, StreamingResizer,
+};
+
+const ORIENTATIONS: [Orientation; 8] = [
+    Orientation::Normal,
+    Orientation::FlipHorizontal,
+    Orientation::Rotate180,
+    Orientation::FlipVertical,
+    Orientation::Transpose,
+    Orientation::Rotate90,
+    Orientation::Transverse,
+    Orientation::Rotate270,
+];
+
+fn u8x4_pixels(width: u32, height: u32) -> Vec<U8x4> {
+    (0..width * height)
+        .map(|i| {
+            let v = (i * 7919 % 256) as u8;
+            U8x4::new([v, (i % 256) as u8, 255 - v, 255 - (i % 7) as u8])
+        })
+        .collect()
+}
+
+/// Returns coordinates of the source pixel for the destination pixel `(x, y)`.
+fn src_coords(orientation: Orientation, x: u32, y: u32, width: u32, height: u32) -> (u32, u32) {
+    match orientation {
+        Orientation::Normal => (x, y),
+        Orientation::FlipHorizontal => (width - 1 - x, y),
+        Orientation::Rotate180 => (width - 1 - x, height - 1 - y),
+        Orientation::FlipVertical => (x, height - 1 - y),
+        Orientation::Transpose => (y, x),
+        Orientation::Rotate90 => (y, height - 1 - x),
+        Orientation::Transverse => (width - 1 - y, height - 1 - x),
+        Orientation::Rotate270 => (width - 1 - y, x),
+    }
+}
+
+fn oriented_image(src_image: &ImageRef, orientation: Orientation) -> Image<'static> {
+    let (width, height) = orientation.oriented_size(src_image.width(), src_image.height());
+    let mut dst_image = Image::new(width, height, src_image.pixel_type());
+    orient(src_image, &mut dst_image, orientation).unwrap();
+    dst_image
+}
+
+#[test]
+fn orient_pixels() {
+    // The size is greater than the size of tiles used to transpose images.
+    for (width, height) in [(3, 2), (70, 45)] {
+        let src_pixels = u8x4_pixels(width, height);
+        let src_image = ImageRef::from_pixels(width, height, &src_pixels).unwrap();
+        for orientation in ORIENTATIONS {
+            let dst_image = oriented_image(&src_image, orientation);
+            let (dst_width, dst_height) = orientation.oriented_size(width, height);
+            assert_eq!(
+                (dst_image.width(), dst_image.height()),
+                (dst_width, dst_height)
+            );
+            let dst_image = dst_image.typed_image::<U8x4>().unwrap();
+            for y in 0..dst_height {
+                for x in 0..dst_width {
+                    let (src_x, src_y) = src_coords(orientation, x, y, width, height);
+                    assert_eq!(
+                        dst_image.pixels()[(y * dst_width + x) as usize],
+                        src_pixels[(src_y * width + src_x) as usize],
+                        "{orientation:?} ({x}, {y})"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn helper_functions() {
+    let src_pixels = u8x4_pixels(5, 3);
+    let src_image = ImageRef::from_pixels(5, 3, &src_pixels).unwrap();
+    type OrientFn = fn(&ImageRef, &mut Image) -> Result<(), OrientationError>;
+    let functions: [(OrientFn, Orientation); 6] = [
+        (|s, d| flip_horizontal(s, d), Orientation::FlipHorizontal),
+        (|s, d| flip_vertical(s, d), Orientation::FlipVertical),
+        (|s, d| rotate90(s, d), Orientation::Rotate90),
+        (|s, d| rotate180(s, d), Orientation::Rotate180),
+        (|s, d| rotate270(s, d), Orientation::Rotate270),
+        (|s, d| transpose(s, d), Orientation::Transpose),
+    ];
+    for (function, orientation) in functions {
+        let expected = oriented_image(&src_image, orientation);
+        let mut dst_image = Image::new(expected.width(), expected.height(), PixelType::U8x4);
+        function(&src_image, &mut dst_image).unwrap();
+        assert_eq!(dst_image.buffer(), expected.buffer(), "{orientation:?}");
+    }
+}
+
+#[test]
+fn exif_values() {
+    for (value, orientation) in (1..).zip(ORIENTATIONS) {
+        assert_eq!(Orientation::from_exif(value), Some(orientation));
+        assert_eq!(orientation.to_exif(), value);
+    }
+    assert_eq!(Orientation::from_exif(0), None);
+    assert_eq!(Orientation::from_exif(9), None);
+}
+
+#[test]
+fn orient_errors() {
+    let src_image = Image::new(5, 3, PixelType::U8x4);
+    let mut dst_image = Image::new(5, 3, PixelType::U8x4);
+    assert_eq!(
+        rotate90(&src_image, &mut dst_image),
+        Err(OrientationError::InvalidDstSize)
+    );
+    assert_eq!(flip_vertical(&src_image, &mut dst_image), Ok(()));
+
+    let mut dst_image = Image::new(5, 3, PixelType::U8x3);
+    assert_eq!(
+        flip_vertical(&src_image, &mut dst_image),
+        Err(OrientationError::PixelTypesAreDifferent)
+    );
+}
+
+fn resize<P: PixelTrait>(
+    src_image: &impl IntoImageView,
+    dst_width: u32,
+    dst_height: u32,
+    options: &ResizeOptions,
+) -> Vec<u8> {
+    let mut dst_image = Image::new(dst_width, dst_height, P::pixel_type());
+    Resizer::new()
+        .resize(src_image, &mut dst_image, options)
+        .unwrap();
+    dst_image.into_vec()
+}
+
+/// Resizing with an orientation option must give the same result
+/// as resizing of the oriented source image. Results may differ by
+/// rounding errors because the order of convolution passes
+/// depends on the orientation.
+fn same_result_as_oriented_src<P: PixelTrait>(
+    src_image: &ImageRef,
+    assert_close: impl Fn(&[u8], &[u8], &str),
+) {
+    let filter = ResizeAlg::Convolution(FilterType::Lanczos3);
+    let per_axis = ResizeAlg::PerAxisConvolution {
+        horizontal: AxisFilter::convolution(FilterType::Box),
+        vertical: AxisFilter::interpolation(FilterType::CatmullRom),
+    };
+    let cases = [
+        // The source image is oriented before resizing.
+        (111, 97, ResizeOptions::new().resize_alg(filter.clone())),
+        (111, 97, ResizeOptions::new().resize_alg(per_axis.clone())),
+        // The resized image is oriented.
+        (23, 17, ResizeOptions::new().resize_alg(filter.clone())),
+        (23, 17, ResizeOptions::new().resize_alg(per_axis.clone())),
+        (23, 17, ResizeOptions::new().resize_alg(ResizeAlg::Nearest)),
+        (
+            23,
+            17,
+            ResizeOptions::new().resize_alg(ResizeAlg::SuperSampling(FilterType::Bilinear, 2)),
+        ),
+        (
+            10,
+            10,
+            ResizeOptions::new().resize_alg(ResizeAlg::IntegerBox),
+        ),
+        (
+            23,
+            17,
+            ResizeOptions::new()
+                .resize_alg(filter.clone())
+                .crop(3., 5., 31., 22.),
+        ),
+        (
+            40,
+            17,
+            ResizeOptions::new()
+                .resize_alg(filter.clone())
+                .fit_into_destination(Some((0.2, 0.7))),
+        ),
+        (
+            40,
+            17,
+            ResizeOptions::new()
+                .resize_alg(filter.clone())
+                .fit_into_destination_with_padding(P::default(), Some((0.3, 0.6))),
+        ),
+    ];
+    for orientation in ORIENTATIONS {
+        let oriented_src = oriented_image(src_image, orientation);
+        for (dst_width, dst_height, options) in &cases {
+            let expected = resize::<P>(&oriented_src, *dst_width, *dst_height, options);
+            let options = options.orientation(orientation);
+            let result = resize::<P>(src_image, *dst_width, *dst_height, &options);
+            assert_close(&result, &expected, &format!("{orientation:?} {options:?}"));
+        }
+    }
+}
+
+#[test]
+fn resize_with_orientation_u8x4() {
+    // Smooth image, so the intermediate results of convolution
+    // are not clipped by the range of `u8`.
+    let pixels: Vec<U8x4> = (0..50u32 * 40)
+        .map(|i| {
+            let (x, y) = ((i % 50) as u8, (i / 50) as u8);
+            U8x4::new([x * 4 + y, 200 - y * 4, 100 + x + y, 255 - x])
+        })
+        .collect();
+    let src_image = ImageRef::from_pixels(50, 40, &pixels).unwrap();
+    same_result_as_oriented_src::<U8x4>(&src_image, |result, expected, msg| {
+        assert_eq!(result.len(), expected.len());
+        for (&a, &b) in result.iter().zip(expected) {
+            // Both passes and the division by alpha are rounded.
+            assert!(a.abs_diff(b) <= 2, "{a} != {b}: {msg}");
+        }
+    });
+}
+
+#[test]
+fn streaming_resizer_does_not_support_orientation() {
+    let options = ResizeOptions::new().orientation(Orientation::Rotate90);
+    assert!(matches!(
+        StreamingResizer::<U8x4>::new(10, 10, 5, 5, &options),
+        Err(ResizeError::UnsupportedOrientation)
+    ));
+}
+
+#[cfg(not(feature = "only_u8x4"))]
+mod not_u8x4 {
+    use super::*;
+
+    #[test]
+    fn resize_with_orientation_f32() {
+        let pixels: Vec<F32> = (0..50 * 40)
+            .map(|i| F32::new(((i * 7919) % 256) as f32))
+            .collect();
+        let src_image = ImageRef::from_pixels(50, 40, &pixels).unwrap();
+        same_result_as_oriented_src::<F32>(&src_image, |result, expected, msg| {
+            assert_eq!(result.len(), expected.len());
+            for (a, b) in result.chunks_exact(4).zip(expected.chunks_exact(4)) {
+                let a = f32::from_le_bytes(a.try_into().unwrap());
+                let b = f32::from_le_bytes(b.try_into().unwrap());
+                assert!((a - b).abs() <= 1e-3 * b.abs().max(1.), "{a} != {b}: {msg}");
+            }
+        });
+    }
+}
@@ -0,0 +1,201 @@
+use fast_image_resize::images::Image;
+use fast_image_resize::pixels::*;
+use fast_image_resize::{
+    FilterType, IntoImageView, Orientation, PixelTrait, PixelType, PyramidOptions, PyramidRounding,
+    ResizeAlg, ResizeOptions, Resizer,
+};
+use testing::PixelTestingExt;
+
+mod testing;
+
+fn level_sizes(width: u32, height: u32, options: &PyramidOptions) -> Vec<(u32, u32)> {
+    let src_image = Image::new(width, height, PixelType::U8x4);
+    let pyramid = Resizer::new().build_pyramid(&src_image, options).unwrap();
+    pyramid
+        .levels()
+        .iter()
+        .map(|level| (level.width, level.height))
+        .collect()
+}
+
+#[test]
+fn sizes_of_levels() {
+    let options = PyramidOptions::new();
+    assert_eq!(
+        level_sizes(16, 4, &options),
+        [(16, 4), (8, 2), (4, 1), (2, 1), (1, 1)]
+    );
+    assert_eq!(
+        level_sizes(10, 3, &options),
+        [(10, 3), (5, 1), (2, 1), (1, 1)]
+    );
+    assert_eq!(level_sizes(1, 1, &options), [(1, 1)]);
+
+    let options = PyramidOptions::new().rounding(PyramidRounding::Ceil);
+    assert_eq!(
+        level_sizes(10, 3, &options),
+        [(10, 3), (5, 2), (3, 1), (2, 1), (1, 1)]
+    );
+
+    let options = PyramidOptions::new().min_size(4, 4);
+    assert_eq!(level_sizes(10, 3, &options), [(10, 3), (5, 3), (4, 3)]);
+    assert_eq!(
+        level_sizes(64, 32, &options),
+        [(64, 32), (32, 16), (16, 8), (8, 4), (4, 4)]
+    );
+}
+
+#[test]
+fn contiguous_buffer() {
+    let src_image = Image::new(100, 60, PixelType::U8x4);
+    let pyramid = Resizer::new().build_pyramid(&src_image, None).unwrap();
+    assert_eq!(pyramid.pixel_type(), PixelType::U8x4);
+    let mut offset = 0;
+    for (i, level) in pyramid.levels().iter().enumerate() {
+        assert_eq!(level.offset, offset);
+        offset += level.width as usize * level.height as usize * 4;
+        let image = pyramid.level(i).unwrap();
+        assert_eq!((image.width(), image.height()), (level.width, level.height));
+        assert_eq!(
+            image.buffer().as_ptr(),
+            pyramid.buffer()[level.offset..].as_ptr()
+        );
+    }
+    assert_eq!(pyramid.buffer().len(), offset);
+    assert!(pyramid.level(pyramid.levels().len()).is_none());
+
+    let src_image = Image::new(0, 60, PixelType::U8x4);
+    let pyramid = Resizer::new().build_pyramid(&src_image, None).unwrap();
+    assert!(pyramid.levels().iter().all(|level| level.width == 0));
+    assert!(pyramid.buffer().is_empty());
+}
+
+#[test]
+fn aligned_levels() {
+    for (pixel_type, align) in [
+        (PixelType::U8x3, 1),
+        (PixelType::U16x3, 2),
+        (PixelType::F32, 4),
+        (PixelType::F64, 8),
+    ] {
+        let src_image = Image::new(13, 7, pixel_type);
+        let pyramid = Resizer::new().build_pyramid(&src_image, None).unwrap();
+        for i in 0..pyramid.levels().len() {
+            let image = pyramid.level(i).unwrap();
+            assert_eq!(
+                image.buffer().as_ptr() as usize % align,
+                0,
+                "{pixel_type:?}"
+            );
+        }
+    }
+}
+
+fn resize<P: PixelTrait>(
+    src_image: &impl IntoImageView,
+    dst_width: u32,
+    dst_height: u32,
+    options: &ResizeOptions,
+) -> Vec<u8> {
+    let mut dst_image = Image::new(dst_width, dst_height, P::pixel_type());
+    Resizer::new()
+        .resize(src_image, &mut dst_image, options)
+        .unwrap();
+    dst_image.into_vec()
+}
+
+fn same_result_as_resizer<P: PixelTrait + PixelTestingExt>() {
+    let src_image = P::load_small_src_image();
+    let resize_options =
+        ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Bilinear));
+    let options = PyramidOptions::new().resize_options(&resize_options);
+    let mut resizer = Resizer::new();
+
+    let pyramid = resizer.build_pyramid(&src_image, &options).unwrap();
+    assert_eq!(pyramid.level(0).unwrap().buffer(), src_image.buffer());
+    for i in 1..pyramid.levels().len() {
+        let level = pyramid.levels()[i];
+        let prev_level = pyramid.level(i - 1).unwrap();
+        let expected = resize::<P>(&prev_level, level.width, level.height, &resize_options);
+        assert_eq!(pyramid.level(i).unwrap().buffer(), expected, "level {i}");
+    }
+
+    let options = options.from_original(true);
+    let mut pyramid = pyramid;
+    // Buffer of the existing pyramid is reused.
+    resizer
+        .build_pyramid_into(&src_image, &mut pyramid, &options)
+        .unwrap();
+    for (i, level) in pyramid.levels().iter().enumerate().skip(1) {
+        let expected = resize::<P>(&src_image, level.width, level.height, &resize_options);
+        assert_eq!(pyramid.level(i).unwrap().buffer(), expected, "level {i}");
+    }
+    let last_level = pyramid.levels().last().unwrap();
+    assert_eq!((last_level.width, last_level.height), (1, 1));
+}
+
+#[test]
+fn oriented_and_cropped_source() {
+    let (width, height) = (12, 5);
+    let pixels: Vec<u8> = (0..width * height * 4)
+        .map(|i| (i * 7 % 256) as u8)
+        .collect();
+    let src_image = Image::from_vec_u8(width, height, pixels, PixelType::U8x4).unwrap();
+    let rotated_options = ResizeOptions::new()
+        .resize_alg(ResizeAlg::Convolution(FilterType::Bilinear))
+        .orientation(Orientation::Rotate90);
+    let cropped_options = rotated_options.crop(1., 2., 4., 9.);
+    let mut resizer = Resizer::new();
+
+    for (resize_options, sizes) in [
+        (rotated_options, vec![(5, 12), (2, 6), (1, 3), (1, 1)]),
+        (cropped_options, vec![(4, 9), (2, 4), (1, 2), (1, 1)]),
+    ] {
+        let options = PyramidOptions::new()
+            .resize_options(&resize_options)
+            .from_original(true);
+        let pyramid = resizer.build_pyramid(&src_image, &options).unwrap();
+        let level_sizes: Vec<(u32, u32)> = pyramid
+            .levels()
+            .iter()
+            .map(|level| (level.width, level.height))
+            .collect();
+        assert_eq!(level_sizes, sizes);
+        for (i, level) in pyramid.levels().iter().enumerate() {
+            let expected = resize::<U8x4>(&src_image, level.width, level.height, &resize_options);
+            assert_eq!(pyramid.level(i).unwrap().buffer(), expected, "level {i}");
+        }
+
+        // Levels resized from previous ones have the same sizes.
+        let options = options.from_original(false);
+        let pyramid = resizer.build_pyramid(&src_image, &options).unwrap();
+        assert_eq!(pyramid.levels().len(), sizes.len());
+        let first_level = resize::<U8x4>(&src_image, sizes[0].0, sizes[0].1, &resize_options);
+        assert_eq!(pyramid.level(0).unwrap().buffer(), first_level);
+    }
+}
+
+#[test]
+fn same_result_as_resizer_u8x4() {
+    same_result_as_resizer::<U8x4>();
+}
+
+#[cfg(not(feature = "only_u8x4"))]
+mod not_u8x4 {
+    use super::*;
+
+    #[test]
+    fn same_result_as_resizer_u8() {
+        same_result_as_resizer::<U8>();
+    }
+
+    #[test]
+    fn same_result_as_resizer_u16x3() {
+        same_result_as_resizer::<U16x3>();
+    }
+
+    #[test]
+    fn same_result_as_resizer_f32() {
+        same_result_as_resizer::<F32>();
+    }
+}
@@ -4,8 +4,9 @@ use std::fmt::Debug;
 use fast_image_resize::images::Image;
 use fast_image_resize::pixels::*;
 use fast_image_resize::{
-    testing as fr_testing, CpuExtensions, CropBoxError, Filter, FilterType, IntoImageView,
-    PixelTrait, PixelType, ResizeAlg, ResizeError, ResizeOptions, Resizer,
+    testing as fr_testing, AlphaMode, AxisFilter, CpuExtensions, CropBoxError, EdgeMode, Filter,
+    FilterType, ImageView, ImageViewMut, IntoImageView, PixelTrait, PixelType, ResizeAlg,
+    ResizeError, ResizeOptions, ResizePlan, Resizer,
 };
 use testing::{cpu_ext_into_str, image_checksum, save_result, PixelTestingExt};
 
@@ -223,6 +224,7 @@ fn resize_to_same_width_or_height_after_cropping() {
             resize_to_same_width(PixelType::U16x4, cpu_extensions, |v| {
                 [v, 0, v, 0, v, 0, v, 0]
             });
+            resize_to_same_width(PixelType::I32, cpu_extensions, |v| (v as i32).to_le_bytes());
         }
         resize_to_same_height(PixelType::U8x4, cpu_extensions, |v| [v; 4]);
         #[cfg(not(feature = "only_u8x4"))]
@@ -236,19 +238,14 @@ fn resize_to_same_width_or_height_after_cropping() {
             resize_to_same_height(PixelType::U16x4, cpu_extensions, |v| {
                 [v, 0, v, 0, v, 0, v, 0]
             });
+            resize_to_same_height(PixelType::I32, cpu_extensions, |v| (v as i32).to_le_bytes());
         }
     }
     #[cfg(not(feature = "only_u8x4"))]
     {
-        resize_to_same_width(PixelType::I32, CpuExtensions::None, |v| {
-            (v as i32).to_le_bytes()
-        });
         resize_to_same_width(PixelType::F32, CpuExtensions::None, |v| {
             (v as f32).to_le_bytes()
         });
-        resize_to_same_height(PixelType::I32, CpuExtensions::None, |v| {
-            (v as i32).to_le_bytes()
-        });
         resize_to_same_height(PixelType::F32, CpuExtensions::None, |v| {
             (v as f32).to_le_bytes()
         });
@@ -288,7 +285,9 @@ where
             .resize(
                 &image,
                 &mut result,
-                &ResizeOptions::new().resize_alg(resize_alg).use_alpha(false),
+                &ResizeOptions::new()
+                    .resize_alg(resize_alg.clone())
+                    .use_alpha(false),
             )
             .unwrap();
 
@@ -354,7 +353,9 @@ where
             .resize(
                 &image,
                 &mut result,
-                &ResizeOptions::new().resize_alg(resize_alg).use_alpha(false),
+                &ResizeOptions::new()
+                    .resize_alg(resize_alg.clone())
+                    .use_alpha(false),
             )
             .unwrap();
 
@@ -855,12 +856,339 @@ mod not_u8x4 {
             .unwrap();
         assert_eq!(dst_image.buffer()[0], 255);
     }
+
+    #[test]
+    fn area() {
+        let mut resizer = Resizer::new();
+        let options = ResizeOptions::new().resize_alg(ResizeAlg::Area);
+        let mut resize = |src_pixels: &[u8], dst_width: u32| {
+            let mut src_buf = src_pixels.to_vec();
+            let src_width = src_buf.len() as u32;
+            let src_image =
+                Image::from_slice_u8(src_width, 1, &mut src_buf, PixelType::U8).unwrap();
+            let mut dst_image = Image::new(dst_width, 1, PixelType::U8);
+            resizer
+                .resize(&src_image, &mut dst_image, &options)
+                .unwrap();
+            dst_image.into_vec()
+        };
+
+        // Expected values are calculated in the same way as `cv2.resize()`
+        // does with `interpolation=cv2.INTER_AREA`.
+        assert_eq!(resize(&[30, 90, 150], 2), [50, 130]);
+        assert_eq!(resize(&[10, 20, 30, 40, 50, 60], 2), [20, 50]);
+        assert_eq!(resize(&[10, 20, 30, 40, 50, 60], 4), [13, 27, 43, 57]);
+        assert_eq!(resize(&[0, 100], 4), [0, 0, 100, 100]);
+        assert_eq!(resize(&[0, 30], 3), [0, 15, 30]);
+
+        // The fractional left border of the crop box is kept in weights
+        // of source pixels. Every destination pixel covers 2/3 of a source
+        // pixel, so the first one covers 3/4 of its area by the pixel 0 and
+        // the last one covers 3/4 of its area by the pixel 2.
+        let mut src_buf = vec![0, 100, 200, 40];
+        let src_image = Image::from_slice_u8(4, 1, &mut src_buf, PixelType::U8).unwrap();
+        let mut dst_image = Image::new(3, 1, PixelType::U8);
+        resizer
+            .resize(&src_image, &mut dst_image, &options.crop(0.5, 0., 2., 1.))
+            .unwrap();
+        assert_eq!(dst_image.buffer(), [25, 100, 175]);
+        resizer
+            .resize(&src_image, &mut dst_image, &options.crop(1.5, 0., 2.25, 1.))
+            .unwrap();
+        assert_eq!(dst_image.buffer(), [133, 200, 40]);
+
+        // Downscaling by an integer factor is equal to the `IntegerBox` algorithm.
+        let src_image = U8::load_small_src_image();
+        let mut area_image = Image::new(284, 189, PixelType::U8);
+        resizer
+            .resize(&src_image, &mut area_image, &options)
+            .unwrap();
+        let mut box_image = Image::new(284, 189, PixelType::U8);
+        resizer
+            .resize(
+                &src_image,
+                &mut box_image,
+                &ResizeOptions::new().resize_alg(ResizeAlg::IntegerBox),
+            )
+            .unwrap();
+        assert!(area_image.buffer() == box_image.buffer());
+    }
+
+    /// Crop box of the source image and size of the destination image.
+    type AreaCase = ((u32, u32, u32, u32), (u32, u32));
+
+    /// Cases used by `data/opencv_area/generate.py`.
+    const OPENCV_AREA_CASES: [AreaCase; 9] = [
+        ((0, 0, 24, 18), (12, 9)),
+        ((0, 0, 24, 18), (8, 6)),
+        ((0, 0, 24, 18), (10, 7)),
+        ((0, 0, 24, 18), (17, 13)),
+        ((0, 0, 24, 18), (48, 36)),
+        ((0, 0, 24, 18), (31, 25)),
+        ((0, 0, 24, 18), (31, 9)),
+        ((2, 3, 20, 15), (9, 7)),
+        ((5, 1, 11, 16), (25, 21)),
+    ];
+
+    /// Loads an image created by `data/opencv_area/generate.py`.
+    fn load_opencv_area_image(file_name: &str, pixel_type: PixelType) -> Image<'static> {
+        let path = format!("./data/opencv_area/{file_name}");
+        let img = image::ImageReader::open(&path)
+            .unwrap_or_else(|_| panic!("{path} is not found, run data/opencv_area/generate.py"))
+            .decode()
+            .unwrap();
+        let buffer = match pixel_type {
+            PixelType::U8x3 => img.to_rgb8().into_raw(),
+            PixelType::U16 => img
+                .to_luma16()
+                .into_raw()
+                .iter()
+                .flat_map(|v| v.to_le_bytes())
+                .collect(),
+            _ => unreachable!(),
+        };
+        Image::from_vec_u8(img.width(), img.height(), buffer, pixel_type).unwrap()
+    }
+
+    /// Compares results of the `Area` algorithm with results of `cv2.resize()`
+    /// called with `interpolation=cv2.INTER_AREA`. OpenCV and this crate
+    /// round intermediate values differently, so components may differ by 1.
+    fn check_area_like_opencv<const N: usize>(
+        pixel_type: PixelType,
+        name: &str,
+        from_bytes: fn([u8; N]) -> i64,
+    ) {
+        let src_image = load_opencv_area_image(&format!("{name}-src.png"), pixel_type);
+        let mut resizer = Resizer::new();
+        for ((left, top, width, height), (dst_width, dst_height)) in OPENCV_AREA_CASES {
+            let file_name =
+                format!("{name}-{left}-{top}-{width}x{height}-{dst_width}x{dst_height}.png");
+            let expected = load_opencv_area_image(&file_name, pixel_type);
+            let mut dst_image = Image::new(dst_width, dst_height, pixel_type);
+            let options = ResizeOptions::new().resize_alg(ResizeAlg::Area).crop(
+                left as _,
+                top as _,
+                width as _,
+                height as _,
+            );
+            resizer
+                .resize(&src_image, &mut dst_image, &options)
+                .unwrap();
+            let components = |image: &Image| -> Vec<i64> {
+                image
+                    .buffer()
+                    .chunks_exact(N)
+                    .map(|bytes| from_bytes(bytes.try_into().unwrap()))
+                    .collect()
+            };
+            let result = components(&dst_image);
+            for (i, (value, expected)) in result.iter().zip(components(&expected)).enumerate() {
+                assert!(
+                    (value - expected).abs() <= 1,
+                    "{file_name}: component {i} is {value} instead of {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn area_like_opencv() {
+        check_area_like_opencv(PixelType::U8x3, "u8x3", |[b]| b as i64);
+        check_area_like_opencv(PixelType::U16, "u16", |bytes| {
+            u16::from_le_bytes(bytes) as i64
+        });
+    }
+
+    /// Resizes an image with the `IntegerBox` algorithm and compares the result
+    /// with averages of blocks of source pixels calculated from components
+    /// converted into `f64`.
+    fn check_integer_box<const N: usize>(
+        pixel_type: PixelType,
+        src_components: &[f64],
+        to_bytes: fn(f64) -> [u8; N],
+        from_bytes: fn([u8; N]) -> f64,
+        round: fn(f64) -> f64,
+    ) {
+        let cc = pixel_type.size() / N;
+        let (src_width, src_height) = (24, 12);
+        let src_buf: Vec<u8> = src_components
+            .iter()
+            .take(src_width * src_height * cc)
+            .flat_map(|&v| to_bytes(v))
+            .collect();
+        let src_image =
+            Image::from_vec_u8(src_width as u32, src_height as u32, src_buf, pixel_type).unwrap();
+
+        let cases = [
+            ((0, 0, 24, 12), (12, 6)),
+            ((0, 0, 24, 12), (8, 4)),
+            ((0, 0, 24, 12), (6, 3)),
+            ((0, 0, 24, 12), (6, 4)),
+            ((0, 0, 24, 12), (24, 6)),
+            ((0, 0, 24, 12), (1, 1)),
+            ((2, 1, 12, 9), (3, 3)),
+        ];
+        for cpu_extensions in [
+            CpuExtensions::None,
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1,
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2,
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon,
+            #[cfg(target_arch = "wasm32")]
+            CpuExtensions::Simd128,
+        ] {
+            if !cpu_extensions.is_supported() {
+                continue;
+            }
+            let mut resizer = Resizer::new();
+            unsafe {
+                resizer.set_cpu_extensions(cpu_extensions);
+            }
+            for ((left, top, width, height), (dst_width, dst_height)) in cases {
+                let (factor_x, factor_y) = (width / dst_width, height / dst_height);
+                let mut expected = Vec::new();
+                for y in 0..dst_height {
+                    for x in 0..dst_width {
+                        for c in 0..cc {
+                            let mut sum = 0.;
+                            for sy in top + y * factor_y..top + (y + 1) * factor_y {
+                                for sx in left + x * factor_x..left + (x + 1) * factor_x {
+                                    sum += src_components[(sy * src_width + sx) * cc + c];
+                                }
+                            }
+                            expected.push(round(sum / (factor_x * factor_y) as f64));
+                        }
+                    }
+                }
+
+                let mut dst_image = Image::new(dst_width as u32, dst_height as u32, pixel_type);
+                // Alpha channels are averaged as other components
+                // if the source image has premultiplied alpha.
+                let options = ResizeOptions::new()
+                    .resize_alg(ResizeAlg::IntegerBox)
+                    .alpha_modes(AlphaMode::Premultiplied, AlphaMode::Premultiplied)
+                    .crop(left as _, top as _, width as _, height as _);
+                resizer
+                    .resize(&src_image, &mut dst_image, &options)
+                    .unwrap();
+                let result: Vec<f64> = dst_image
+                    .buffer()
+                    .chunks_exact(N)
+                    .map(|bytes| from_bytes(bytes.try_into().unwrap()))
+                    .collect();
+                assert_eq!(
+                    result, expected,
+                    "{pixel_type:?}, {cpu_extensions:?}, {dst_width}x{dst_height}"
+                );
+
+                // The `Area` algorithm gives the same result.
+                let mut area_image = Image::new(dst_width as u32, dst_height as u32, pixel_type);
+                resizer
+                    .resize(
+                        &src_image,
+                        &mut area_image,
+                        &options.resize_alg(ResizeAlg::Area),
+                    )
+                    .unwrap();
+                assert!(area_image.buffer() == dst_image.buffer());
+            }
+        }
+    }
+
+    #[test]
+    fn integer_box() {
+        let mut state = 1u32;
+        let random_values: Vec<u32> = (0..24 * 12 * 4)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                state >> 8
+            })
+            .collect();
+        let components = |max: u32, k: f64| -> Vec<f64> {
+            random_values
+                .iter()
+                .map(|&v| (v % max) as f64 * k)
+                .collect()
+        };
+        let round_half_up = |v: f64| (v + 0.5).floor();
+
+        let u8_components = components(256, 1.);
+        for pixel_type in [
+            PixelType::U8,
+            PixelType::U8x2,
+            PixelType::U8x3,
+            PixelType::U8x4,
+        ] {
+            check_integer_box(
+                pixel_type,
+                &u8_components,
+                |v| [v as u8],
+                |b| b[0] as f64,
+                round_half_up,
+            );
+        }
+        let u16_components = components(0x10000, 1.);
+        for pixel_type in [
+            PixelType::U16,
+            PixelType::U16x2,
+            PixelType::U16x3,
+            PixelType::U16x4,
+        ] {
+            check_integer_box(
+                pixel_type,
+                &u16_components,
+                |v| (v as u16).to_ne_bytes(),
+                |b| u16::from_ne_bytes(b) as f64,
+                round_half_up,
+            );
+        }
+        let i32_components: Vec<f64> = components(1 << 24, 1.)
+            .into_iter()
+            .map(|v| v - (1 << 23) as f64)
+            .collect();
+        check_integer_box(
+            PixelType::I32,
+            &i32_components,
+            |v| (v as i32).to_ne_bytes(),
+            |b| i32::from_ne_bytes(b) as f64,
+            round_half_up,
+        );
+        let f32_components = components(1 << 16, 1. / 256.);
+        for pixel_type in [
+            PixelType::F32,
+            PixelType::F32x2,
+            PixelType::F32x3,
+            PixelType::F32x4,
+        ] {
+            check_integer_box(
+                pixel_type,
+                &f32_components,
+                |v| (v as f32).to_ne_bytes(),
+                |b| f32::from_ne_bytes(b) as f64,
+                |v| v as f32 as f64,
+            );
+        }
+
+        let src_image = Image::new(24, 12, PixelType::U8x4);
+        let mut dst_image = Image::new(5, 6, PixelType::U8x4);
+        assert!(matches!(
+            Resizer::new().resize(
+                &src_image,
+                &mut dst_image,
+                &ResizeOptions::new().resize_alg(ResizeAlg::IntegerBox),
+            ),
+            Err(ResizeError::NotIntegerScaleFactor)
+        ));
+    }
 }
 
 mod u8x4 {
     use std::f64::consts::PI;
+    use std::sync::Arc;
 
-    use fast_image_resize::ResizeError;
+    use fast_image_resize::{ResizeError, SharedFilterFn};
     use image::ImageReader;
 
     use super::*;
@@ -1045,6 +1373,636 @@ mod u8x4 {
             );
         }
     }
+
+    #[test]
+    fn resize_with_plan() {
+        let src_image = P::load_small_src_image();
+        let src_width = src_image.width();
+        let src_height = src_image.height();
+        let mut resizer = Resizer::new();
+
+        for resize_alg in [
+            ResizeAlg::Nearest,
+            ResizeAlg::Convolution(FilterType::Lanczos3),
+            ResizeAlg::SuperSampling(FilterType::Bilinear, 2),
+        ] {
+            for (dst_width, dst_height) in [(255, 170), (1000, 700), (src_width, 300)] {
+                let options = ResizeOptions::new()
+                    .resize_alg(resize_alg.clone())
+                    .crop(10., 20., 500., 400.);
+                let plan = ResizePlan::new(
+                    P::pixel_type(),
+                    src_width,
+                    src_height,
+                    dst_width,
+                    dst_height,
+                    &options,
+                )
+                .unwrap();
+
+                let mut expected_image = Image::new(dst_width, dst_height, P::pixel_type());
+                resizer
+                    .resize(&src_image, &mut expected_image, &options)
+                    .unwrap();
+
+                // The same plan may be used several times.
+                for _ in 0..2 {
+                    let mut dst_image = Image::new(dst_width, dst_height, P::pixel_type());
+                    resizer
+                        .resize_with_plan(&plan, &src_image, &mut dst_image)
+                        .unwrap();
+                    assert_eq!(dst_image.buffer(), expected_image.buffer());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn images_do_not_match_plan() {
+        let mut resizer = Resizer::new();
+        let plan = ResizePlan::new(P::pixel_type(), 64, 32, 16, 8, None).unwrap();
+
+        let src_image = Image::new(64, 32, P::pixel_type());
+        let mut dst_image = Image::new(16, 8, P::pixel_type());
+        assert_eq!(
+            resizer.resize_with_plan(&plan, &src_image, &mut dst_image),
+            Ok(())
+        );
+
+        let wrong_src_image = Image::new(64, 33, P::pixel_type());
+        assert_eq!(
+            resizer.resize_with_plan(&plan, &wrong_src_image, &mut dst_image),
+            Err(ResizeError::ImagesDoNotMatchPlan)
+        );
+
+        let mut wrong_dst_image = Image::new(16, 9, P::pixel_type());
+        assert_eq!(
+            resizer.resize_with_plan(&plan, &src_image, &mut wrong_dst_image),
+            Err(ResizeError::ImagesDoNotMatchPlan)
+        );
+    }
+
+    #[test]
+    fn invalid_crop_box_in_plan() {
+        let options = ResizeOptions::new().crop(1., 0., 1., 1.);
+        assert_eq!(
+            ResizePlan::new(P::pixel_type(), 1, 1, 2, 2, &options).err(),
+            Some(ResizeError::SrcCroppingError(
+                CropBoxError::PositionIsOutOfImageBoundaries
+            ))
+        );
+    }
+
+    #[test]
+    fn fit_into_destination_with_padding() {
+        let src_image = P::load_small_src_image();
+        let fill_color = P::new([10, 20, 30, 40]);
+        let options = ResizeOptions::new().crop(10., 20., 400., 200.);
+        let mut resizer = Resizer::new();
+
+        // The cropped image with aspect ratio 2:1 is resized to 300x150
+        // and placed at the position (0, 38) of the destination image.
+        let mut expected_inner_image = Image::new(300, 150, P::pixel_type());
+        resizer
+            .resize(&src_image, &mut expected_inner_image, &options)
+            .unwrap();
+        let expected_inner_view = expected_inner_image.typed_image::<P>().unwrap();
+        let mut expected_rows = expected_inner_view.iter_rows(0);
+
+        let mut dst_image = Image::new(300, 300, P::pixel_type());
+        let options = options.fit_into_destination_with_padding(fill_color, Some((0.5, 0.25)));
+        resizer
+            .resize(&src_image, &mut dst_image, &options)
+            .unwrap();
+        let dst_view = dst_image.typed_image::<P>().unwrap();
+        for (y, row) in dst_view.iter_rows(0).enumerate() {
+            if (38..188).contains(&y) {
+                assert_eq!(row, expected_rows.next().unwrap(), "row {y}");
+            } else {
+                assert!(row.iter().all(|&p| p == fill_color), "row {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn parameterized_filters() {
+        let src_image = P::load_small_src_image();
+        let mut resizer = Resizer::new();
+        let mut resize = |filter_type: &FilterType| {
+            let mut dst_image = Image::new(300, 200, P::pixel_type());
+            let options =
+                ResizeOptions::new().resize_alg(ResizeAlg::Convolution(filter_type.clone()));
+            resizer
+                .resize(&src_image, &mut dst_image, &options)
+                .map(|_| dst_image.into_vec())
+        };
+
+        for (parameterized, builtin) in [
+            (FilterType::Lanczos(3), FilterType::Lanczos3),
+            (FilterType::Cubic { b: 0., c: 0.5 }, FilterType::CatmullRom),
+            (
+                FilterType::Cubic {
+                    b: 1. / 3.,
+                    c: 1. / 3.,
+                },
+                FilterType::Mitchell,
+            ),
+            (
+                FilterType::GaussianWithSigma { sigma: 0.5 },
+                FilterType::Gaussian,
+            ),
+        ] {
+            let result = resize(&parameterized).unwrap();
+            let expected = resize(&builtin).unwrap();
+            let max_diff = result
+                .iter()
+                .zip(&expected)
+                .map(|(&a, &b)| a.abs_diff(b))
+                .max()
+                .unwrap();
+            assert!(max_diff <= 1, "{parameterized:?}: max diff is {max_diff}");
+        }
+
+        let lanczos3 = resize(&FilterType::Lanczos3).unwrap();
+        for filter_type in [
+            FilterType::Lanczos(2),
+            FilterType::Kaiser {
+                alpha: 6.,
+                support: 3.,
+            },
+            FilterType::Blackman,
+            FilterType::Welch,
+        ] {
+            // Windowed sinc filters give results similar to Lanczos3.
+            let result = resize(&filter_type).unwrap();
+            let mean_diff = result
+                .iter()
+                .zip(&lanczos3)
+                .map(|(&a, &b)| a.abs_diff(b) as f64)
+                .sum::<f64>()
+                / result.len() as f64;
+            assert!(mean_diff < 3., "{filter_type:?}: mean diff is {mean_diff}");
+        }
+
+        for filter_type in [
+            FilterType::Lanczos(0),
+            FilterType::Cubic { b: f64::NAN, c: 0. },
+            FilterType::GaussianWithSigma { sigma: 0. },
+            FilterType::Kaiser {
+                alpha: -1.,
+                support: 3.,
+            },
+            FilterType::Kaiser {
+                alpha: 6.,
+                support: f64::INFINITY,
+            },
+        ] {
+            assert_eq!(
+                resize(&filter_type),
+                Err(ResizeError::InvalidFilterParameters),
+                "{filter_type:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn closure_filter() {
+        let src_image = P::load_small_src_image();
+        let mut resizer = Resizer::new();
+        let mut resize = |filter_type: FilterType| {
+            let mut dst_image = Image::new(300, 200, P::pixel_type());
+            let options = ResizeOptions::new().resize_alg(ResizeAlg::Convolution(filter_type));
+            resizer
+                .resize(&src_image, &mut dst_image, &options)
+                .unwrap();
+            dst_image.into_vec()
+        };
+
+        // Parameters of the filter are known only at runtime.
+        let lobes: f64 = "3".parse().unwrap();
+        let sinc = |mut x: f64| {
+            if x == 0.0 {
+                1.0
+            } else {
+                x *= PI;
+                x.sin() / x
+            }
+        };
+        let lanczos = move |x: f64| {
+            if (-lobes..lobes).contains(&x) {
+                sinc(x) * sinc(x / lobes)
+            } else {
+                0.0
+            }
+        };
+        let expected = resize(FilterType::Lanczos3);
+
+        let name = format!("Lanczos{lobes}");
+        let shared_fn: SharedFilterFn = Arc::new(lanczos);
+        let filter = Filter::from_shared_fn(name, shared_fn.clone(), lobes).unwrap();
+        assert_eq!(filter.name(), "Lanczos3");
+        let filter_type = FilterType::Custom(filter.clone());
+        assert!(resize(filter_type.clone()) == expected);
+        assert!(resize(filter_type.clone()) == resize(filter_type.clone()));
+        assert!(Filter::from_shared_fn("Bad", shared_fn.clone(), 0.).is_err());
+
+        // The function is freed with the last clone of the filter.
+        assert_eq!(Arc::strong_count(&shared_fn), 3);
+        drop(filter_type);
+        assert_eq!(Arc::strong_count(&shared_fn), 2);
+        drop(filter);
+        assert_eq!(Arc::strong_count(&shared_fn), 1);
+
+        // Filters with the same name and support but different functions
+        // are not equal.
+        let filter = Filter::from_shared_fn("Lanczos3", shared_fn.clone(), lobes).unwrap();
+        assert_eq!(filter, filter.clone());
+        assert_ne!(
+            filter,
+            Filter::from_shared_fn("Lanczos3", Arc::new(lanczos), lobes).unwrap()
+        );
+        static BOX_FILTER: fn(f64) -> f64 = |x| if x.abs() < 0.5 { 1. } else { 0. };
+        let static_filter = Filter::from_static_fn("Box", &BOX_FILTER, 0.5).unwrap();
+        assert_eq!(
+            static_filter,
+            Filter::from_static_fn("Box", &BOX_FILTER, 0.5).unwrap()
+        );
+        assert_ne!(
+            FilterType::Custom(static_filter),
+            FilterType::Custom(Filter::from_shared_fn("Box", Arc::new(BOX_FILTER), 0.5).unwrap())
+        );
+    }
+
+    #[test]
+    fn kernel_scale() {
+        let src_image = P::load_small_src_image();
+        let mut resizer = Resizer::new();
+        let mut resize = |width: u32, height: u32, options: &ResizeOptions| {
+            let mut dst_image = Image::new(width, height, P::pixel_type());
+            resizer
+                .resize(&src_image, &mut dst_image, options)
+                .map(|_| dst_image.into_vec())
+        };
+        let (width, height) = (src_image.width() / 4, src_image.height());
+
+        // The fixed kernel scaled by the factor of downscaling
+        // is equal to the adaptive kernel.
+        let options = ResizeOptions::new()
+            .resize_alg(ResizeAlg::Interpolation(FilterType::Bilinear))
+            .kernel_scale(4.);
+        let expected_options =
+            ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Bilinear));
+        assert!(
+            resize(width, height, &options).unwrap()
+                == resize(width, height, &expected_options).unwrap()
+        );
+
+        // A wider kernel gives a smoother result.
+        let mut sharpness = |kernel_scale: f64| {
+            let options = ResizeOptions::new().kernel_scale(kernel_scale);
+            let pixels = resize(300, 200, &options).unwrap();
+            pixels
+                .windows(8)
+                .step_by(4)
+                .map(|w| w[0].abs_diff(w[4]) as u64)
+                .sum::<u64>()
+        };
+        let default_sharpness = sharpness(1.);
+        assert!(sharpness(2.) < default_sharpness);
+        assert!(sharpness(0.7) > default_sharpness);
+
+        for kernel_scale in [0., -1., f64::NAN, f64::INFINITY] {
+            let options = ResizeOptions::new().kernel_scale(kernel_scale);
+            assert_eq!(
+                resize(300, 200, &options),
+                Err(ResizeError::InvalidKernelScale),
+                "{kernel_scale}"
+            );
+        }
+    }
+
+    #[test]
+    fn reducing_gap() {
+        let src_image = P::load_small_src_image();
+        let mut resizer = Resizer::new();
+        let mut resize = |width: u32, height: u32, resize_alg: ResizeAlg| {
+            let mut dst_image = Image::new(width, height, P::pixel_type());
+            let options = ResizeOptions::new().resize_alg(resize_alg);
+            resizer
+                .resize(&src_image, &mut dst_image, &options)
+                .map(|_| dst_image.into_vec())
+        };
+
+        // The image is resized in two steps only if it is large enough.
+        for (width, height) in [(300, 200), (60, 40), (852, 20)] {
+            let expected =
+                resize(width, height, ResizeAlg::Convolution(FilterType::Lanczos3)).unwrap();
+            let result = resize(
+                width,
+                height,
+                ResizeAlg::ReducingGap(FilterType::Lanczos3, 3.),
+            )
+            .unwrap();
+            let mean_diff = result
+                .iter()
+                .zip(&expected)
+                .map(|(&a, &b)| a.abs_diff(b) as f64)
+                .sum::<f64>()
+                / result.len() as f64;
+            assert!(mean_diff < 2., "{width}x{height}: mean diff is {mean_diff}");
+        }
+        assert!(
+            resize(300, 200, ResizeAlg::ReducingGap(FilterType::Lanczos3, 3.)).unwrap()
+                == resize(300, 200, ResizeAlg::Convolution(FilterType::Lanczos3)).unwrap()
+        );
+
+        // Reducing by an integer factor is equal to the integer box filter.
+        assert!(
+            resize(213, 189, ResizeAlg::ReducingGap(FilterType::Lanczos3, 1.)).unwrap()
+                == resize(213, 189, ResizeAlg::IntegerBox).unwrap()
+        );
+
+        for reducing_gap in [0.5, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                resize(
+                    60,
+                    40,
+                    ResizeAlg::ReducingGap(FilterType::Lanczos3, reducing_gap)
+                ),
+                Err(ResizeError::InvalidReducingGap),
+                "{reducing_gap}"
+            );
+        }
+    }
+
+    /// Reduces the region of the image like `Image.reduce()` in Pillow.
+    fn reduce_like_pillow(
+        image: &Image,
+        (left, top, width, height): (u32, u32, u32, u32),
+        (factor_x, factor_y): (u32, u32),
+    ) -> Image<'static> {
+        let src_pixels = image.typed_image::<P>().unwrap();
+        let dst_width = width.div_ceil(factor_x);
+        let dst_height = height.div_ceil(factor_y);
+        let mut buffer = Vec::new();
+        for dst_y in 0..dst_height {
+            let rows = top + dst_y * factor_y..top + ((dst_y + 1) * factor_y).min(height);
+            for dst_x in 0..dst_width {
+                let cols = left + dst_x * factor_x..left + ((dst_x + 1) * factor_x).min(width);
+                let count = rows.len() as u32 * cols.len() as u32;
+                let mut sums = [0u32; 4];
+                for y in rows.clone() {
+                    let row = src_pixels.iter_rows(y).next().unwrap();
+                    for pixel in &row[cols.clone().start as usize..cols.end as usize] {
+                        for (sum, &c) in sums.iter_mut().zip(&pixel.0) {
+                            *sum += c as u32;
+                        }
+                    }
+                }
+                buffer.extend(sums.map(|sum| ((sum + count / 2) / count) as u8));
+            }
+        }
+        Image::from_vec_u8(dst_width, dst_height, buffer, P::pixel_type()).unwrap()
+    }
+
+    #[test]
+    fn reducing_gap_like_pillow() {
+        let src_image = P::load_small_src_image();
+        let mut resizer = Resizer::new();
+        // (crop box, size of the destination image, reducing box, factors)
+        let cases = [
+            // Blocks of the last row are partial.
+            ((0., 0., 852., 567.), (100, 70), (0, 0, 852, 567), (4, 4)),
+            // Blocks of the last column are partial.
+            (
+                (100.5, 50., 600., 400.),
+                (70, 50),
+                (79, 30, 643, 440),
+                (4, 4),
+            ),
+        ];
+        for ((left, top, width, height), (dst_width, dst_height), region, factors) in cases {
+            let options = ResizeOptions::new().use_alpha(false);
+            let mut result = Image::new(dst_width, dst_height, P::pixel_type());
+            resizer
+                .resize(
+                    &src_image,
+                    &mut result,
+                    &options
+                        .resize_alg(ResizeAlg::ReducingGap(FilterType::Lanczos3, 2.))
+                        .crop(left, top, width, height),
+                )
+                .unwrap();
+
+            let reduced = reduce_like_pillow(&src_image, region, factors);
+            let (factor_x, factor_y) = (factors.0 as f64, factors.1 as f64);
+            let mut expected = Image::new(dst_width, dst_height, P::pixel_type());
+            resizer
+                .resize(
+                    &reduced,
+                    &mut expected,
+                    &options
+                        .resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3))
+                        .crop(
+                            (left - region.0 as f64) / factor_x,
+                            (top - region.1 as f64) / factor_y,
+                            width / factor_x,
+                            height / factor_y,
+                        ),
+                )
+                .unwrap();
+            assert!(result.buffer() == expected.buffer(), "{region:?}");
+        }
+    }
+
+    #[test]
+    fn per_axis_convolution() {
+        let src_image = P::load_small_src_image();
+        let mut resizer = Resizer::new();
+        let mut resize = |width: u32, height: u32, resize_alg: ResizeAlg| {
+            let mut dst_image = Image::new(width, height, P::pixel_type());
+            let options = ResizeOptions::new().resize_alg(resize_alg);
+            resizer
+                .resize(&src_image, &mut dst_image, &options)
+                .unwrap();
+            dst_image.into_vec()
+        };
+        let per_axis =
+            |horizontal: FilterType, vertical: FilterType| ResizeAlg::PerAxisConvolution {
+                horizontal: AxisFilter::convolution(horizontal),
+                vertical: AxisFilter::interpolation(vertical),
+            };
+        let (width, height) = (src_image.width(), src_image.height());
+
+        // The vertical filter is not used if the image is resized only horizontally.
+        assert!(
+            resize(300, height, per_axis(FilterType::Lanczos3, FilterType::Box))
+                == resize(300, height, ResizeAlg::Convolution(FilterType::Lanczos3))
+        );
+        // The horizontal filter is not used if the image is resized only vertically.
+        assert!(
+            resize(width, 200, per_axis(FilterType::Box, FilterType::Mitchell))
+                == resize(width, 200, ResizeAlg::Interpolation(FilterType::Mitchell))
+        );
+
+        let result = resize(300, 200, per_axis(FilterType::Lanczos3, FilterType::Box));
+        assert!(result != resize(300, 200, ResizeAlg::Convolution(FilterType::Lanczos3)));
+        assert!(result != resize(300, 200, ResizeAlg::Interpolation(FilterType::Box)));
+    }
+
+    #[test]
+    fn fill_color_with_other_pixel_type() {
+        let src_image = Image::new(64, 32, P::pixel_type());
+        let mut dst_image = Image::new(16, 16, P::pixel_type());
+        let options = ResizeOptions::new().fit_into_destination_with_padding(U8::new(0), None);
+        assert_eq!(
+            Resizer::new().resize(&src_image, &mut dst_image, &options),
+            Err(ResizeError::PixelTypesAreDifferent)
+        );
+    }
+}
+
+mod edge_modes {
+    use super::*;
+
+    /// Shifts pixels of the image cyclically to the left and to the top.
+    fn roll<P: PixelTrait>(image: &Image, dx: u32, dy: u32) -> Image<'static> {
+        let src_view = image.typed_image::<P>().unwrap();
+        let mut dst_image = Image::new(image.width(), image.height(), P::pixel_type());
+        let mut dst_view = dst_image.typed_image_mut::<P>().unwrap();
+        for (y, dst_row) in dst_view.iter_rows_mut(0).enumerate() {
+            let src_y = (y as u32 + dy) % image.height();
+            let src_row = src_view.iter_rows(src_y).next().unwrap();
+            for (x, dst_pixel) in dst_row.iter_mut().enumerate() {
+                *dst_pixel = src_row[(x + dx as usize) % src_row.len()];
+            }
+        }
+        dst_image
+    }
+
+    #[test]
+    fn wrap_gives_tileable_result() {
+        type P = U8x4;
+        let (width, height) = (100, 64);
+        let mut seed = 1u32;
+        let buffer: Vec<u8> = (0..width * height * 16)
+            .map(|_| {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                (seed >> 16) as u8
+            })
+            .collect();
+        let src_image = Image::from_vec_u8(width * 2, height * 2, buffer, P::pixel_type()).unwrap();
+        let options = ResizeOptions::new()
+            .resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3))
+            .edge_mode(EdgeMode::Wrap);
+        let mut resizer = Resizer::new();
+
+        let mut dst_image = Image::new(width, height, P::pixel_type());
+        resizer
+            .resize(&src_image, &mut dst_image, &options)
+            .unwrap();
+
+        // Shifting of the source image by two pixels must shift
+        // the downscaled image by one pixel.
+        let rolled_src_image = roll::<P>(&src_image, 2, 2);
+        let mut rolled_dst_image = Image::new(width, height, P::pixel_type());
+        resizer
+            .resize(&rolled_src_image, &mut rolled_dst_image, &options)
+            .unwrap();
+        assert!(rolled_dst_image.buffer() == roll::<P>(&dst_image, 1, 1).buffer());
+    }
+
+    #[cfg(not(feature = "only_u8x4"))]
+    mod f32 {
+        use super::*;
+
+        const BORDER: u32 = 16;
+
+        /// Returns the source image with added borders filled accordingly
+        /// to the edge mode.
+        fn pad_image(src_image: &Image, edge_mode: EdgeMode) -> Image<'static> {
+            let width = src_image.width() as i64;
+            let height = src_image.height() as i64;
+            let source_index = |x: i64, size: i64| -> Option<usize> {
+                if (0..size).contains(&x) {
+                    return Some(x as usize);
+                }
+                match edge_mode {
+                    EdgeMode::Clamp => Some(x.clamp(0, size - 1) as usize),
+                    EdgeMode::Mirror => {
+                        let x = x.rem_euclid(size * 2);
+                        Some(if x < size { x } else { size * 2 - 1 - x } as usize)
+                    }
+                    EdgeMode::Wrap => Some(x.rem_euclid(size) as usize),
+                    _ => None,
+                }
+            };
+            let src_view = src_image.typed_image::<F32>().unwrap();
+            let src_rows: Vec<&[F32]> = src_view.iter_rows(0).collect();
+            let mut dst_image = Image::new(
+                src_image.width() + BORDER * 2,
+                src_image.height() + BORDER * 2,
+                PixelType::F32,
+            );
+            let mut dst_view = dst_image.typed_image_mut::<F32>().unwrap();
+            for (y, dst_row) in (0i64..).zip(dst_view.iter_rows_mut(0)) {
+                let src_y = source_index(y - BORDER as i64, height);
+                for (x, dst_pixel) in (0i64..).zip(dst_row.iter_mut()) {
+                    let src_x = source_index(x - BORDER as i64, width);
+                    if let (Some(src_x), Some(src_y)) = (src_x, src_y) {
+                        *dst_pixel = src_rows[src_y][src_x];
+                    }
+                }
+            }
+            dst_image
+        }
+
+        #[test]
+        fn same_result_as_resizing_of_padded_image() {
+            let src_image = F32::load_small_src_image();
+            let mut resizer = Resizer::new();
+            for edge_mode in [
+                EdgeMode::Clamp,
+                EdgeMode::Mirror,
+                EdgeMode::Wrap,
+                EdgeMode::Constant,
+            ] {
+                let padded_image = pad_image(&src_image, edge_mode);
+                for (dst_width, dst_height) in [(213, 189), (1000, 700)] {
+                    let options = ResizeOptions::new()
+                        .resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3));
+                    let mut dst_image = Image::new(dst_width, dst_height, PixelType::F32);
+                    resizer
+                        .resize(&src_image, &mut dst_image, &options.edge_mode(edge_mode))
+                        .unwrap();
+
+                    let mut expected_image = Image::new(dst_width, dst_height, PixelType::F32);
+                    let options = options.crop(
+                        BORDER as f64,
+                        BORDER as f64,
+                        src_image.width() as f64,
+                        src_image.height() as f64,
+                    );
+                    resizer
+                        .resize(&padded_image, &mut expected_image, &options)
+                        .unwrap();
+
+                    let result = dst_image.typed_image::<F32>().unwrap();
+                    let expected = expected_image.typed_image::<F32>().unwrap();
+                    let max_diff = result
+                        .pixels()
+                        .iter()
+                        .zip(expected.pixels())
+                        .map(|(a, b)| (a.0 - b.0).abs())
+                        .fold(0f32, f32::max);
+                    assert!(
+                        max_diff < 1e-3,
+                        "{edge_mode:?} {dst_width}x{dst_height}: max diff is {max_diff}"
+                    );
+                }
+            }
+        }
+    }
 }
 
 #[cfg(feature = "rayon")]
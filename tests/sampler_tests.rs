@@ -0,0 +1,127 @@
+use fast_image_resize::images::TypedImageRef;
+use fast_image_resize::pixels::*;
+use fast_image_resize::{EdgeMode, FilterType, ResizeError, Sampler};
+
+#[test]
+fn sample_u8x4() {
+    let pixels = [
+        U8x4::new([0, 0, 0, 255]),
+        U8x4::new([100, 200, 40, 255]),
+        U8x4::new([255, 255, 255, 0]),
+    ];
+    let image = TypedImageRef::new(3, 1, &pixels).unwrap();
+    let sampler = Sampler::new(&image, FilterType::Bilinear).unwrap();
+
+    // Centers of pixels
+    assert_eq!(sampler.sample(0.5, 0.5), pixels[0]);
+    assert_eq!(sampler.sample(1.5, 0.5), pixels[1]);
+    assert_eq!(sampler.sample(1.5, 0.2), pixels[1]);
+    // Between pixels
+    assert_eq!(sampler.sample(1.0, 0.5), U8x4::new([50, 100, 20, 255]));
+    assert_eq!(sampler.sample(0.75, 0.5), U8x4::new([25, 50, 10, 255]));
+    // Colors of the transparent pixel are ignored
+    assert_eq!(sampler.sample(2.0, 0.5), U8x4::new([100, 200, 40, 128]));
+    let sampler = sampler.use_alpha(false);
+    assert_eq!(sampler.sample(2.0, 0.5), U8x4::new([178, 228, 148, 128]));
+
+    let points = [(0.5, 0.5), (1.0, 0.5), (2.0, 0.5)];
+    let pixels = sampler.sample_many(&points);
+    let mut dst_pixels = [U8x4::default(); 3];
+    sampler.sample_into(&points, &mut dst_pixels);
+    assert_eq!(pixels, dst_pixels);
+    for (&(x, y), pixel) in points.iter().zip(pixels) {
+        assert_eq!(sampler.sample(x, y), pixel);
+    }
+}
+
+#[cfg(not(feature = "only_u8x4"))]
+mod not_u8x4 {
+    use super::*;
+
+    fn f32_pixels(values: &[f32]) -> Vec<F32> {
+        values.iter().map(|&v| F32::new(v)).collect()
+    }
+
+    #[test]
+    fn edge_modes() {
+        let pixels = f32_pixels(&[10., 20., 30., 40.]);
+        let image = TypedImageRef::new(4, 1, &pixels).unwrap();
+        let sampler = Sampler::new(&image, FilterType::Bilinear).unwrap();
+        let sample = |edge_mode: EdgeMode, x: f64| sampler.edge_mode(edge_mode).sample(x, 0.5).0;
+
+        assert_eq!(sample(EdgeMode::Renormalize, 0.), 10.);
+        assert_eq!(sample(EdgeMode::Clamp, 0.), 10.);
+        assert_eq!(sample(EdgeMode::Mirror, 0.), 10.);
+        assert_eq!(sample(EdgeMode::Wrap, 0.), 25.);
+        assert_eq!(sample(EdgeMode::Constant, 0.), 5.);
+
+        assert_eq!(sample(EdgeMode::Mirror, 4.75), 37.5);
+        assert_eq!(sample(EdgeMode::Wrap, 4.25), 17.5);
+        assert_eq!(sample(EdgeMode::Wrap, -3.75), 17.5);
+        assert_eq!(sample(EdgeMode::Clamp, 100.), 40.);
+        assert_eq!(sample(EdgeMode::Renormalize, 100.), 0.);
+        assert_eq!(sample(EdgeMode::Constant, 100.), 0.);
+    }
+
+    #[test]
+    fn two_dimensional_kernel() {
+        let pixels = f32_pixels(&[0., 10., 20., 30., 40., 50.]);
+        let image = TypedImageRef::new(3, 2, &pixels).unwrap();
+        let sampler = Sampler::new(&image, FilterType::Bilinear).unwrap();
+        assert_eq!(sampler.sample(1.5, 1.0).0, 25.);
+        assert_eq!(sampler.sample(1.0, 1.0).0, 20.);
+        assert_eq!(sampler.sample(1.25, 0.75).0, 15.);
+
+        let sampler = Sampler::new(&image, FilterType::Box).unwrap();
+        assert_eq!(sampler.sample(1.9, 1.2).0, 40.);
+    }
+
+    #[test]
+    fn same_result_as_interpolation() {
+        use fast_image_resize::images::TypedImage;
+        use fast_image_resize::{ResizeAlg, ResizeOptions, Resizer};
+
+        let values: Vec<f32> = (0..60 * 40).map(|i| ((i * 7919) % 256) as f32).collect();
+        let pixels = f32_pixels(&values);
+        let image = TypedImageRef::new(60, 40, &pixels).unwrap();
+        let (dst_width, dst_height) = (85, 33);
+
+        for filter_type in [
+            FilterType::Bilinear,
+            FilterType::CatmullRom,
+            FilterType::Lanczos3,
+        ] {
+            let mut dst_image = TypedImage::<F32>::new(dst_width, dst_height);
+            let options =
+                ResizeOptions::new().resize_alg(ResizeAlg::Interpolation(filter_type.clone()));
+            Resizer::new()
+                .resize_typed(&image, &mut dst_image, &options)
+                .unwrap();
+
+            let sampler = Sampler::new(&image, filter_type).unwrap();
+            let scale_x = 60. / dst_width as f64;
+            let scale_y = 40. / dst_height as f64;
+            for (i, pixel) in dst_image.pixels().iter().enumerate() {
+                let x = (i as u32 % dst_width) as f64 + 0.5;
+                let y = (i as u32 / dst_width) as f64 + 0.5;
+                let sample = sampler.sample(x * scale_x, y * scale_y);
+                assert!(
+                    (sample.0 - pixel.0).abs() < 1e-3,
+                    "{} != {}",
+                    sample.0,
+                    pixel.0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_filter() {
+        let pixels = f32_pixels(&[1., 2.]);
+        let image = TypedImageRef::new(2, 1, &pixels).unwrap();
+        assert!(matches!(
+            Sampler::new(&image, FilterType::Lanczos(0)),
+            Err(ResizeError::InvalidFilterParameters)
+        ));
+    }
+}
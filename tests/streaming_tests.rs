@@ -0,0 +1,185 @@
+use fast_image_resize::images::Image;
+use fast_image_resize::pixels::*;
+use fast_image_resize::{
+    AlphaMode, AxisFilter, ColorSpace, EdgeMode, FilterType, ImageView, PixelTrait, ResizeAlg,
+    ResizeError, ResizeOptions, Resizer, StreamingResizer,
+};
+use testing::PixelTestingExt;
+
+mod testing;
+
+fn streaming_resize<P: PixelTrait>(
+    src_image: &Image,
+    dst_width: u32,
+    dst_height: u32,
+    options: &ResizeOptions,
+) -> Vec<P> {
+    let src_view = src_image.typed_image::<P>().unwrap();
+    let mut resizer = StreamingResizer::<P>::new(
+        src_image.width(),
+        src_image.height(),
+        dst_width,
+        dst_height,
+        options,
+    )
+    .unwrap();
+
+    let mut dst_pixels = Vec::with_capacity(dst_width as usize * dst_height as usize);
+    for src_row in src_view.iter_rows(0) {
+        for dst_row in resizer.push_row(src_row).unwrap() {
+            dst_pixels.extend_from_slice(dst_row);
+        }
+    }
+    assert!(resizer.is_finished());
+    assert_eq!(resizer.emitted_rows(), dst_height);
+    dst_pixels
+}
+
+fn resize<P: PixelTrait>(
+    src_image: &Image,
+    dst_width: u32,
+    dst_height: u32,
+    options: &ResizeOptions,
+) -> Vec<P> {
+    let mut dst_image = Image::new(dst_width, dst_height, P::pixel_type());
+    Resizer::new()
+        .resize(src_image, &mut dst_image, options)
+        .unwrap();
+    let dst_view = dst_image.typed_image::<P>().unwrap();
+    dst_view.pixels().to_vec()
+}
+
+fn options_for_tests<P: PixelTrait>() -> Vec<ResizeOptions> {
+    let mut res = vec![];
+    for resize_alg in [
+        ResizeAlg::Nearest,
+        ResizeAlg::Convolution(FilterType::Lanczos3),
+        ResizeAlg::Convolution(FilterType::Mitchell),
+        ResizeAlg::Interpolation(FilterType::Bilinear),
+        ResizeAlg::PerAxisConvolution {
+            horizontal: AxisFilter::interpolation(FilterType::CatmullRom),
+            vertical: AxisFilter::convolution(FilterType::Hamming),
+        },
+        ResizeAlg::Area,
+    ] {
+        let options = ResizeOptions::new().resize_alg(resize_alg);
+        res.push(options.clone());
+        res.push(options.crop(10.5, 20., 500., 400.));
+    }
+    let options = ResizeOptions::new().color_space(ColorSpace::Srgb);
+    res.push(options.clone());
+    res.push(options.resize_alg(ResizeAlg::Nearest));
+    for (src_alpha_mode, dst_alpha_mode) in [
+        (AlphaMode::Premultiplied, AlphaMode::Straight),
+        (AlphaMode::Straight, AlphaMode::Premultiplied),
+    ] {
+        let options = ResizeOptions::new().alpha_modes(src_alpha_mode, dst_alpha_mode);
+        res.push(options.clone());
+        res.push(options.resize_alg(ResizeAlg::Nearest));
+        res.push(options.color_space(ColorSpace::Srgb));
+    }
+    let options = ResizeOptions::new().fit_into_destination_with_padding(P::default(), None);
+    res.push(options.clone());
+    res.push(options.crop(10.5, 20., 500., 400.));
+    res.push(options.resize_alg(ResizeAlg::Nearest));
+    for kernel_scale in [0.7, 1.5] {
+        res.push(ResizeOptions::new().kernel_scale(kernel_scale));
+    }
+    for edge_mode in [EdgeMode::Clamp, EdgeMode::Mirror, EdgeMode::Constant] {
+        let options = ResizeOptions::new().edge_mode(edge_mode);
+        res.push(options.clone());
+        res.push(options.crop(10.5, 20., 500., 400.));
+    }
+    res
+}
+
+const SIZES: [(u32, u32); 4] = [(255, 170), (1000, 700), (852, 300), (300, 567)];
+
+/// Results must be exactly the same as results of [Resizer] for all pixel
+/// types, including images with `u8` components that are resized by
+/// the vertical pass first.
+fn same_result_as_resizer<P: PixelTrait + PixelTestingExt + PartialEq>() {
+    let src_image = P::load_small_src_image();
+    for options in options_for_tests::<P>() {
+        for (dst_width, dst_height) in SIZES {
+            let expected = resize::<P>(&src_image, dst_width, dst_height, &options);
+            let result = streaming_resize::<P>(&src_image, dst_width, dst_height, &options);
+            assert!(
+                result == expected,
+                "result is incorrect for {:?} and size {}x{}",
+                options.algorithm,
+                dst_width,
+                dst_height,
+            );
+        }
+    }
+}
+
+#[test]
+fn same_result_as_resizer_u8x4() {
+    same_result_as_resizer::<U8x4>();
+}
+
+#[cfg(not(feature = "only_u8x4"))]
+mod not_u8x4 {
+    use super::*;
+
+    #[test]
+    fn same_result_as_resizer_u8() {
+        same_result_as_resizer::<U8>();
+    }
+
+    #[test]
+    fn same_result_as_resizer_u16x4() {
+        same_result_as_resizer::<U16x4>();
+    }
+
+    #[test]
+    fn same_result_as_resizer_f32() {
+        same_result_as_resizer::<F32>();
+    }
+}
+
+#[test]
+fn errors() {
+    type P = U8x4;
+    let mut resizer = StreamingResizer::<P>::new(4, 2, 2, 1, None).unwrap();
+    let row = vec![P::new([0; 4]); 4];
+
+    assert!(matches!(
+        resizer.push_row(&row[..3]),
+        Err(ResizeError::InvalidRowLength)
+    ));
+    assert_eq!(resizer.push_row(&row).unwrap().len(), 0);
+    assert_eq!(resizer.push_row(&row).unwrap().len(), 1);
+    assert!(resizer.is_finished());
+    assert!(matches!(
+        resizer.push_row(&row),
+        Err(ResizeError::TooManyRows)
+    ));
+}
+
+#[test]
+fn wrap_edge_mode_is_not_supported() {
+    let options = ResizeOptions::new().edge_mode(EdgeMode::Wrap);
+    let result = StreamingResizer::<U8x4>::new(4, 2, 2, 1, &options);
+    assert!(matches!(result, Err(ResizeError::UnsupportedEdgeMode)));
+}
+
+#[test]
+fn invalid_kernel_scale() {
+    let options = ResizeOptions::new().kernel_scale(0.);
+    let result = StreamingResizer::<U8x4>::new(4, 2, 2, 1, &options);
+    assert!(matches!(result, Err(ResizeError::InvalidKernelScale)));
+}
+
+#[test]
+fn empty_destination() {
+    type P = U8x4;
+    let mut resizer = StreamingResizer::<P>::new(4, 2, 0, 0, None).unwrap();
+    let row = vec![P::new([0; 4]); 4];
+    for _ in 0..2 {
+        assert_eq!(resizer.push_row(&row).unwrap().len(), 0);
+    }
+    assert!(resizer.is_finished());
+}
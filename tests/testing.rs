@@ -1,3 +1,6 @@
+// Not every test uses all of the shared helpers.
+#![allow(dead_code)]
+
 use std::fs::File;
 use std::io::BufReader;
 use std::num::NonZeroU32;
@@ -279,10 +282,6 @@ pub mod not_u8x4 {
         type ImagePixel = image::Luma<i32>;
         type Container = Vec<i32>;
 
-        fn cpu_extensions() -> Vec<CpuExtensions> {
-            vec![CpuExtensions::None]
-        }
-
         fn load_image_buffer(
             img_reader: ImageReader<BufReader<File>>,
         ) -> ImageBuffer<Self::ImagePixel, Self::Container> {
@@ -0,0 +1,256 @@
+use fast_image_resize::images::{Image, TypedImage, TypedImageRef};
+use fast_image_resize::pixels::*;
+use fast_image_resize::{
+    AffineMatrix, AlphaMode, ColorSpace, EdgeMode, FilterType, PixelType, ResizeAlg, ResizeError,
+    ResizeOptions, Resizer, Sampler,
+};
+
+const IDENTITY: AffineMatrix = [[1., 0., 0.], [0., 1., 0.]];
+
+fn u8x4_pixels(width: u32, height: u32) -> Vec<U8x4> {
+    (0..width * height)
+        .map(|i| {
+            let v = (i * 37 % 256) as u8;
+            U8x4::new([v, v.wrapping_mul(3), 255 - v, 255])
+        })
+        .collect()
+}
+
+fn warp_u8x4(
+    src_image: &TypedImageRef<U8x4>,
+    dst_width: u32,
+    dst_height: u32,
+    matrix: &AffineMatrix,
+    options: &ResizeOptions,
+) -> Vec<U8x4> {
+    let mut dst_image = TypedImage::<U8x4>::new(dst_width, dst_height);
+    Resizer::new()
+        .warp_affine_typed(src_image, &mut dst_image, matrix, options)
+        .unwrap();
+    dst_image.pixels().to_vec()
+}
+
+#[test]
+fn identity_transform() {
+    let pixels = u8x4_pixels(7, 5);
+    let src_image = TypedImageRef::new(7, 5, &pixels).unwrap();
+    for algorithm in [
+        ResizeAlg::Nearest,
+        ResizeAlg::Interpolation(FilterType::Bilinear),
+        ResizeAlg::Convolution(FilterType::CatmullRom),
+        ResizeAlg::Convolution(FilterType::Lanczos3),
+    ] {
+        let options = ResizeOptions::new().resize_alg(algorithm.clone());
+        let result = warp_u8x4(&src_image, 7, 5, &IDENTITY, &options);
+        assert_eq!(result, pixels, "{algorithm:?}");
+
+        let options = options
+            .color_space(ColorSpace::Srgb)
+            .alpha_modes(AlphaMode::Premultiplied, AlphaMode::Premultiplied);
+        let result = warp_u8x4(&src_image, 7, 5, &IDENTITY, &options);
+        assert_eq!(result, pixels, "{algorithm:?} in sRGB");
+    }
+}
+
+#[test]
+fn translation_and_rotation() {
+    let (width, height) = (6, 4);
+    let pixels = u8x4_pixels(width, height);
+    let src_image = TypedImageRef::new(width, height, &pixels).unwrap();
+    let pixel = |x: u32, y: u32| pixels[(y * width + x) as usize];
+
+    for algorithm in [
+        ResizeAlg::Nearest,
+        ResizeAlg::Interpolation(FilterType::Bilinear),
+        ResizeAlg::Convolution(FilterType::Lanczos3),
+    ] {
+        let options = ResizeOptions::new()
+            .resize_alg(algorithm.clone())
+            .edge_mode(EdgeMode::Wrap);
+
+        // Shift by 2 pixels to the right and 1 pixel down.
+        let matrix = [[1., 0., -2.], [0., 1., -1.]];
+        let result = warp_u8x4(&src_image, width, height, &matrix, &options);
+        for y in 0..height {
+            for x in 0..width {
+                let expected = pixel((x + width - 2) % width, (y + height - 1) % height);
+                assert_eq!(result[(y * width + x) as usize], expected, "{algorithm:?}");
+            }
+        }
+
+        // Rotation by 90 degrees clockwise.
+        let matrix = [[0., 1., 0.], [-1., 0., height as f64]];
+        let result = warp_u8x4(&src_image, height, width, &matrix, &options);
+        for y in 0..width {
+            for x in 0..height {
+                let expected = pixel(y, height - 1 - x);
+                assert_eq!(result[(y * height + x) as usize], expected, "{algorithm:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn pixels_outside_source_image() {
+    let pixels = u8x4_pixels(4, 4);
+    let src_image = TypedImageRef::new(4, 4, &pixels).unwrap();
+    let matrix = [[1., 0., 100.], [0., 1., 0.]];
+    for algorithm in [
+        ResizeAlg::Nearest,
+        ResizeAlg::Convolution(FilterType::Bilinear),
+    ] {
+        let options = ResizeOptions::new().resize_alg(algorithm);
+        let result = warp_u8x4(&src_image, 4, 4, &matrix, &options);
+        assert!(result.iter().all(|&p| p == U8x4::default()));
+
+        let options = options.edge_mode(EdgeMode::Clamp);
+        let result = warp_u8x4(&src_image, 4, 4, &matrix, &options);
+        for y in 0..4 {
+            assert!(result[y * 4..y * 4 + 4]
+                .iter()
+                .all(|&p| p == pixels[y * 4 + 3]));
+        }
+    }
+}
+
+/// Transformations that only scale and translate the image are done
+/// by passes of convolution, their results must be the same as
+/// results of sampling.
+#[test]
+fn scale_and_translation_like_sampler() {
+    let (width, height) = (40, 30);
+    // Smooth gradients don't overshoot the range of values
+    // between passes of convolution.
+    let pixels: Vec<U8x4> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            U8x4::new([
+                (x * 6) as u8,
+                (y * 8) as u8,
+                (x + y) as u8 * 3,
+                128 + x as u8 * 3,
+            ])
+        })
+        .collect();
+    let src_image = TypedImageRef::new(width, height, &pixels).unwrap();
+    let (dst_width, dst_height) = (20, 32);
+    let matrix = [[1.5, 0., 3.25], [0., 0.75, 2.5]];
+    // Colors multiplied by alpha are rounded between passes of convolution.
+    for (use_alpha, tolerance) in [(false, 1), (true, 3)] {
+        for filter_type in [FilterType::Bilinear, FilterType::CatmullRom] {
+            let options = ResizeOptions::new()
+                .resize_alg(ResizeAlg::Interpolation(filter_type.clone()))
+                .use_alpha(use_alpha);
+            let result = warp_u8x4(&src_image, dst_width, dst_height, &matrix, &options);
+
+            let sampler = Sampler::new(&src_image, filter_type.clone())
+                .unwrap()
+                .use_alpha(use_alpha);
+            for y in 0..dst_height {
+                for x in 0..dst_width {
+                    let src_x = matrix[0][0] * (x as f64 + 0.5) + matrix[0][2];
+                    let src_y = matrix[1][1] * (y as f64 + 0.5) + matrix[1][2];
+                    let expected = sampler.sample(src_x, src_y);
+                    let pixel = result[(y * dst_width + x) as usize];
+                    for (a, b) in pixel.0.iter().zip(expected.0) {
+                        assert!(
+                            a.abs_diff(b) <= tolerance,
+                            "{filter_type:?} ({x}, {y}): {pixel:?} != {expected:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn errors() {
+    let src_image = Image::new(4, 4, PixelType::U8x4);
+    let mut dst_image = Image::new(4, 4, PixelType::U8x4);
+    let mut resizer = Resizer::new();
+
+    let matrix = [[1., 0., f64::NAN], [0., 1., 0.]];
+    let result = resizer.warp_affine(&src_image, &mut dst_image, &matrix, None);
+    assert!(matches!(result, Err(ResizeError::InvalidTransformMatrix)));
+
+    let options = ResizeOptions::new().resize_alg(ResizeAlg::Area);
+    let result = resizer.warp_affine(&src_image, &mut dst_image, &IDENTITY, &options);
+    assert!(matches!(result, Err(ResizeError::UnsupportedAlgorithm)));
+
+    let options = ResizeOptions::new().resize_alg(ResizeAlg::Convolution(FilterType::Lanczos(0)));
+    let result = resizer.warp_affine(&src_image, &mut dst_image, &IDENTITY, &options);
+    assert!(matches!(result, Err(ResizeError::InvalidFilterParameters)));
+    // The transformation is not separable
+    let rotation = [[0., -1., 4.], [1., 0., 0.]];
+    let result = resizer.warp_affine(&src_image, &mut dst_image, &rotation, &options);
+    assert!(matches!(result, Err(ResizeError::InvalidFilterParameters)));
+
+    let options = ResizeOptions::new().kernel_scale(0.);
+    let result = resizer.warp_affine(&src_image, &mut dst_image, &IDENTITY, &options);
+    assert!(matches!(result, Err(ResizeError::InvalidKernelScale)));
+
+    let mut dst_image = Image::new(4, 4, PixelType::U8x3);
+    let result = resizer.warp_affine(&src_image, &mut dst_image, &IDENTITY, None);
+    assert!(matches!(result, Err(ResizeError::PixelTypesAreDifferent)));
+}
+
+#[cfg(not(feature = "only_u8x4"))]
+mod not_u8x4 {
+    use super::*;
+
+    fn f32_image(width: u32, height: u32) -> TypedImage<'static, F32> {
+        let pixels = (0..width * height)
+            .map(|i| F32::new(((i * 7919) % 256) as f32))
+            .collect();
+        TypedImage::from_pixels(width, height, pixels).unwrap()
+    }
+
+    /// Downscaling with a scale matrix must give the same result
+    /// as resizing because the kernel of the filter is widened.
+    #[test]
+    fn downscale_like_resizer() {
+        let src_image = f32_image(64, 48);
+        let (dst_width, dst_height) = (20, 24);
+        let mut resizer = Resizer::new();
+        for filter_type in [FilterType::Bilinear, FilterType::Lanczos3] {
+            let options = ResizeOptions::new().resize_alg(ResizeAlg::Convolution(filter_type));
+
+            let mut expected = TypedImage::<F32>::new(dst_width, dst_height);
+            resizer
+                .resize_typed(&src_image, &mut expected, &options)
+                .unwrap();
+
+            let matrix = [
+                [64. / dst_width as f64, 0., 0.],
+                [0., 48. / dst_height as f64, 0.],
+            ];
+            let mut result = TypedImage::<F32>::new(dst_width, dst_height);
+            resizer
+                .warp_affine_typed(&src_image, &mut result, &matrix, &options)
+                .unwrap();
+
+            for (a, b) in result.pixels().iter().zip(expected.pixels()) {
+                assert!((a.0 - b.0).abs() < 1e-3, "{} != {}", a.0, b.0);
+            }
+        }
+    }
+
+    #[test]
+    fn interpolation_does_not_widen_kernel() {
+        let src_image = f32_image(16, 1);
+        let matrix = [[2., 0., 0.], [0., 1., 0.]];
+        let options =
+            ResizeOptions::new().resize_alg(ResizeAlg::Interpolation(FilterType::Bilinear));
+        let mut result = TypedImage::<F32>::new(8, 1);
+        Resizer::new()
+            .warp_affine_typed(&src_image, &mut result, &matrix, &options)
+            .unwrap();
+        let src = src_image.pixels();
+        for (x, pixel) in result.pixels().iter().enumerate() {
+            // Centers of destination pixels are between two source pixels.
+            let expected = (src[x * 2].0 + src[x * 2 + 1].0) / 2.;
+            assert_eq!(pixel.0, expected);
+        }
+    }
+}